@@ -1,15 +1,60 @@
+use std::time::Duration;
 use clap::{Parser, Subcommand};
-use anyhow::Result;
+use anyhow::{Result, Context};
 use colored::Colorize;
 use nix::unistd::Uid;
 
-use crate::{device, display, grub, iommu, vfio, config, systemd, frameworks};
+use crate::{device, display, grub, iommu, lock, vfio, config, systemd, frameworks, snapshot, watch, diff, recipe, quiet, exit, drift};
+use crate::exit::Exit;
 
 /// Check if running as root (effective UID == 0)
 fn is_root() -> bool {
     Uid::effective().is_root()
 }
 
+/// Rescan the PCI bus and report any devices that appeared
+fn rescan_pci_bus() -> Result<()> {
+    println!("{}", "Rescanning PCI bus...".bright_cyan());
+    let added = device::rescan_pci_bus()?;
+
+    if added.is_empty() {
+        println!("  No new PCI devices found");
+    } else {
+        for pci in &added {
+            println!("  {} {}", "+".bright_green(), pci.bright_blue());
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Exit the process if the command is still running once `limit` has passed
+/// A sysfs write to wedged hardware can block forever and cannot be interrupted, so the
+/// watchdog thread ends the process rather than waiting for the work to return.
+fn start_watchdog(limit: Duration) {
+    std::thread::spawn(move || {
+        std::thread::sleep(limit);
+        eprintln!("{} timed out after {}s; the operation may be incomplete",
+            "Error:".bright_red(), limit.as_secs());
+        Exit::TimedOut.exit();
+    });
+}
+
+/// Names of present network interfaces whose whole name matches a regex, sorted
+fn interfaces_matching(pattern: &str) -> Result<Vec<String>> {
+    let re = regex::Regex::new(&format!("^(?:{})$", pattern))
+        .context(format!("Invalid interface pattern '{}'", pattern))?;
+
+    let mut names: Vec<String> = device::list_network_devices()?
+        .into_iter()
+        .map(|d| d.interface)
+        .filter(|name| !name.starts_with('(') && re.is_match(name))
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
 /// Require root privileges or exit with error
 fn require_root(command: &str) {
     if !is_root() {
@@ -17,8 +62,33 @@ fn require_root(command: &str) {
         eprintln!();
         eprintln!("Run with sudo:");
         eprintln!("  {}", format!("sudo vfio-tool {}", command).bright_cyan());
-        std::process::exit(4);
+        Exit::NotRoot.exit();
+    }
+}
+
+/// Parse an interface list argument: comma-separated, or `@file` with
+/// newline/comma-separated entries (`#` comments and blank lines ignored)
+fn parse_interface_list(arg: &str) -> Result<Vec<String>> {
+    let Some(path) = arg.strip_prefix('@') else {
+        return Ok(arg.split(',').map(String::from).collect());
+    };
+
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read interface list {}: {}", path, e))?;
+
+    let entries: Vec<String> = content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or(""))
+        .flat_map(|line| line.split(','))
+        .map(|entry| entry.trim().to_string())
+        .filter(|entry| !entry.is_empty())
+        .collect();
+
+    if entries.is_empty() {
+        anyhow::bail!("Interface list {} is empty", path);
     }
+
+    Ok(entries)
 }
 
 /// Comprehensive VFIO management tool for kernel bypass
@@ -26,6 +96,26 @@ fn require_root(command: &str) {
 #[command(name = "vfio-tool")]
 #[command(version, about, long_about = None)]
 pub struct Cli {
+    /// Wait for another running vfio-tool operation instead of failing
+    #[arg(long, global = true)]
+    wait: bool,
+
+    /// Print nothing on success except warnings (to stderr); show all output on failure
+    #[arg(long, global = true)]
+    quiet_success: bool,
+
+    /// Retry a bind that fails with EBUSY up to N times (default 5) before giving up
+    #[arg(long, global = true, value_name = "N", num_args = 0..=1, require_equals = true, default_missing_value = "5")]
+    retry_on_ebusy: Option<u32>,
+
+    /// Delay between EBUSY bind retries, in milliseconds
+    #[arg(long, global = true, value_name = "MS", default_value_t = 200, requires = "retry_on_ebusy")]
+    retry_delay_ms: u64,
+
+    /// Abort the whole command if it has not finished after this many seconds
+    #[arg(long, global = true, value_name = "SECS")]
+    timeout: Option<u64>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -37,44 +127,200 @@ enum Commands {
         /// Show detailed information
         #[arg(short, long)]
         verbose: bool,
+
+        /// Save a fingerprint of the current device inventory to a file
+        #[arg(long, value_name = "FILE", conflicts_with = "changed_since")]
+        snapshot: Option<String>,
+
+        /// Compare the current device inventory against a saved snapshot (exit 1 if changed)
+        #[arg(long, value_name = "FILE", visible_alias = "compare")]
+        changed_since: Option<String>,
+
+        /// Also list wireless (Wi-Fi) and WWAN devices
+        #[arg(long)]
+        include_wireless: bool,
+
+        /// Rescan the PCI bus first to pick up hot-plugged devices (requires root)
+        #[arg(long)]
+        rescan: bool,
+
+        /// Only list devices in these states (comma-separated)
+        #[arg(long, value_name = "STATUS", value_delimiter = ',', value_parser = ["vfio", "kernel", "unbound"])]
+        only: Vec<String>,
+
+        /// Stable tab-separated output for scripts (see README for the fields)
+        #[arg(long, conflicts_with_all = ["verbose", "snapshot", "changed_since"])]
+        porcelain: bool,
+    },
+
+    /// Rescan the PCI bus for hot-plugged devices, then list interfaces
+    Rescan {
+        /// Also list wireless (Wi-Fi) and WWAN devices
+        #[arg(long)]
+        include_wireless: bool,
     },
 
     /// Show system VFIO/IOMMU status
-    Status,
+    Status {
+        /// Stable `key<TAB>value` output for scripts (see README for the keys)
+        #[arg(long)]
+        porcelain: bool,
+    },
+
+    /// Print a one-line status summary (for shell prompts / tmux)
+    Summary {
+        /// Colorize the output
+        #[arg(long)]
+        color: bool,
+    },
+
+    /// Watch devices for driver/state changes
+    Watch {
+        /// Poll interval in seconds
+        #[arg(short, long, default_value = "2")]
+        interval: u64,
+
+        /// Command to run on each change ($1=interface, $2=new state; VFIO_TOOL_* env vars also set)
+        #[arg(long, value_name = "CMD", visible_alias = "output-on-change")]
+        on_change: Option<String>,
+
+        /// Output format: json (one event object per line)
+        #[arg(short, long)]
+        format: Option<String>,
+    },
 
     /// Show detailed information about a specific interface
     Info {
-        /// Interface name (e.g., enp33s0f0np0)
-        interface: String,
+        /// Comma-separated list of interfaces or PCI addresses (or @file with one per line)
+        interfaces: String,
+
+        /// Also show full details of every other device in the IOMMU group
+        #[arg(long)]
+        group_members: bool,
+
+        /// Output format: json (an array with one object per device)
+        #[arg(short, long, conflicts_with = "group_members")]
+        format: Option<String>,
     },
 
-    /// Check system readiness for VFIO
+    /// Check system readiness for VFIO (exit 0 = ready, 1 = issues found)
     Check {
         /// Automatically fix issues if possible
         #[arg(short, long)]
         fix: bool,
+
+        /// Output format: json (every check with status, remediation and reboot needs)
+        #[arg(long, conflicts_with = "fix")]
+        format: Option<String>,
     },
 
     /// Bind interface(s) to VFIO immediately
     Bind {
-        /// Comma-separated list of interfaces
+        /// Comma-separated list of interfaces (or @file with one per line)
         interfaces: String,
+
+        /// Bind even if a device carries the default route or a GPU drives the console
+        #[arg(long)]
+        force: bool,
+
+        /// Bind devices in different IOMMU groups concurrently (--parallel=N workers, default 4)
+        #[arg(long, value_name = "N", num_args = 0..=1, require_equals = true, default_missing_value = "4")]
+        parallel: Option<usize>,
+
+        /// Bind every endpoint in each interface's IOMMU group, not just the named device
+        #[arg(long, conflicts_with = "parallel")]
+        group: bool,
     },
 
     /// Unbind interface(s) from VFIO (return to kernel)
     Unbind {
-        /// Comma-separated list of interfaces
-        interfaces: String,
+        /// Comma-separated list of interfaces (or @file with one per line)
+        #[arg(required_unless_present = "restore_config")]
+        interfaces: Option<String>,
+
+        /// Instead of unbinding, return every device to its configured vfio/kernel mode
+        #[arg(long, conflicts_with = "interfaces")]
+        restore_config: bool,
+
+        /// Unbind every endpoint in each interface's IOMMU group, not just the named device
+        #[arg(long, conflicts_with = "restore_config")]
+        group: bool,
+
+        /// Skip the kernel driver reprobe. The devices stay driverless only until the next
+        /// drivers_probe or PCI rescan, which hands them back to vfio-pci (their IDs stay registered)
+        #[arg(long, conflicts_with_all = ["restore_config", "group"])]
+        no_reprobe: bool,
+    },
+
+    /// List IOMMU groups and their member devices
+    Groups {
+        /// Output format: json
+        #[arg(short, long)]
+        format: Option<String>,
+    },
+
+    /// Bind or unbind all devices in an IOMMU group
+    Group {
+        #[command(subcommand)]
+        action: GroupCommands,
+    },
+
+    /// Manage the configuration file
+    Config {
+        #[command(subcommand)]
+        action: ConfigCommands,
+    },
+
+    /// Share a binding setup between identical machines
+    Recipe {
+        #[command(subcommand)]
+        action: RecipeCommands,
     },
 
     /// Reset all VFIO bindings (unbind all)
-    Reset,
+    Reset {
+        /// Only unbind devices bound by vfio-tool since boot (leave external bindings alone)
+        #[arg(long)]
+        managed_only: bool,
+
+        /// PCI class prefix to reset, in hex (02 = network, 03 = display, 0108 = NVMe)
+        #[arg(long, default_value = "02", conflicts_with = "all_classes")]
+        class: String,
+
+        /// Unbind every vfio-pci device regardless of class
+        #[arg(long)]
+        all_classes: bool,
+
+        /// Output format: json (where each device ended up)
+        #[arg(short, long)]
+        format: Option<String>,
+    },
 
     /// Interactive configuration wizard
-    Configure,
+    Configure {
+        /// Also offer wireless (Wi-Fi) and WWAN devices
+        #[arg(long)]
+        include_wireless: bool,
+
+        /// Pre-select VFIO devices from the live bindings (default) or the config file
+        #[arg(long, value_name = "SOURCE", value_parser = ["live", "config"])]
+        defaults: Option<String>,
+
+        /// Cancel if a prompt gets no answer within this many seconds
+        #[arg(long, value_name = "SECS")]
+        timeout_secs: Option<u64>,
+    },
 
     /// Update configuration when hardware changes
-    Update,
+    Update {
+        /// Pre-select VFIO devices from the config file (default) or the live bindings
+        #[arg(long, value_name = "SOURCE", value_parser = ["live", "config"])]
+        defaults: Option<String>,
+
+        /// Cancel if a prompt gets no answer within this many seconds
+        #[arg(long, value_name = "SECS")]
+        timeout_secs: Option<u64>,
+    },
 
     /// Save configuration for specified interfaces
     Save {
@@ -85,35 +331,124 @@ enum Commands {
         /// Comma-separated list of interfaces for kernel
         #[arg(long)]
         kernel: Option<String>,
+
+        /// Detect current bindings (VFIO and kernel) and save them as the configuration
+        #[arg(long, conflicts_with_all = ["vfio", "kernel"])]
+        detect: bool,
+
+        /// Also remove stale PCI mappings (as `config prune`)
+        #[arg(long)]
+        prune: bool,
+    },
+
+    /// Import VFIO device IDs from a modprobe config (options vfio-pci ids=...)
+    ImportModprobe {
+        /// Path to modprobe config file
+        #[arg(default_value = config::MODPROBE_VFIO_CONF)]
+        path: String,
     },
 
     /// Apply saved configuration
-    Apply,
+    Apply {
+        /// Notify systemd (READY=1) once the configuration is applied, for Type=notify units
+        #[arg(long)]
+        systemd_notify: bool,
+
+        /// Bind even if a device carries the default route or a GPU drives the console
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Show devices changed out-of-band since the last successful apply (exit 1 if any)
+    Drift,
+
+    /// Show configured devices that are not in their configured mode (exit 1 if any)
+    Diff {
+        /// Bind/unbind only the differing devices
+        #[arg(long)]
+        apply: bool,
+
+        /// With --apply, print what would change without changing it
+        #[arg(long, requires = "apply")]
+        dry_run: bool,
+
+        /// With --apply, bind even if a device carries the default route or a GPU drives the console
+        #[arg(long, requires = "apply")]
+        force: bool,
+    },
 
     /// Show current configuration
     ShowConfig,
 
     /// Install systemd service for persistence
-    Install,
+    Install {
+        /// Where to install the vfio-tool binary used by the service
+        #[arg(long, value_name = "PATH", default_value = systemd::SERVICE_BINARY)]
+        install_path: String,
+
+        /// Install without prompts (exit 0=installed, 2=no config, 3=config test failed)
+        #[arg(short, long)]
+        yes: bool,
+
+        /// With --yes, skip applying the configuration before installing
+        #[arg(long, requires = "yes")]
+        no_test: bool,
+    },
 
     /// Uninstall systemd service
     Uninstall,
 
     /// Generate standalone bash script
     GenerateScript {
-        /// Output file path
+        /// Output file path (written executable; "-" for stdout)
         #[arg(short, long)]
         output: Option<String>,
+
+        /// Output format: json (the script's operations as a structured plan)
+        #[arg(short, long)]
+        format: Option<String>,
+
+        /// Write PCI addresses resolved now into the script instead of looking them up at run time
+        #[arg(long = "static", conflicts_with = "format")]
+        static_pci: bool,
     },
 
     /// Explain what would happen to an interface
     Explain {
         /// Interface name
         interface: String,
+
+        /// Output format: json
+        #[arg(short, long)]
+        format: Option<String>,
+
+        /// Preview a specific operation regardless of the current state
+        #[arg(long, value_name = "OP", value_parser = ["bind", "unbind"])]
+        what_if: Option<String>,
     },
 
     /// Validate configuration file
-    Validate,
+    Validate {
+        /// Output format: json
+        #[arg(short, long)]
+        format: Option<String>,
+    },
+
+    /// Verify /dev/vfio/vfio can be opened and queried (VFIO_GET_API_VERSION)
+    CheckContainer,
+
+    /// List every command's exit codes and what they mean
+    ExitCodes {
+        /// Output format: json
+        #[arg(long)]
+        format: Option<String>,
+    },
+
+    /// End-to-end test that a device is usable via VFIO (exit 1 if not)
+    Verify {
+        /// Interface name or PCI address
+        interface: String,
+    },
 
     /// Check if specific interfaces exist and are in the correct mode
     CheckInterfaces {
@@ -127,12 +462,42 @@ enum Commands {
 
         /// Comma-separated list of interfaces to check for existence only (deprecated, use --vfio or --kernel)
         interfaces: Option<String>,
+
+        /// Treat missing interfaces as warnings; only wrong-mode interfaces fail
+        #[arg(long, visible_alias = "assume-interface-exists")]
+        warn_missing: bool,
+
+        /// Regex: every interface whose whole name matches must be in VFIO mode
+        #[arg(long, value_name = "REGEX")]
+        vfio_pattern: Option<String>,
+
+        /// Regex: every interface whose whole name matches must be in kernel mode
+        #[arg(long, value_name = "REGEX")]
+        kernel_pattern: Option<String>,
+
+        /// Pass when a pattern matches no interface (by default that fails as not found)
+        #[arg(long)]
+        allow_empty_pattern: bool,
     },
 
     /// Ensure specific interfaces are in VFIO mode (bind if needed)
     EnsureVfio {
-        /// Comma-separated list of interfaces
+        /// Comma-separated list of interfaces or PCI addresses (or @file with one per line)
         interfaces: String,
+
+        /// Bind devices in different IOMMU groups concurrently (--parallel=N workers, default 4)
+        #[arg(long, value_name = "N", num_args = 0..=1, require_equals = true, default_missing_value = "4")]
+        parallel: Option<usize>,
+
+        /// Bind even if a device carries the default route or a GPU drives the console
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Load a VFIO-related kernel module (vfio, vfio-pci, vfio_iommu_type1, iommufd)
+    LoadModule {
+        /// Module name
+        name: String,
     },
 
     /// Setup GRUB for IOMMU support
@@ -140,9 +505,27 @@ enum Commands {
         /// Skip confirmation prompts
         #[arg(short, long)]
         yes: bool,
+
+        /// Also offer GPU passthrough parameters (intel_iommu=igfx_off, video=efifb:off)
+        #[arg(long)]
+        gpu: bool,
     },
 
-    /// Show devices for specific framework (dpdk, rdma, tcpdirect, openonload, efvi, spdk, vpp, xdp)
+    /// Put a device in the state a framework needs (VFIO for DPDK/SPDK/VPP, kernel for RDMA/XDP/...)
+    Use {
+        /// Interface name or PCI address
+        interface: String,
+
+        /// Framework to prepare the device for (as for `show`)
+        #[arg(long = "for", value_name = "FRAMEWORK")]
+        framework: String,
+
+        /// Bind even if the interface carries the default route or a GPU drives the console
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Show devices for specific framework (dpdk, rdma, tcpdirect, openonload, efvi, spdk, vpp, xdp, or custom)
     Show {
         /// Framework name
         framework: String,
@@ -151,40 +534,245 @@ enum Commands {
         #[arg(short, long)]
         capable: bool,
 
-        /// Output format: json or args (comma-separated)
+        /// Output format: json, args (comma-separated) or tree (capable devices by IOMMU group and readiness)
         #[arg(short, long)]
         format: Option<String>,
+
+        /// Exit 0 only if enough devices are ready (1 otherwise), for CI gating
+        #[arg(long, conflicts_with_all = ["capable", "format"])]
+        check: bool,
+
+        /// With --check, the minimum number of ready devices required (default: 1)
+        #[arg(long, value_name = "N", requires = "check")]
+        min: Option<usize>,
+
+        /// Print only the number of ready devices (capable devices with --capable)
+        #[arg(long, conflicts_with_all = ["format", "check"])]
+        count: bool,
+
+        /// Report which of the framework's userspace tools and libraries are missing
+        #[arg(long, conflicts_with_all = ["capable", "format", "check", "count"])]
+        missing_deps: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum GroupCommands {
+    /// Bind every endpoint device in the group to VFIO
+    Bind {
+        /// IOMMU group ID
+        group: u32,
+
+        /// Bind even if a device carries the default route or a GPU drives the console
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Unbind every endpoint device in the group from VFIO
+    Unbind {
+        /// IOMMU group ID
+        group: u32,
+    },
+}
+
+#[derive(Subcommand)]
+enum RecipeCommands {
+    /// Export the config as a recipe matched by vendor:device and slot, not interface names
+    Export {
+        /// Recipe file to write
+        file: String,
+
+        /// Description stored in the recipe (e.g. the server model)
+        #[arg(short, long)]
+        description: Option<String>,
+    },
+
+    /// Match a recipe against this machine's devices and bind/unbind them
+    Apply {
+        /// Recipe file to read
+        file: String,
+
+        /// Show the matches and planned changes without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Bind even if a matched device carries the default route or a GPU drives the console
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Rewrite the config file in the current schema version
+    Migrate,
+
+    /// Set a config key (e.g. devices.vfio enp1s0,enp2s0 or options.set_permissions false)
+    Set {
+        /// Dotted key (devices.vfio, devices.kernel, devices.pci_mappings.<iface>, options.<name>)
+        key: String,
+
+        /// New value: comma-separated list, true/false, or PCI address
+        value: String,
+    },
+
+    /// Reset a config key to its default
+    Unset {
+        /// Dotted key, as for `config set`
+        key: String,
+    },
+
+    /// Remove PCI mappings whose device and interface no longer exist
+    Prune,
+
+    /// Check a config file's syntax and consistency without looking at hardware (for CI)
+    ValidateFile {
+        /// Config file to check
+        path: String,
     },
 }
 
 impl Cli {
     pub fn run(self) -> Result<()> {
+        if self.quiet_success && !quiet::is_child() {
+            let notify = matches!(self.command, Commands::Apply { systemd_notify: true, .. });
+            quiet::run_captured(notify);
+        }
+
+        let wait = self.wait;
+
+        if let Some(secs) = self.timeout {
+            start_watchdog(Duration::from_secs(secs));
+        }
+
+        if let Some(attempts) = self.retry_on_ebusy {
+            vfio::set_ebusy_retry(vfio::EbusyRetry {
+                attempts,
+                delay: Duration::from_millis(self.retry_delay_ms),
+            });
+        }
+
         match self.command {
-            Commands::List { verbose } => {
-                let devices = device::list_network_devices()?;
-                display::show_device_table(&devices, verbose)?;
+            Commands::List { verbose, snapshot: snapshot_file, changed_since, include_wireless, rescan, only, porcelain } => {
+                if rescan {
+                    require_root("list --rescan");
+                    let _lock = lock::acquire(wait)?;
+                    rescan_pci_bus()?;
+                }
+
+                let mut devices = if include_wireless {
+                    device::list_all_network_devices()?
+                } else {
+                    device::list_network_devices()?
+                };
+
+                if !only.is_empty() {
+                    devices.retain(|d| only.contains(&display::status_to_string(&d.status)));
+                }
+
+                if let Some(path) = snapshot_file {
+                    let snap = snapshot::Snapshot::from_devices(&devices);
+                    snapshot::save_snapshot(&path, &snap)?;
+                    println!("{} Snapshot of {} device(s) written to: {}",
+                        "✓".bright_green(), snap.devices.len(), path);
+                } else if let Some(path) = changed_since {
+                    let old = snapshot::load_snapshot(&path)?;
+                    let changes = snapshot::compare(&old, &snapshot::Snapshot::from_devices(&devices));
+                    display::show_inventory_changes(&changes)?;
+                    if !changes.is_empty() {
+                        Exit::InventoryChanged.exit();
+                    }
+                } else if porcelain {
+                    display::show_device_porcelain(&devices);
+                } else {
+                    display::show_device_table(&devices, verbose)?;
+                }
+            }
+
+            Commands::Status { porcelain } => {
+                let status = iommu::get_system_status()?;
+                if porcelain {
+                    display::show_system_status_porcelain(&status);
+                } else {
+                    display::show_system_status(&status)?;
+                }
             }
 
-            Commands::Status => {
+            Commands::Rescan { include_wireless } => {
+                require_root("rescan");
+                {
+                    let _lock = lock::acquire(wait)?;
+                    rescan_pci_bus()?;
+                }
+
+                let devices = if include_wireless {
+                    device::list_all_network_devices()?
+                } else {
+                    device::list_network_devices()?
+                };
+                display::show_device_table(&devices, false)?;
+            }
+
+            Commands::Summary { color } => {
                 let status = iommu::get_system_status()?;
-                display::show_system_status(&status)?;
+                let (total, bound) = device::count_network_devices();
+                display::show_summary(&status, total, bound, color);
             }
 
-            Commands::Info { interface } => {
-                let device = device::get_device_info(&interface)?;
-                display::show_device_details(&device)?;
+            Commands::Watch { interval, on_change, format } => {
+                let json = format.as_deref() == Some("json");
+                watch::watch(interval, on_change.as_deref(), json)?;
+            }
+
+            Commands::Info { interfaces, group_members, format } => {
+                let config = config::load_config().ok();
+                let devices = parse_interface_list(&interfaces)?
+                    .iter()
+                    .map(|target| vfio::resolve_device(target.trim(), &config))
+                    .collect::<Result<Vec<_>>>()?;
+
+                if format.as_deref() == Some("json") {
+                    display::show_device_details_json(&devices)?;
+                    return Ok(());
+                }
+
+                for (i, device) in devices.iter().enumerate() {
+                    if i > 0 {
+                        println!();
+                    }
+                    display::show_device_details(device)?;
+                    if group_members {
+                        display::show_group_members(device)?;
+                    }
+                }
             }
 
-            Commands::Check { fix } => {
+            Commands::Check { fix, format } => {
                 if fix {
                     require_root("check --fix");
                 }
 
+                if format.as_deref() == Some("json") {
+                    let issues = iommu::check_system()?;
+                    display::show_issues_json(&issues)?;
+                    if !issues.is_empty() {
+                        Exit::IssuesFound.exit();
+                    }
+                    return Ok(());
+                }
+
                 println!("{}", "Checking system readiness...".bright_cyan());
                 let issues = iommu::check_system()?;
 
                 if issues.is_empty() {
                     println!("{}", "✓ System is ready for VFIO!".bright_green());
+                    if let Ok(cfg) = config::load_config() {
+                        let early = config::check_early_binding(&cfg);
+                        if !early.is_empty() {
+                            println!();
+                            config::show_early_binding_issues(&early);
+                        }
+                    }
                     return Ok(());
                 }
 
@@ -198,52 +786,227 @@ impl Cli {
                     println!("{}", "✓ Issues fixed!".bright_green());
                 } else {
                     println!("\n{}", "Run with --fix to automatically resolve issues.".bright_yellow());
+                    Exit::IssuesFound.exit();
                 }
             }
 
-            Commands::Bind { interfaces } => {
+            Commands::Bind { interfaces, force, parallel, group } => {
                 require_root("bind");
-                let ifaces: Vec<&str> = interfaces.split(',').collect();
-                vfio::bind_interfaces(&ifaces)?;
+                let _lock = lock::acquire(wait)?;
+                let iface_list = parse_interface_list(&interfaces)?;
+                let ifaces: Vec<&str> = iface_list.iter().map(String::as_str).collect();
+                match parallel {
+                    Some(jobs) => vfio::bind_parallel(&ifaces, jobs, force)?,
+                    None if group => vfio::bind_interface_groups(&ifaces, force)?,
+                    None => vfio::bind_interfaces(&ifaces, force)?,
+                }
             }
 
-            Commands::Unbind { interfaces } => {
+            Commands::Unbind { interfaces, restore_config, group, no_reprobe } => {
                 require_root("unbind");
-                let ifaces: Vec<&str> = interfaces.split(',').collect();
-                vfio::unbind_interfaces(&ifaces)?;
+                let _lock = lock::acquire(wait)?;
+
+                if restore_config {
+                    let cfg = config::load_config()?;
+                    vfio::restore_config(&cfg)?;
+                } else if let Some(interfaces) = interfaces {
+                    let iface_list = parse_interface_list(&interfaces)?;
+                    let ifaces: Vec<&str> = iface_list.iter().map(String::as_str).collect();
+                    if group {
+                        vfio::unbind_interface_groups(&ifaces)?;
+                    } else {
+                        vfio::unbind_interfaces(&ifaces, !no_reprobe)?;
+                    }
+                }
+            }
+
+            Commands::Groups { format } => {
+                let groups = iommu::list_iommu_groups()?;
+                match format.as_deref() {
+                    Some("json") => display::show_iommu_groups_json(&groups)?,
+                    _ => display::show_iommu_groups(&groups)?,
+                }
+            }
+
+            Commands::Group { action } => {
+                require_root("group");
+                let _lock = lock::acquire(wait)?;
+                match action {
+                    GroupCommands::Bind { group, force } => vfio::bind_group(group, force)?,
+                    GroupCommands::Unbind { group } => vfio::unbind_group(group)?,
+                }
             }
 
-            Commands::Reset => {
+            Commands::Recipe { action } => match action {
+                RecipeCommands::Export { file, description } => {
+                    let cfg = config::load_config()?;
+                    let recipe = recipe::from_config(&cfg, description.as_deref().unwrap_or_default())?;
+                    recipe::save_recipe(&file, &recipe)?;
+                    println!("{}", format!("✓ Recipe with {} device(s) written to {}", recipe.devices.len(), file).bright_green());
+                }
+
+                RecipeCommands::Apply { file, dry_run, force } => {
+                    if !dry_run {
+                        require_root("recipe apply");
+                    }
+                    let _lock = if dry_run { None } else { Some(lock::acquire(wait)?) };
+                    let recipe = recipe::load_recipe(&file)?;
+                    recipe::apply_recipe(&recipe, dry_run, force)?;
+                }
+            },
+
+            Commands::Config { action } => match action {
+                ConfigCommands::Migrate => {
+                    require_root("config migrate");
+                    let _lock = lock::acquire(wait)?;
+                    config::migrate_config()?;
+                }
+
+                ConfigCommands::Set { key, value } => {
+                    require_root("config set");
+                    let _lock = lock::acquire(wait)?;
+                    config::set_config_value(&key, &value)?;
+                }
+
+                ConfigCommands::Unset { key } => {
+                    require_root("config unset");
+                    let _lock = lock::acquire(wait)?;
+                    config::unset_config_value(&key)?;
+                }
+
+                ConfigCommands::Prune => {
+                    require_root("config prune");
+                    let _lock = lock::acquire(wait)?;
+                    config::prune_config()?;
+                }
+
+                ConfigCommands::ValidateFile { path } => {
+                    let issues = match config::lint_config_file(&path) {
+                        Ok(issues) => issues,
+                        Err(e) => {
+                            eprintln!("{} {:#}", "✗".bright_red(), e);
+                            Exit::ValidationFailed.exit();
+                        }
+                    };
+
+                    if issues.is_empty() {
+                        println!("{}", format!("✓ {} is valid", path).bright_green());
+                    } else {
+                        for issue in &issues {
+                            println!("  {} {}", "✗".bright_red(), issue);
+                        }
+                        println!();
+                        println!("{}", format!("{} issue(s) in {}", issues.len(), path).bright_red());
+                        Exit::ValidationFailed.exit();
+                    }
+                }
+            },
+
+            Commands::Reset { managed_only, class, all_classes, format } => {
                 require_root("reset");
-                vfio::unbind_all()?;
+                let _lock = lock::acquire(wait)?;
+                let class = (!all_classes).then_some(class.as_str());
+                let json = format.as_deref() == Some("json");
+                let results = vfio::unbind_all(managed_only, class, json)?;
+
+                if json {
+                    let output: Vec<serde_json::Value> = results.iter().map(vfio::ResetResult::to_json).collect();
+                    println!("{}", serde_json::to_string_pretty(&output)?);
+                }
+
+                let failed = results.iter().filter(|r| !r.succeeded()).count();
+                if failed > 0 {
+                    if !json {
+                        println!();
+                        println!("{}", format!("⚠ {} device(s) did not return to a kernel driver", failed).bright_yellow());
+                    }
+                    Exit::ResetIncomplete.exit();
+                }
             }
 
-            Commands::Configure => {
+            Commands::Configure { include_wireless, defaults, timeout_secs } => {
                 require_root("configure");
-                config::interactive_configure()?;
+                let _lock = lock::acquire(wait)?;
+                let defaults = defaults.as_deref().map(str::parse::<config::SelectionDefaults>).transpose()?
+                    .unwrap_or(config::SelectionDefaults::Live);
+                config::interactive_configure(include_wireless, defaults, timeout_secs.map(Duration::from_secs))?;
             }
 
-            Commands::Update => {
+            Commands::Update { defaults, timeout_secs } => {
                 require_root("update");
-                config::interactive_update()?;
+                let _lock = lock::acquire(wait)?;
+                let defaults = defaults.as_deref().map(str::parse::<config::SelectionDefaults>).transpose()?
+                    .unwrap_or(config::SelectionDefaults::Config);
+                config::interactive_update(defaults, timeout_secs.map(Duration::from_secs))?;
             }
 
-            Commands::Save { vfio: vfio_list, kernel } => {
+            Commands::Save { vfio: vfio_list, kernel, detect, prune } => {
                 require_root("save");
-                let vfio_ifaces = vfio_list
-                    .map(|s| s.split(',').map(String::from).collect())
-                    .unwrap_or_default();
-                let kernel_ifaces = kernel
-                    .map(|s| s.split(',').map(String::from).collect())
-                    .unwrap_or_default();
+                let _lock = lock::acquire(wait)?;
+
+                if detect {
+                    config::save_detected_config()?;
+                } else {
+                    let vfio_ifaces = vfio_list
+                        .map(|s| s.split(',').map(String::from).collect())
+                        .unwrap_or_default();
+                    let kernel_ifaces = kernel
+                        .map(|s| s.split(',').map(String::from).collect())
+                        .unwrap_or_default();
+
+                    config::save_config(vfio_ifaces, kernel_ifaces)?;
+                }
+
+                if prune {
+                    println!();
+                    config::prune_config()?;
+                }
+            }
 
-                config::save_config(vfio_ifaces, kernel_ifaces)?;
+            Commands::ImportModprobe { path } => {
+                require_root("import-modprobe");
+                let _lock = lock::acquire(wait)?;
+                config::import_modprobe(&path)?;
             }
 
-            Commands::Apply => {
+            Commands::Apply { systemd_notify, force } => {
                 require_root("apply");
+                let _lock = lock::acquire(wait)?;
                 let cfg = config::load_config()?;
-                vfio::apply_config(&cfg)?;
+                vfio::apply_config(&cfg, force)?;
+                if let Err(e) = drift::record_applied(&cfg) {
+                    eprintln!("{} Could not record the applied state: {:#}", "⚠".bright_yellow(), e);
+                }
+                if systemd_notify {
+                    systemd::notify_ready()?;
+                }
+            }
+
+            Commands::Drift => {
+                let Some(applied) = drift::load_applied()? else {
+                    anyhow::bail!("No apply recorded since boot - run 'sudo vfio-tool apply' first");
+                };
+                let cfg = config::load_config().ok();
+                if drift::check_drift(&applied, cfg.as_ref())? {
+                    Exit::Drifted.exit();
+                }
+            }
+
+            Commands::Diff { apply, dry_run, force } => {
+                if apply && !dry_run {
+                    require_root("diff --apply");
+                }
+                let _lock = if apply && !dry_run { Some(lock::acquire(wait)?) } else { None };
+
+                let cfg = config::load_config()?;
+                let delta = diff::compute(&cfg);
+                diff::show(&delta);
+
+                if apply {
+                    diff::converge(&cfg, &delta, dry_run, force)?;
+                } else if !delta.deltas.is_empty() || !delta.missing.is_empty() {
+                    Exit::OutOfSync.exit();
+                }
             }
 
             Commands::ShowConfig => {
@@ -251,53 +1014,166 @@ impl Cli {
                 display::show_config(&cfg)?;
             }
 
-            Commands::Install => {
+            Commands::Install { install_path, yes, no_test } => {
                 require_root("install");
-                systemd::install_service()?;
+                let _lock = lock::acquire(wait)?;
+
+                if !yes {
+                    systemd::install_service(&install_path)?;
+                    return Ok(());
+                }
+
+                match systemd::install(&install_path, !no_test)? {
+                    systemd::InstallResult::Installed => systemd::print_service_installed(),
+                    systemd::InstallResult::AlreadyInstalled => {
+                        println!("{}", "✓ VFIO systemd service already installed".bright_green());
+                    }
+                    systemd::InstallResult::ConfigMissing => {
+                        eprintln!("{}", "✗ No valid configuration found (run 'vfio-tool configure' first)".bright_red());
+                        Exit::ConfigMissing.exit();
+                    }
+                    systemd::InstallResult::TestFailed(e) => {
+                        eprintln!("{}", "✗ Failed to apply configuration, service not installed".bright_red());
+                        eprintln!("Error: {}", e);
+                        Exit::TestFailed.exit();
+                    }
+                }
             }
 
             Commands::Uninstall => {
                 require_root("uninstall");
-                systemd::uninstall_service()?;
+                if systemd::uninstall_service()? == systemd::UninstallResult::NotInstalled {
+                    println!("{}", "VFIO systemd service is not installed".bright_yellow());
+                }
             }
 
-            Commands::GenerateScript { output } => {
+            Commands::GenerateScript { output, format, static_pci } => {
                 let cfg = config::load_config()?;
-                let script = systemd::generate_bash_script(&cfg)?;
+
+                if format.as_deref() == Some("json") {
+                    let plan = serde_json::to_string_pretty(&systemd::generate_plan_json(&cfg))?;
+                    match output.as_deref() {
+                        Some(path) if path != "-" => {
+                            std::fs::write(path, plan + "\n")
+                                .context(format!("Failed to write plan to {}", path))?;
+                            println!("Plan written to: {}", path);
+                        }
+                        _ => println!("{}", plan),
+                    }
+                    return Ok(());
+                }
+
+                let script = systemd::generate_bash_script(&cfg, static_pci)?;
 
                 if let Some(path) = output {
-                    std::fs::write(&path, script)?;
-                    println!("Script written to: {}", path);
+                    if systemd::write_script(&path, &script)? {
+                        println!("Script written to: {}", path);
+                    }
                 } else {
                     println!("{}", script);
                 }
             }
 
-            Commands::Explain { interface } => {
+            Commands::Explain { interface, format, what_if } => {
                 let device = device::get_device_info(&interface)?;
-                display::explain_device(&device)?;
+                let what_if = what_if.as_deref().map(|op| match op {
+                    "unbind" => display::ExplainAction::Unbind,
+                    _ => display::ExplainAction::Bind,
+                });
+                match format.as_deref() {
+                    Some("json") => display::explain_device_json(&device, what_if)?,
+                    _ => display::explain_device(&device, what_if)?,
+                }
+            }
+
+            Commands::Validate { format } => {
+                let result = config::load_config().and_then(|cfg| {
+                    let issues = config::validate_config(&cfg)?;
+                    Ok((cfg, issues))
+                });
+                let (cfg, issues) = match result {
+                    Ok(r) => r,
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        eprintln!();
+                        eprintln!("{}", "Validation failed.".bright_red());
+                        Exit::ValidationFailed.exit();
+                    }
+                };
+
+                let json = format.as_deref() == Some("json");
+                if json {
+                    display::show_validation_json(&issues)?;
+                } else {
+                    config::show_validation(&cfg, &issues);
+                }
+
+                if issues.iter().any(config::ValidationIssue::is_failure) {
+                    if !json {
+                        eprintln!();
+                        eprintln!("{}", "Validation failed.".bright_red());
+                    }
+                    Exit::ValidationFailed.exit();
+                }
+            }
+
+            Commands::CheckContainer => {
+                match iommu::probe_container() {
+                    Ok(probe) => display::show_container_probe(&probe)?,
+                    Err(e) => {
+                        eprintln!("{} {}", "✗".bright_red(), e);
+                        Exit::ContainerUnusable.exit();
+                    }
+                }
             }
 
-            Commands::Validate => {
-                if let Err(e) = config::validate_config() {
-                    eprintln!("{}", e);
-                    eprintln!();
-                    eprintln!("{}", "Validation failed.".bright_red());
-                    std::process::exit(2);
+            Commands::ExitCodes { format } => {
+                match format.as_deref() {
+                    Some("json") => exit::show_exit_codes_json()?,
+                    _ => exit::show_exit_codes(),
                 }
             }
 
-            Commands::CheckInterfaces { vfio, kernel, interfaces } => {
+            Commands::Verify { interface } => {
+                if !vfio::verify(&interface)? {
+                    Exit::VerifyFailed.exit();
+                }
+            }
+
+            Commands::CheckInterfaces { vfio, kernel, interfaces, warn_missing, vfio_pattern, kernel_pattern, allow_empty_pattern } => {
+                // Expand patterns to the interfaces present now
+                let mut matched = Vec::new();
+                for (flag, pattern) in [("--vfio-pattern", &vfio_pattern), ("--kernel-pattern", &kernel_pattern)] {
+                    let Some(pattern) = pattern else {
+                        matched.push(Vec::new());
+                        continue;
+                    };
+                    let names = match interfaces_matching(pattern) {
+                        Ok(names) => names,
+                        Err(e) => {
+                            eprintln!("{:#}", e);
+                            Exit::UsageError.exit();
+                        }
+                    };
+                    if names.is_empty() && !allow_empty_pattern {
+                        eprintln!("{} No interfaces match {} '{}'", "✗".bright_red(), flag, pattern);
+                        Exit::InterfaceNotFound.exit();
+                    }
+                    matched.push(names);
+                }
+
                 // Parse interface lists
-                let vfio_list: Vec<&str> = vfio
+                let mut vfio_list: Vec<&str> = vfio
                     .as_ref()
                     .map(|s| s.split(',').collect())
                     .unwrap_or_default();
+                vfio_list.extend(matched[0].iter().map(String::as_str));
 
-                let kernel_list: Vec<&str> = kernel
+                let mut kernel_list: Vec<&str> = kernel
                     .as_ref()
                     .map(|s| s.split(',').collect())
                     .unwrap_or_default();
+                kernel_list.extend(matched[1].iter().map(String::as_str));
 
                 // For backward compatibility: if interfaces arg provided without flags
                 let existence_list: Vec<&str> = interfaces
@@ -306,69 +1182,166 @@ impl Cli {
                     .unwrap_or_default();
 
                 if vfio_list.is_empty() && kernel_list.is_empty() && existence_list.is_empty() {
+                    if vfio_pattern.is_some() || kernel_pattern.is_some() {
+                        println!("{}", "✓ No interfaces match the patterns".bright_green());
+                        Exit::Success.exit();
+                    }
                     eprintln!("{}", "Error: No interfaces specified".bright_red());
                     eprintln!("Usage:");
                     eprintln!("  vfio-tool check-interfaces --vfio <list>");
                     eprintln!("  vfio-tool check-interfaces --kernel <list>");
                     eprintln!("  vfio-tool check-interfaces --vfio <list> --kernel <list>");
-                    std::process::exit(3);
+                    Exit::UsageError.exit();
                 }
 
-                match vfio::check_interfaces_with_mode(&vfio_list, &kernel_list, &existence_list) {
-                    Ok(()) => std::process::exit(0),
+                match vfio::check_interfaces_with_mode(&vfio_list, &kernel_list, &existence_list, warn_missing) {
+                    Ok(()) => Exit::Success.exit(),
                     Err(e) => {
                         eprintln!("{}", e);
                         // Exit code based on error type
                         if e.to_string().contains("not found") || e.to_string().contains("INTERFACE NOT FOUND") {
-                            std::process::exit(1);
+                            Exit::InterfaceNotFound.exit();
                         } else if e.to_string().contains("not in") || e.to_string().contains("wrong mode") || e.to_string().contains("not all required") {
-                            std::process::exit(2);
+                            Exit::WrongMode.exit();
                         } else {
-                            std::process::exit(3);
+                            Exit::OtherError.exit();
                         }
                     }
                 }
             }
 
-            Commands::EnsureVfio { interfaces } => {
+            Commands::EnsureVfio { interfaces, parallel, force } => {
                 require_root("ensure-vfio");
-                let iface_list: Vec<&str> = interfaces.split(',').collect();
-                match vfio::ensure_vfio(&iface_list) {
-                    Ok(()) => std::process::exit(0),
+                let _lock = lock::acquire(wait)?;
+                let iface_list = match parse_interface_list(&interfaces) {
+                    Ok(list) => list,
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        Exit::UsageError.exit();
+                    }
+                };
+                let iface_list: Vec<&str> = iface_list.iter().map(String::as_str).collect();
+                let result = match parallel {
+                    Some(jobs) => vfio::bind_parallel(&iface_list, jobs, force),
+                    None => vfio::ensure_vfio(&iface_list, force),
+                };
+                match result {
+                    Ok(()) => Exit::Success.exit(),
                     Err(e) => {
                         eprintln!("{}", e);
                         // Exit code based on error type
                         if e.to_string().contains("not found") || e.to_string().contains("INTERFACE NOT FOUND") {
-                            std::process::exit(1);
+                            Exit::InterfaceNotFound.exit();
                         } else if e.to_string().contains("Failed to bind") {
-                            std::process::exit(2);
+                            Exit::BindFailed.exit();
                         } else {
-                            std::process::exit(3);
+                            Exit::OtherError.exit();
                         }
                     }
                 }
             }
 
-            Commands::SetupGrub { yes } => {
+            Commands::LoadModule { name } => {
+                require_root("load-module");
+                let _lock = lock::acquire(wait)?;
+                vfio::load_module(&name)?;
+            }
+
+            Commands::SetupGrub { yes, gpu } => {
                 require_root("setup-grub");
-                grub::setup_iommu(yes)?;
+                grub::setup_iommu(yes, gpu)?;
             }
 
-            Commands::Show { framework, capable, format } => {
-                let fw = frameworks::Framework::from_str(&framework)
-                    .ok_or_else(|| anyhow::anyhow!("Unknown framework: {}\nSupported: dpdk, rdma, tcpdirect, openonload, efvi, spdk, vpp, xdp", framework))?;
+            Commands::Use { interface, framework, force } => {
+                require_root("use");
+                let _lock = lock::acquire(wait)?;
+                let fw: frameworks::Framework = framework.parse()?;
+                vfio::use_for_framework(&interface, &fw, force)?;
+            }
+
+            Commands::Show { framework, capable, format, check, min, count, missing_deps } => {
+                let fw: frameworks::Framework = framework.parse()?;
+
+                if check {
+                    let required = min.unwrap_or(1);
+                    // One scan for both counts, so a device vanishing in between can't skew them
+                    let capable = frameworks::get_capable_devices(&fw)?;
+                    let ready = capable.iter().filter(|d| d.is_ready).count();
 
-                let devices = if capable {
-                    frameworks::get_capable_devices(fw)?
+                    if ready >= required {
+                        println!("{}", format!("✓ {} device(s) ready for {}", ready, fw.name()).bright_green());
+                        Exit::Success.exit();
+                    }
+
+                    let pending = capable.len() - ready;
+                    eprintln!("{}", format!("✗ {} of {} required device(s) ready for {}", ready, required, fw.name()).bright_red());
+                    if pending > 0 {
+                        let action = if fw.requires_vfio() { "bind" } else { "unbind" };
+                        eprintln!("  {} capable device(s) need: vfio-tool {} <interface>", pending, action);
+                    } else {
+                        eprintln!("  No other {}-capable devices found", fw.name());
+                    }
+                    Exit::NotEnoughReady.exit();
+                }
+
+                if missing_deps {
+                    display::show_framework_dependencies(&fw, &frameworks::check_dependencies(&fw));
+                    return Ok(());
+                }
+
+                if count {
+                    let devices = if capable {
+                        frameworks::get_capable_devices(&fw)?
+                    } else {
+                        frameworks::get_available_devices(&fw)?
+                    };
+                    println!("{}", devices.len());
+                    return Ok(());
+                }
+
+                // The tree groups by readiness, so it always includes not-ready devices
+                let capable = capable || format.as_deref() == Some("tree");
+                let (devices, incapable) = if capable {
+                    (frameworks::get_capable_devices(&fw)?, frameworks::get_incapable_devices(&fw)?)
                 } else {
-                    frameworks::get_available_devices(fw)?
+                    (frameworks::get_available_devices(&fw)?, Vec::new())
                 };
 
                 let format_type = format.as_deref().unwrap_or("default");
-                display::show_framework_devices(fw, &devices, capable, format_type)?;
+                display::show_framework_devices(&fw, &devices, &incapable, capable, format_type)?;
             }
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn interface_list_splits_commas() {
+        assert_eq!(parse_interface_list("eth0,0000:03:00.0").unwrap(), ["eth0", "0000:03:00.0"]);
+    }
+
+    #[test]
+    fn interface_list_file_skips_comments_and_blanks() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(file, "# uplinks stay on the kernel\neth0\n\n  eth1, eth2  # bonded pair\n#eth3\n").unwrap();
+
+        let arg = format!("@{}", file.path().display());
+        assert_eq!(parse_interface_list(&arg).unwrap(), ["eth0", "eth1", "eth2"]);
+    }
+
+    #[test]
+    fn interface_list_file_must_name_something() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(file, "# nothing here\n\n").unwrap();
+
+        let arg = format!("@{}", file.path().display());
+        assert!(parse_interface_list(&arg).is_err());
+        assert!(parse_interface_list("@/nonexistent/vfio-tool-list").is_err());
+    }
+}
@@ -1,20 +1,35 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::collections::HashMap;
+use std::time::Duration;
 use anyhow::{Result, Context};
 use serde::{Deserialize, Serialize};
 use colored::Colorize;
 use dialoguer::{MultiSelect, Confirm};
 
-use crate::device;
+use crate::{device, grub, prompt};
 
 const CONFIG_DIR: &str = "/etc/vfio-tool";
 const CONFIG_FILE: &str = "/etc/vfio-tool/config.toml";
 
+/// Current config schema version
+/// 1: original format (no `version` key)
+/// 2: adds `version`, `options.enforce_kernel_list` and `[frameworks.*]`
+/// Fields added with a serde default need no bump; bump only for changes `upgrade_config` has to migrate
+pub const CONFIG_VERSION: u32 = 2;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
+    /// Schema version (1 when absent)
+    #[serde(default = "default_config_version")]
+    pub version: u32,
+
     pub devices: DeviceConfig,
     pub options: Options,
+
+    /// User-defined frameworks for `vfio-tool show`, keyed by name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub frameworks: HashMap<String, FrameworkDefinition>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,10 +40,24 @@ pub struct DeviceConfig {
     #[serde(default)]
     pub kernel: Vec<String>,
 
+    /// IOMMU groups whose endpoint devices `apply` binds to VFIO, whatever their interface names
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<u32>,
+
     /// Mapping of interface names to PCI addresses
     /// This allows us to unbind by interface name even when interface disappeared
     #[serde(default)]
     pub pci_mappings: HashMap<String, String>,
+
+    /// IOMMU group of each interface when the config was saved
+    /// Groups can shift after BIOS updates or topology changes
+    #[serde(default)]
+    pub iommu_groups: HashMap<String, u32>,
+
+    /// Other names of each interface (ifalias and kernel altnames), captured at bind time
+    /// A VFIO-bound device has no netdev, so this is the only record of them
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub altnames: HashMap<String, Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,120 +67,608 @@ pub struct Options {
 
     #[serde(default = "default_true")]
     pub auto_load_module: bool,
+
+    /// Also return devices in the kernel list to their kernel drivers on apply
+    #[serde(default)]
+    pub enforce_kernel_list: bool,
+
+    /// Don't fail validation for configured interfaces that are transiently gone
+    /// (netdev missing but the mapped PCI device still present)
+    #[serde(default)]
+    pub allow_absent: bool,
+}
+
+/// How applications refer to a device under a custom framework
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReferenceType {
+    Pci,
+    Interface,
+    Rdma,
+}
+
+/// A `[frameworks.<name>]` section describing an in-house framework
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrameworkDefinition {
+    /// true: device must be bound to vfio-pci, false: device must use its kernel driver
+    pub requires_vfio: bool,
+
+    #[serde(default = "default_reference_type")]
+    pub reference_type: ReferenceType,
+
+    /// Capable vendor IDs (e.g. "0x8086"); empty means any vendor
+    #[serde(default)]
+    pub vendors: Vec<String>,
+
+    /// Capable vendor:device pairs (e.g. "0x8086:0x1572"); empty means any device
+    #[serde(default)]
+    pub devices: Vec<String>,
 }
 
 fn default_true() -> bool {
     true
 }
 
+fn default_config_version() -> u32 {
+    1
+}
+
+fn default_reference_type() -> ReferenceType {
+    ReferenceType::Pci
+}
+
 impl Default for Config {
     fn default() -> Self {
         Config {
+            version: CONFIG_VERSION,
             devices: DeviceConfig {
                 vfio: Vec::new(),
                 kernel: Vec::new(),
+                groups: Vec::new(),
                 pci_mappings: HashMap::new(),
+                iommu_groups: HashMap::new(),
+                altnames: HashMap::new(),
             },
             options: Options {
                 set_permissions: true,
                 auto_load_module: true,
+                enforce_kernel_list: false,
+                allow_absent: false,
             },
+            frameworks: HashMap::new(),
         }
     }
 }
 
-/// Validate configuration against current hardware
-pub fn validate_config() -> Result<()> {
-    println!("{}", "Validating configuration against current hardware...".bright_cyan());
-    println!();
+/// With `options.allow_absent`, the PCI address of a configured interface whose netdev is
+/// gone but whose mapped PCI device still exists (driver reload, firmware reset, link flap)
+fn absent_known<'a>(cfg: &'a Config, iface: &str) -> Option<&'a str> {
+    if !cfg.options.allow_absent {
+        return None;
+    }
 
-    let cfg = load_config()?;
-    let current_devices = device::list_network_devices()?;
+    cfg.devices.pci_mappings
+        .get(iface)
+        .map(String::as_str)
+        .filter(|pci| Path::new("/sys/bus/pci/devices").join(pci).exists())
+}
 
-    let current_interfaces: Vec<String> = current_devices
-        .iter()
-        .map(|d| d.interface.clone())
-        .collect();
+/// A mismatch between the configuration and current hardware
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationIssue {
+    /// Configured for VFIO but not present
+    MissingVfio { interface: String },
+    /// Configured for the kernel but not present
+    MissingKernel { interface: String },
+    /// Present but in neither device list
+    Unconfigured { interface: String, pci_address: String, driver: Option<String> },
+    /// Listed in both the vfio and kernel lists
+    Overlap { interface: String },
+    /// Netdev gone but its PCI device is present; tolerated with `options.allow_absent`
+    AbsentKnown { interface: String, pci_address: String },
+    /// Configured for VFIO, but `pci-stub.ids=` on the kernel command line claims its ID at boot
+    StubClaimed { interface: String, id: String },
+    /// Listed under `devices.groups` but no such IOMMU group exists
+    MissingGroup { group: u32 },
+}
 
-    let mut has_issues = false;
+impl ValidationIssue {
+    /// Short machine-readable name, e.g. "missing_vfio"
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ValidationIssue::MissingVfio { .. } => "missing_vfio",
+            ValidationIssue::MissingKernel { .. } => "missing_kernel",
+            ValidationIssue::Unconfigured { .. } => "unconfigured",
+            ValidationIssue::Overlap { .. } => "overlap",
+            ValidationIssue::AbsentKnown { .. } => "absent_known",
+            ValidationIssue::StubClaimed { .. } => "stub_claimed",
+            ValidationIssue::MissingGroup { .. } => "missing_group",
+        }
+    }
 
-    // Check VFIO devices
-    println!("{}", "VFIO devices (kernel bypass):".bright_green());
-    if cfg.devices.vfio.is_empty() {
-        println!("  {}", "(none)".bright_black());
-    } else {
-        for iface in &cfg.devices.vfio {
-            if current_interfaces.contains(iface) {
-                println!("  ✓ {} - {}", iface, "present".bright_green());
-            } else {
-                println!("  ✗ {} - {}", iface, "MISSING".bright_red().bold());
-                has_issues = true;
-            }
+    /// The interface concerned (None for issues about a whole IOMMU group)
+    pub fn interface(&self) -> Option<&str> {
+        match self {
+            ValidationIssue::MissingVfio { interface }
+            | ValidationIssue::MissingKernel { interface }
+            | ValidationIssue::Unconfigured { interface, .. }
+            | ValidationIssue::Overlap { interface }
+            | ValidationIssue::AbsentKnown { interface, .. }
+            | ValidationIssue::StubClaimed { interface, .. } => Some(interface),
+            ValidationIssue::MissingGroup { .. } => None,
         }
     }
 
-    println!();
+    /// Whether this issue makes validation fail (known-absent devices don't)
+    pub fn is_failure(&self) -> bool {
+        !matches!(self, ValidationIssue::AbsentKnown { .. })
+    }
 
-    // Check kernel devices
-    println!("{}", "Kernel devices (normal networking):".bright_yellow());
-    if cfg.devices.kernel.is_empty() {
-        println!("  {}", "(none)".bright_black());
-    } else {
-        for iface in &cfg.devices.kernel {
-            if current_interfaces.contains(iface) {
-                println!("  ✓ {} - {}", iface, "present".bright_green());
-            } else {
-                println!("  ✗ {} - {}", iface, "MISSING".bright_red().bold());
-                has_issues = true;
-            }
+    pub fn describe(&self) -> String {
+        match self {
+            ValidationIssue::MissingVfio { interface } => format!("{} is configured for VFIO but not present", interface),
+            ValidationIssue::MissingKernel { interface } => format!("{} is configured for the kernel but not present", interface),
+            ValidationIssue::Unconfigured { interface, pci_address, .. } => format!("{} ({}) is not in the configuration", interface, pci_address),
+            ValidationIssue::Overlap { interface } => format!("{} is listed as both vfio and kernel", interface),
+            ValidationIssue::AbsentKnown { interface, pci_address } => format!("{} is absent (known, {} present)", interface, pci_address),
+            ValidationIssue::StubClaimed { interface, id } => format!("{} ({}) is claimed by pci-stub at boot (pci-stub.ids= on the kernel command line)", interface, id),
+            ValidationIssue::MissingGroup { group } => format!("IOMMU group {} is configured but does not exist", group),
         }
     }
+}
 
-    println!();
+/// Validate configuration against current hardware
+/// Returns every mismatch found; nothing is printed
+pub fn validate_config(cfg: &Config) -> Result<Vec<ValidationIssue>> {
+    // Include wireless so explicitly configured Wi-Fi/WWAN devices aren't reported missing
+    let current_devices = device::list_all_network_devices()?;
+
+    let current_interfaces: Vec<&String> = current_devices
+        .iter()
+        .map(|d| &d.interface)
+        .collect();
+
+    let mut issues = Vec::new();
+
+    for iface in cfg.devices.vfio.iter().filter(|i| cfg.devices.kernel.contains(i)) {
+        issues.push(ValidationIssue::Overlap { interface: iface.clone() });
+    }
+
+    let configured = cfg.devices.vfio.iter().map(|i| (i, true))
+        .chain(cfg.devices.kernel.iter().map(|i| (i, false)));
+
+    for (iface, vfio) in configured {
+        if current_interfaces.contains(&iface) {
+            continue;
+        }
+
+        let issue = match absent_known(cfg, iface) {
+            Some(pci) => ValidationIssue::AbsentKnown { interface: iface.clone(), pci_address: pci.to_string() },
+            None if vfio => ValidationIssue::MissingVfio { interface: iface.clone() },
+            None => ValidationIssue::MissingKernel { interface: iface.clone() },
+        };
+        issues.push(issue);
+    }
 
-    // Show unconfigured interfaces
     let configured_interfaces: Vec<&String> = cfg.devices.vfio
         .iter()
         .chain(cfg.devices.kernel.iter())
         .collect();
 
-    let unconfigured: Vec<&device::NetworkDevice> = current_devices
+    // Known-absent interfaces show up under their PCI address; they're configured, not new
+    let absent_pcis: Vec<&str> = configured_interfaces
         .iter()
-        .filter(|d| !configured_interfaces.contains(&&d.interface))
+        .filter_map(|iface| absent_known(cfg, iface))
         .collect();
 
+    for dev in current_devices
+        .iter()
+        .filter(|d| !configured_interfaces.contains(&&d.interface))
+        .filter(|d| !absent_pcis.contains(&d.pci_address.as_str()))
+        .filter(|d| !d.is_wireless())
+    {
+        issues.push(ValidationIssue::Unconfigured {
+            interface: dev.interface.clone(),
+            pci_address: dev.pci_address.clone(),
+            driver: dev.driver.clone(),
+        });
+    }
+
+    for &group in &cfg.devices.groups {
+        if device::get_iommu_group_devices(group).is_err() {
+            issues.push(ValidationIssue::MissingGroup { group });
+        }
+    }
+
+    // pci-stub binds these at boot, so vfio-pci (and `apply`) never get them
+    for conflict in pci_stub_conflicts(Some(cfg)) {
+        for iface in &conflict.interfaces {
+            issues.push(ValidationIssue::StubClaimed { interface: iface.clone(), id: conflict.id.clone() });
+        }
+    }
+
+    Ok(issues)
+}
+
+/// Print one configured device list with each entry's validation state
+fn show_validated_list(interfaces: &[String], issues: &[ValidationIssue]) {
+    if interfaces.is_empty() {
+        println!("  {}", "(none)".bright_black());
+        return;
+    }
+
+    for iface in interfaces {
+        match issues.iter().find(|i| i.interface() == Some(iface.as_str()) && !matches!(i.kind(), "overlap" | "stub_claimed")) {
+            Some(ValidationIssue::AbsentKnown { pci_address, .. }) => {
+                println!("  ~ {} - {}", iface, format!("absent (known, {} present)", pci_address).bright_yellow());
+            }
+            Some(_) => println!("  ✗ {} - {}", iface, "MISSING".bright_red().bold()),
+            None => println!("  ✓ {} - {}", iface, "present".bright_green()),
+        }
+    }
+}
+
+/// Print validation results, followed by group-change and early-binding warnings
+pub fn show_validation(cfg: &Config, issues: &[ValidationIssue]) {
+    println!("{}", "Validating configuration against current hardware...".bright_cyan());
+    println!();
+
+    println!("{}", "VFIO devices (kernel bypass):".bright_green());
+    show_validated_list(&cfg.devices.vfio, issues);
+    println!();
+
+    println!("{}", "Kernel devices (normal networking):".bright_yellow());
+    show_validated_list(&cfg.devices.kernel, issues);
+    println!();
+
+    let overlaps: Vec<&ValidationIssue> = issues.iter().filter(|i| i.kind() == "overlap").collect();
+    if !overlaps.is_empty() {
+        println!("{}", "Listed as both vfio and kernel:".bright_red());
+        for issue in overlaps {
+            println!("  ✗ {}", issue.interface().unwrap_or_default());
+        }
+        println!();
+    }
+
+    let missing_groups: Vec<&ValidationIssue> = issues.iter().filter(|i| i.kind() == "missing_group").collect();
+    if !missing_groups.is_empty() {
+        println!("{}", "Configured IOMMU groups:".bright_red());
+        for issue in missing_groups {
+            println!("  ✗ {}", issue.describe());
+        }
+        println!("  Check {} for the current group numbers.", "vfio-tool groups".bright_cyan());
+        println!();
+    }
+
+    let stub_claimed: Vec<&ValidationIssue> = issues.iter().filter(|i| i.kind() == "stub_claimed").collect();
+    if !stub_claimed.is_empty() {
+        println!("{}", "Claimed by pci-stub at boot:".bright_red());
+        for issue in stub_claimed {
+            println!("  ✗ {}", issue.describe());
+        }
+        println!("  Remove these IDs from pci-stub.ids= on the kernel command line.");
+        println!();
+    }
+
+    let unconfigured: Vec<&ValidationIssue> = issues.iter().filter(|i| i.kind() == "unconfigured").collect();
     if !unconfigured.is_empty() {
         println!("{}", "New/unconfigured interfaces:".bright_cyan());
-        for dev in &unconfigured {
-            println!("  + {} ({} - {})",
-                dev.interface.bright_white(),
-                dev.pci_address,
-                dev.driver.as_deref().unwrap_or("no driver"));
+        for issue in unconfigured {
+            if let ValidationIssue::Unconfigured { interface, pci_address, driver } = issue {
+                println!("  + {} ({} - {})",
+                    interface.bright_white(),
+                    pci_address,
+                    driver.as_deref().unwrap_or("no driver"));
+            }
         }
         println!();
         println!("{}", "These interfaces are not in your configuration.".bright_yellow());
-        has_issues = true;
     }
 
     println!();
 
-    if has_issues {
+    // Warnings only: the devices can still be bound by `apply`
+    show_group_changes(cfg);
+    show_early_binding_issues(&check_early_binding(cfg));
+
+    if issues.iter().any(ValidationIssue::is_failure) {
         println!("{}", "⚠ Configuration does not match current hardware".bright_yellow().bold());
         println!();
         println!("Options:");
         println!("  1. Run {} to reconfigure", "sudo vfio-tool configure".bright_cyan());
         println!("  2. Run {} to add/remove interfaces", "sudo vfio-tool update".bright_cyan());
         println!("  3. Manually edit {}", CONFIG_FILE.bright_cyan());
-        return Err(anyhow::anyhow!("Configuration validation failed"));
     } else {
         println!("{}", "✓ Configuration matches current hardware".bright_green().bold());
     }
+}
 
-    Ok(())
+/// Warn about configured devices whose IOMMU group differs from the one recorded at save time
+/// A moved device may now share its group with different devices that must be co-bound
+fn show_group_changes(cfg: &Config) {
+    let mut moved: Vec<(&String, u32, u32)> = cfg.devices.iommu_groups
+        .iter()
+        .filter_map(|(iface, &recorded)| {
+            let pci = cfg.devices.pci_mappings.get(iface)?;
+            let current = device::get_iommu_group(pci)?;
+            (current != recorded).then_some((iface, recorded, current))
+        })
+        .collect();
+
+    if moved.is_empty() {
+        return;
+    }
+    moved.sort();
+
+    println!("{}", "IOMMU group changes since config was saved:".bright_yellow());
+    for (iface, recorded, current) in &moved {
+        println!("  {} {} moved from group {} to group {}", "⚠".bright_yellow(), iface, recorded, current);
+    }
+    println!("  Check {} for devices that must now be bound together,", "vfio-tool groups".bright_cyan());
+    println!("  then run {} to record the new groups.", "sudo vfio-tool update".bright_cyan());
+    println!();
+}
+
+/// A configured VFIO device that will not be claimed by vfio-pci early at boot
+#[derive(Debug, Clone)]
+pub enum EarlyBindingIssue {
+    /// vendor:device not in the vfio-pci `ids=` list, so early binding won't happen
+    NotInIds { interface: String, id: String },
+    /// In `ids=`, but the native driver has no `softdep <driver> pre: vfio-pci`
+    /// and may load first and claim the device
+    MissingSoftdep { interface: String, id: String, driver: String },
+}
+
+impl EarlyBindingIssue {
+    pub fn describe(&self) -> String {
+        match self {
+            EarlyBindingIssue::NotInIds { interface, id } => format!(
+                "{} ({}) is not in vfio-pci ids= - it will come up on its kernel driver until 'apply' runs",
+                interface, id),
+            EarlyBindingIssue::MissingSoftdep { interface, id, driver } => format!(
+                "{} ({}) is in vfio-pci ids= but {} may claim it first - add 'softdep {} pre: vfio-pci' to /etc/modprobe.d/",
+                interface, id, driver, driver),
+        }
+    }
+}
+
+/// Collect vfio-pci `ids=` from the loaded module, the kernel command line and /etc/modprobe.d
+fn active_vfio_pci_ids() -> Vec<(String, String)> {
+    let mut ids = Vec::new();
+
+    let mut add_list = |list: &str| {
+        for id in list.trim().split(',') {
+            let mut fields = id.split(':');
+            if let (Some(vendor), Some(dev_id)) = (fields.next(), fields.next()) {
+                let pair = (vendor.to_lowercase(), dev_id.to_lowercase());
+                if !ids.contains(&pair) {
+                    ids.push(pair);
+                }
+            }
+        }
+    };
+
+    // Not exposed by every kernel (the parameter is often mode 0)
+    if let Ok(list) = fs::read_to_string("/sys/module/vfio_pci/parameters/ids") {
+        add_list(&list);
+    }
+
+    for pair in grub::cmdline_ids("vfio-pci") {
+        if !ids.contains(&pair) {
+            ids.push(pair);
+        }
+    }
+
+    for content in modprobe_d_contents() {
+        for (vendor, dev_id) in parse_modprobe_ids(&content) {
+            if !ids.contains(&(vendor.clone(), dev_id.clone())) {
+                ids.push((vendor, dev_id));
+            }
+        }
+    }
+
+    ids
+}
+
+/// Contents of every /etc/modprobe.d/*.conf file
+fn modprobe_d_contents() -> Vec<String> {
+    fs::read_dir("/etc/modprobe.d")
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().extension().is_some_and(|ext| ext == "conf"))
+                .filter_map(|e| fs::read_to_string(e.path()).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Modules with `softdep <module> pre: vfio-pci` (names normalized to underscores)
+fn vfio_softdep_modules() -> Vec<String> {
+    modprobe_d_contents()
+        .iter()
+        .flat_map(|content| parse_softdeps(content))
+        .collect()
+}
+
+/// Modules that one modprobe.d file makes load vfio-pci first
+fn parse_softdeps(content: &str) -> Vec<String> {
+    let mut modules = Vec::new();
+
+    for line in content.lines() {
+        let mut parts = line.split_whitespace();
+        if parts.next() != Some("softdep") {
+            continue;
+        }
+        let Some(module) = parts.next() else { continue };
+
+        let mut in_pre = false;
+        for part in parts {
+            match part {
+                "pre:" => in_pre = true,
+                "post:" => in_pre = false,
+                "vfio-pci" | "vfio_pci" if in_pre => modules.push(module.replace('-', "_")),
+                _ => {}
+            }
+        }
+    }
+
+    modules
+}
+
+/// Lowercase vendor/device ID pair of a PCI device, without the 0x prefixes
+fn device_id_pair(pci: &str) -> Option<(String, String)> {
+    let (vendor, dev_id) = device::get_vendor_device_id(pci).ok()?;
+    Some((
+        vendor.trim_start_matches("0x").to_lowercase(),
+        dev_id.trim_start_matches("0x").to_lowercase(),
+    ))
+}
+
+/// A device ID that `pci-stub.ids=` on the kernel command line claims at boot
+/// although it is meant for vfio-pci
+#[derive(Debug, Clone, PartialEq)]
+pub struct StubConflict {
+    pub id: String,
+    /// Also in `vfio-pci.ids=`: whichever driver registers first gets the device
+    pub in_vfio_ids: bool,
+    /// Configured VFIO interfaces with this ID
+    pub interfaces: Vec<String>,
+}
+
+impl StubConflict {
+    pub fn describe(&self) -> String {
+        let mut reasons = Vec::new();
+        if self.in_vfio_ids {
+            reasons.push("also in vfio-pci.ids=".to_string());
+        }
+        if !self.interfaces.is_empty() {
+            reasons.push(format!("configured for VFIO: {}", self.interfaces.join(", ")));
+        }
+        format!("{} is in pci-stub.ids= ({})", self.id, reasons.join("; "))
+    }
+}
+
+/// IDs in the command line's `pci-stub.ids=` that also appear in `vfio-pci.ids=`
+/// or belong to a device configured for VFIO
+pub fn pci_stub_conflicts(cfg: Option<&Config>) -> Vec<StubConflict> {
+    let stub_ids = grub::cmdline_ids("pci-stub");
+    if stub_ids.is_empty() {
+        return Vec::new();
+    }
+
+    let vfio_ids = grub::cmdline_ids("vfio-pci");
+    let configured: Vec<(&String, (String, String))> = cfg
+        .map(|c| c.devices.vfio
+            .iter()
+            .filter_map(|iface| {
+                let pci = c.devices.pci_mappings.get(iface)?;
+                Some((iface, device_id_pair(pci)?))
+            })
+            .collect())
+        .unwrap_or_default();
+
+    stub_ids
+        .into_iter()
+        .filter_map(|pair| {
+            let interfaces: Vec<String> = configured
+                .iter()
+                .filter(|(_, p)| *p == pair)
+                .map(|(iface, _)| iface.to_string())
+                .collect();
+            let in_vfio_ids = vfio_ids.contains(&pair);
+
+            (in_vfio_ids || !interfaces.is_empty()).then(|| StubConflict {
+                id: format!("{}:{}", pair.0, pair.1),
+                in_vfio_ids,
+                interfaces,
+            })
+        })
+        .collect()
+}
+
+/// Check configured VFIO devices against the vfio-pci early-binding setup
+/// Returns nothing when no `ids=` list is in use (binding is left to `apply`/the service)
+pub fn check_early_binding(cfg: &Config) -> Vec<EarlyBindingIssue> {
+    let ids = active_vfio_pci_ids();
+    if ids.is_empty() {
+        return Vec::new();
+    }
+
+    let softdeps = vfio_softdep_modules();
+    let mut issues = Vec::new();
+
+    for iface in &cfg.devices.vfio {
+        let Some(pci) = cfg.devices.pci_mappings.get(iface) else { continue };
+        let Some(pair) = device_id_pair(pci) else { continue };
+        let id = format!("{}:{}", pair.0, pair.1);
+
+        if !ids.contains(&pair) {
+            issues.push(EarlyBindingIssue::NotInIds { interface: iface.clone(), id });
+            continue;
+        }
+
+        // Only a native driver currently owning the device tells us who wins the race
+        if let Some(driver) = device::get_driver(pci)
+            && driver != "vfio-pci" && !softdeps.contains(&driver.replace('-', "_"))
+        {
+            issues.push(EarlyBindingIssue::MissingSoftdep { interface: iface.clone(), id, driver });
+        }
+    }
+
+    issues
+}
+
+/// Print early-binding warnings
+pub fn show_early_binding_issues(issues: &[EarlyBindingIssue]) {
+    if issues.is_empty() {
+        return;
+    }
+
+    println!("{}", "Early binding (vfio-pci ids=):".bright_yellow());
+    for issue in issues {
+        println!("  {} {}", "⚠".bright_yellow(), issue.describe());
+    }
+    println!();
+}
+
+/// Where the wizards pre-populate the VFIO selection from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SelectionDefaults {
+    /// Devices currently bound to vfio-pci
+    Live,
+    /// Devices listed under `devices.vfio` in the config file
+    Config,
+}
+
+impl std::str::FromStr for SelectionDefaults {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "live" => Ok(SelectionDefaults::Live),
+            "config" => Ok(SelectionDefaults::Config),
+            _ => anyhow::bail!("Unknown selection defaults '{}' (expected live or config)", s),
+        }
+    }
+}
+
+impl SelectionDefaults {
+    /// Pre-selection for each device; `Config` selects nothing when there is no config
+    fn for_devices(self, devices: &[device::NetworkDevice], config: Option<&Config>) -> Vec<bool> {
+        match (self, config) {
+            (SelectionDefaults::Live, _) => devices.iter().map(|d| d.is_vfio_bound()).collect(),
+            (SelectionDefaults::Config, Some(cfg)) => devices
+                .iter()
+                .map(|d| cfg.devices.vfio.contains(&d.interface))
+                .collect(),
+            (SelectionDefaults::Config, None) => vec![false; devices.len()],
+        }
+    }
 }
 
 /// Interactive configuration update (preserves existing config where possible)
-pub fn interactive_update() -> Result<()> {
+pub fn interactive_update(defaults_from: SelectionDefaults, timeout: Option<Duration>) -> Result<()> {
     println!("{}", "═══════════════════════════════════════".bright_cyan());
     println!("{}", "    Update VFIO Configuration".bright_cyan().bold());
     println!("{}", "═══════════════════════════════════════".bright_cyan());
@@ -237,10 +754,12 @@ pub fn interactive_update() -> Result<()> {
             println!("{}", "✓ No hardware changes detected".bright_green());
             println!();
 
-            let should_continue = Confirm::new()
-                .with_prompt("Continue anyway to modify configuration?")
-                .default(false)
-                .interact()?;
+            let should_continue = prompt::with_timeout(timeout, || {
+                Confirm::new()
+                    .with_prompt("Continue anyway to modify configuration?")
+                    .default(false)
+                    .interact()
+            })?;
 
             if !should_continue {
                 println!("Update cancelled.");
@@ -297,20 +816,17 @@ pub fn interactive_update() -> Result<()> {
         ))
         .collect();
 
-    // Pre-select based on existing config
-    let defaults: Vec<bool> = if let Some(ref cfg) = existing_cfg {
-        devices
-            .iter()
-            .map(|d| cfg.devices.vfio.contains(&d.interface))
-            .collect()
-    } else {
-        vec![false; devices.len()]
-    };
+    let defaults = defaults_from.for_devices(&devices, existing_cfg.as_ref());
 
-    let selections = MultiSelect::new()
-        .items(&options)
-        .defaults(&defaults)
-        .interact()?;
+    let selections = {
+        let (options, defaults) = (options.clone(), defaults.clone());
+        prompt::with_timeout(timeout, move || {
+            MultiSelect::new()
+                .items(&options)
+                .defaults(&defaults)
+                .interact()
+        })?
+    };
 
     let vfio_interfaces: Vec<String> = selections
         .iter()
@@ -357,6 +873,8 @@ pub fn interactive_update() -> Result<()> {
         Options {
             set_permissions: true,
             auto_load_module: true,
+            enforce_kernel_list: false,
+            allow_absent: false,
         }
     };
 
@@ -372,23 +890,27 @@ pub fn interactive_update() -> Result<()> {
     println!();
 
     // Ask to apply immediately
-    let should_apply = Confirm::new()
-        .with_prompt("Apply updated configuration now?")
-        .default(true)
-        .interact()?;
+    let should_apply = prompt::with_timeout(timeout, || {
+        Confirm::new()
+            .with_prompt("Apply updated configuration now?")
+            .default(true)
+            .interact()
+    })?;
 
     if should_apply {
         println!();
         use crate::vfio;
         let cfg = load_config()?;
-        vfio::apply_config(&cfg)?;
+        vfio::apply_config(&cfg, false)?;
     }
 
     // Ask about persistence
-    let should_install = Confirm::new()
-        .with_prompt("Update systemd service to use new configuration?")
-        .default(true)
-        .interact()?;
+    let should_install = prompt::with_timeout(timeout, || {
+        Confirm::new()
+            .with_prompt("Update systemd service to use new configuration?")
+            .default(true)
+            .interact()
+    })?;
 
     if should_install {
         println!();
@@ -400,15 +922,45 @@ pub fn interactive_update() -> Result<()> {
     Ok(())
 }
 
+/// Warn when only some ports of a multi-port card were selected for VFIO
+fn warn_partial_cards(devices: &[device::NetworkDevice], selections: &[usize]) {
+    let mut slots: Vec<&str> = devices
+        .iter()
+        .filter_map(|d| device::pci_slot(&d.pci_address))
+        .collect();
+    slots.sort();
+    slots.dedup();
+
+    for slot in slots {
+        let ports: Vec<(usize, &device::NetworkDevice)> = devices
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| device::pci_slot(&d.pci_address) == Some(slot))
+            .collect();
+        let selected = ports.iter().filter(|(i, _)| selections.contains(i)).count();
+
+        if ports.len() > 1 && selected > 0 && selected < ports.len() {
+            let names: Vec<&str> = ports.iter().map(|(_, d)| d.interface.as_str()).collect();
+            println!("{} Only {} of {} ports selected on card {} ({}); partial binding may not behave as expected",
+                "⚠".bright_yellow(), selected, ports.len(), slot, names.join(", "));
+        }
+    }
+}
+
 /// Interactive configuration wizard
-pub fn interactive_configure() -> Result<()> {
+/// With `timeout`, an unanswered prompt cancels the wizard before anything is saved
+pub fn interactive_configure(include_wireless: bool, defaults_from: SelectionDefaults, timeout: Option<Duration>) -> Result<()> {
     println!("{}", "═══════════════════════════════════════".bright_cyan());
     println!("{}", "    VFIO Configuration Wizard".bright_cyan().bold());
     println!("{}", "═══════════════════════════════════════".bright_cyan());
     println!();
 
-    // Get all network devices
-    let devices = device::list_network_devices()?;
+    // Get all network devices (wireless/WWAN only on request)
+    let devices = if include_wireless {
+        device::list_all_network_devices()?
+    } else {
+        device::list_network_devices()?
+    };
 
     if devices.is_empty() {
         println!("{}", "No network devices found.".bright_red());
@@ -444,6 +996,11 @@ pub fn interactive_configure() -> Result<()> {
             println!("    Speed: {}", speed);
         }
 
+        let siblings = device::get_sibling_functions(&dev.pci_address);
+        if !siblings.is_empty() {
+            println!("    Same card as: {}", siblings.join(", ").bright_black());
+        }
+
         println!();
     }
 
@@ -463,12 +1020,17 @@ pub fn interactive_configure() -> Result<()> {
     println!("{}", "Select interfaces to bind to VFIO for kernel bypass:".bright_cyan());
     println!("{}", "(Use Space to select, Enter to confirm)".bright_black());
 
-    let defaults: Vec<bool> = devices.iter().map(|d| d.is_vfio_bound()).collect();
+    let defaults = defaults_from.for_devices(&devices, load_config().ok().as_ref());
 
-    let selections = MultiSelect::new()
-        .items(&items)
-        .defaults(&defaults)
-        .interact()?;
+    let selections = {
+        let (items, defaults) = (items.clone(), defaults.clone());
+        prompt::with_timeout(timeout, move || {
+            MultiSelect::new()
+                .items(&items)
+                .defaults(&defaults)
+                .interact()
+        })?
+    };
 
     let vfio_interfaces: Vec<String> = selections
         .iter()
@@ -482,6 +1044,8 @@ pub fn interactive_configure() -> Result<()> {
         .map(|(_, d)| d.interface.clone())
         .collect();
 
+    warn_partial_cards(&devices, &selections);
+
     println!();
     println!("{}", "Configuration:".bright_cyan());
     println!();
@@ -490,20 +1054,26 @@ pub fn interactive_configure() -> Result<()> {
     println!();
 
     // Options
-    let apply_now = Confirm::new()
-        .with_prompt("Apply changes immediately?")
-        .default(true)
-        .interact()?;
-
-    let make_persistent = Confirm::new()
-        .with_prompt("Make persistent (install systemd service)?")
-        .default(true)
-        .interact()?;
-
-    let set_permissions = Confirm::new()
-        .with_prompt("Set /dev/vfio/* permissions for non-root access?")
-        .default(true)
-        .interact()?;
+    let apply_now = prompt::with_timeout(timeout, || {
+        Confirm::new()
+            .with_prompt("Apply changes immediately?")
+            .default(true)
+            .interact()
+    })?;
+
+    let make_persistent = prompt::with_timeout(timeout, || {
+        Confirm::new()
+            .with_prompt("Make persistent (install systemd service)?")
+            .default(true)
+            .interact()
+    })?;
+
+    let set_permissions = prompt::with_timeout(timeout, || {
+        Confirm::new()
+            .with_prompt("Set /dev/vfio/* permissions for non-root access?")
+            .default(true)
+            .interact()
+    })?;
 
     // Save configuration
     save_config_with_options(vfio_interfaces.clone(), kernel_interfaces, set_permissions)?;
@@ -514,13 +1084,13 @@ pub fn interactive_configure() -> Result<()> {
         println!("{}", "Applying configuration...".bright_cyan());
 
         let vfio_refs: Vec<&str> = vfio_interfaces.iter().map(String::as_str).collect();
-        crate::vfio::bind_interfaces(&vfio_refs)?;
+        crate::vfio::bind_interfaces(&vfio_refs, false)?;
     }
 
     // Install service if requested
     if make_persistent {
         println!();
-        crate::systemd::install_service()?;
+        crate::systemd::install_service(crate::systemd::SERVICE_BINARY)?;
     }
 
     println!();
@@ -534,6 +1104,158 @@ pub fn save_config(vfio: Vec<String>, kernel: Vec<String>) -> Result<()> {
     save_config_with_options(vfio, kernel, true)
 }
 
+/// Save configuration from the current live state of the system
+/// (VFIO-bound devices become the vfio list, kernel-bound devices the kernel list)
+pub fn save_detected_config() -> Result<()> {
+    println!("{}", "Detecting current device bindings...".bright_cyan());
+    println!();
+
+    let devices = device::list_network_devices()?;
+
+    let mut vfio = Vec::new();
+    let mut kernel = Vec::new();
+    let mut unnamed_vfio = 0;
+
+    for dev in &devices {
+        // Devices without a known interface name are shown as "(<pci-address>)"
+        // and cannot be referenced from the config
+        if dev.interface.starts_with('(') {
+            if dev.status == device::DeviceStatus::Vfio {
+                // A device bound before its name was recorded - easy to lose on the next apply
+                println!("  {} {} is bound to vfio-pci but its interface name is unknown - NOT saved",
+                    "⚠".bright_yellow(), dev.pci_address.bright_white());
+                unnamed_vfio += 1;
+            } else {
+                println!("  {} Skipping {} - no interface name known",
+                    "⚠".bright_yellow(), dev.pci_address);
+            }
+            continue;
+        }
+
+        match dev.status {
+            device::DeviceStatus::Vfio => {
+                println!("  {} {} ({})", "VFIO".bright_green(), dev.interface.bright_white(), dev.pci_address);
+                vfio.push(dev.interface.clone());
+            }
+            device::DeviceStatus::Kernel => {
+                println!("  {} {} ({})", "Kernel".bright_yellow(), dev.interface.bright_white(), dev.pci_address);
+                kernel.push(dev.interface.clone());
+            }
+            device::DeviceStatus::Unbound => {
+                println!("  {} Skipping {} - no driver bound",
+                    "⚠".bright_yellow(), dev.interface);
+            }
+        }
+    }
+
+    println!();
+
+    if unnamed_vfio > 0 {
+        println!("{} {} VFIO {} left out of the config. Name each one with:",
+            "⚠".bright_yellow(), unnamed_vfio, if unnamed_vfio == 1 { "device was" } else { "devices were" });
+        println!("  {}", "vfio-tool config set devices.pci_mappings.<interface> <pci-address>".bright_cyan());
+        println!("  then run 'vfio-tool save --detect' again (or unbind it so its kernel driver names it).");
+        println!();
+    }
+
+    save_config(vfio, kernel)
+}
+
+/// Default location of a hand-written modprobe VFIO configuration
+pub const MODPROBE_VFIO_CONF: &str = "/etc/modprobe.d/vfio.conf";
+
+/// Import VFIO device IDs from a modprobe config (`options vfio-pci ids=...`)
+pub fn import_modprobe(path: &str) -> Result<()> {
+    println!("{}", format!("Importing VFIO device IDs from {}...", path).bright_cyan());
+    println!();
+
+    let content = fs::read_to_string(path)
+        .context(format!("Failed to read {}", path))?;
+
+    let ids = parse_modprobe_ids(&content);
+    if ids.is_empty() {
+        anyhow::bail!("No 'options vfio-pci ids=...' entries found in {}", path);
+    }
+
+    let devices = device::list_network_devices()?;
+    let existing = load_config().ok();
+
+    let mut vfio = existing.as_ref().map(|c| c.devices.vfio.clone()).unwrap_or_default();
+    let mut kernel = existing.as_ref().map(|c| c.devices.kernel.clone()).unwrap_or_default();
+    let set_permissions = existing.as_ref().map(|c| c.options.set_permissions).unwrap_or(true);
+
+    for (vendor, dev_id) in &ids {
+        let matching: Vec<&device::NetworkDevice> = devices
+            .iter()
+            .filter(|d| d.vendor_id == format!("0x{}", vendor) && d.device_id == format!("0x{}", dev_id))
+            .collect();
+
+        if matching.is_empty() {
+            println!("  {} {}:{} - no matching network device found", "⚠".bright_yellow(), vendor, dev_id);
+            continue;
+        }
+
+        for dev in matching {
+            // Devices without a known interface name cannot be referenced from the config
+            if dev.interface.starts_with('(') {
+                println!("  {} {}:{} - {} has no known interface name, skipping",
+                    "⚠".bright_yellow(), vendor, dev_id, dev.pci_address);
+                continue;
+            }
+
+            println!("  {} {}:{} → {} ({})", "✓".bright_green(), vendor, dev_id,
+                dev.interface.bright_white(), dev.pci_address);
+
+            kernel.retain(|iface| iface != &dev.interface);
+            if !vfio.contains(&dev.interface) {
+                vfio.push(dev.interface.clone());
+            }
+        }
+    }
+
+    println!();
+
+    save_config_with_options(vfio, kernel, set_permissions)
+}
+
+/// Parse `ids=vvvv:dddd,...` from `options vfio-pci` / `options vfio_pci` lines
+/// Returns lowercase (vendor, device) hex pairs without the 0x prefix
+fn parse_modprobe_ids(content: &str) -> Vec<(String, String)> {
+    let mut ids = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+
+        let mut parts = line.split_whitespace();
+        if parts.next() != Some("options") {
+            continue;
+        }
+        if !matches!(parts.next(), Some("vfio-pci") | Some("vfio_pci")) {
+            continue;
+        }
+
+        for option in parts {
+            if let Some(list) = option.strip_prefix("ids=") {
+                for id in list.split(',') {
+                    // Entries may carry subsystem IDs (vendor:device:subvendor:subdevice)
+                    let mut fields = id.split(':');
+                    if let (Some(vendor), Some(dev_id)) = (fields.next(), fields.next()) {
+                        let pair = (vendor.to_lowercase(), dev_id.to_lowercase());
+                        if !ids.contains(&pair) {
+                            ids.push(pair);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    ids
+}
+
 /// Save configuration with options
 fn save_config_with_options(
     vfio: Vec<String>,
@@ -544,18 +1266,44 @@ fn save_config_with_options(
     fs::create_dir_all(CONFIG_DIR)
         .context("Failed to create config directory")?;
 
-    // Load existing config to preserve PCI mappings
-    let existing_mappings = if let Ok(existing_config) = load_config() {
-        existing_config.devices.pci_mappings
-    } else {
-        HashMap::new()
-    };
+    // Load existing config to preserve PCI mappings and options
+    let existing_config = load_config().ok();
+
+    let existing_mappings = existing_config
+        .as_ref()
+        .map(|c| c.devices.pci_mappings.clone())
+        .unwrap_or_default();
+
+    let enforce_kernel_list = existing_config
+        .as_ref()
+        .map(|c| c.options.enforce_kernel_list)
+        .unwrap_or(false);
+
+    let allow_absent = existing_config
+        .as_ref()
+        .map(|c| c.options.allow_absent)
+        .unwrap_or(false);
+
+    let frameworks = existing_config
+        .as_ref()
+        .map(|c| c.frameworks.clone())
+        .unwrap_or_default();
+
+    let groups = existing_config
+        .as_ref()
+        .map(|c| c.devices.groups.clone())
+        .unwrap_or_default();
+
+    let altnames = existing_config
+        .as_ref()
+        .map(|c| c.devices.altnames.clone())
+        .unwrap_or_default();
 
     // Build new PCI mappings for all interfaces
     let mut pci_mappings = existing_mappings.clone();
 
     // Get all network devices (including unbound ones)
-    let all_devices = crate::device::list_network_devices()
+    let all_devices = crate::device::list_all_network_devices()
         .unwrap_or_default();
 
     // Add/update mappings for interfaces in vfio list
@@ -580,16 +1328,35 @@ fn save_config_with_options(
         }
     }
 
+    // Record the current IOMMU group of every mapped interface
+    let mut iommu_groups = existing_config
+        .as_ref()
+        .map(|c| c.devices.iommu_groups.clone())
+        .unwrap_or_default();
+
+    for iface in vfio.iter().chain(kernel.iter()) {
+        if let Some(group) = pci_mappings.get(iface).and_then(|pci| device::get_iommu_group(pci)) {
+            iommu_groups.insert(iface.clone(), group);
+        }
+    }
+
     let config = Config {
+        version: CONFIG_VERSION,
         devices: DeviceConfig {
             vfio,
             kernel,
+            groups,
             pci_mappings,
+            iommu_groups,
+            altnames,
         },
         options: Options {
             set_permissions,
             auto_load_module: true,
+            enforce_kernel_list,
+            allow_absent,
         },
+        frameworks,
     };
 
     let toml = toml::to_string_pretty(&config)
@@ -619,6 +1386,8 @@ pub fn save_config_raw(config: &Config) -> Result<()> {
 }
 
 /// Load configuration
+/// An older config is upgraded in memory only; the file picks up the new version the next
+/// time it is saved (under the operation lock) or with `config migrate`
 pub fn load_config() -> Result<Config> {
     if !Path::new(CONFIG_FILE).exists() {
         anyhow::bail!("Configuration file not found: {}\nRun 'vfio-tool configure' to create one.", CONFIG_FILE);
@@ -627,14 +1396,348 @@ pub fn load_config() -> Result<Config> {
     let content = fs::read_to_string(CONFIG_FILE)
         .context("Failed to read config file")?;
 
-    let config: Config = toml::from_str(&content)
+    let mut config: Config = toml::from_str(&content)
         .context("Failed to parse config file")?;
 
+    if config.version > CONFIG_VERSION {
+        eprintln!(
+            "{} Config version {} is newer than this vfio-tool supports ({}); unknown fields are ignored and would be lost if the config is rewritten",
+            "⚠".bright_yellow(), config.version, CONFIG_VERSION
+        );
+    } else {
+        upgrade_config(&mut config);
+    }
+
     Ok(config)
 }
 
+/// Upgrade an older config to the current schema in memory
+/// Fields added since the config was written are already filled by their serde defaults,
+/// so this only bumps the version marker. Returns true if anything changed.
+fn upgrade_config(config: &mut Config) -> bool {
+    if config.version >= CONFIG_VERSION {
+        return false;
+    }
+
+    config.version = CONFIG_VERSION;
+    true
+}
+
+/// Rewrite the config file in the current schema version
+pub fn migrate_config() -> Result<()> {
+    if !Path::new(CONFIG_FILE).exists() {
+        anyhow::bail!("Configuration file not found: {}\nRun 'vfio-tool configure' to create one.", CONFIG_FILE);
+    }
+
+    let content = fs::read_to_string(CONFIG_FILE)
+        .context("Failed to read config file")?;
+
+    let mut config: Config = toml::from_str(&content)
+        .context("Failed to parse config file")?;
+
+    if config.version > CONFIG_VERSION {
+        anyhow::bail!(
+            "Config version {} is newer than this vfio-tool supports ({}).\nUpgrade vfio-tool instead of migrating the config.",
+            config.version, CONFIG_VERSION
+        );
+    }
+
+    let from = config.version;
+    if !upgrade_config(&mut config) {
+        println!("{}", format!("✓ Config is already at version {}", CONFIG_VERSION).bright_green());
+        return Ok(());
+    }
+
+    save_config_raw(&config)?;
+    println!("{}", format!("✓ Migrated {} from version {} to {}", CONFIG_FILE, from, CONFIG_VERSION).bright_green());
+
+    Ok(())
+}
+
+/// Remove PCI mappings whose PCI device and interface are both gone
+/// Such a mapping could otherwise send an unbind-by-name to a recycled slot.
+/// The IOMMU groups recorded for pruned interfaces are dropped too.
+/// Returns the pruned (interface, PCI address) pairs, sorted
+pub fn prune_mappings(config: &mut Config) -> Vec<(String, String)> {
+    let mut pruned: Vec<(String, String)> = config.devices.pci_mappings
+        .iter()
+        .filter(|(iface, pci)| {
+            !Path::new("/sys/bus/pci/devices").join(pci).exists()
+                && !Path::new("/sys/class/net").join(iface).exists()
+        })
+        .map(|(iface, pci)| (iface.clone(), pci.clone()))
+        .collect();
+    pruned.sort();
+
+    for (iface, _) in &pruned {
+        config.devices.pci_mappings.remove(iface);
+        config.devices.iommu_groups.remove(iface);
+        config.devices.altnames.remove(iface);
+    }
+
+    pruned
+}
+
+/// Prune stale PCI mappings from the config file and report them
+pub fn prune_config() -> Result<()> {
+    let mut config = load_config()?;
+    let pruned = prune_mappings(&mut config);
+
+    if pruned.is_empty() {
+        println!("{}", "✓ No stale PCI mappings".bright_green());
+        return Ok(());
+    }
+
+    println!("{}", "Pruned stale PCI mappings:".bright_cyan());
+    for (iface, pci) in &pruned {
+        println!("  - {} → {} {}", iface.bright_white(), pci, "(device and interface gone)".bright_black());
+    }
+
+    save_config_raw(&config)?;
+    println!("{}", format!("✓ Removed {} mapping(s) from {}", pruned.len(), CONFIG_FILE).bright_green());
+
+    Ok(())
+}
+
+/// Keys each config table may contain ("" is the top level; frameworks.<name> shares one entry)
+const KNOWN_KEYS: &[(&str, &[&str])] = &[
+    ("", &["version", "devices", "options", "frameworks"]),
+    ("devices", &["vfio", "kernel", "groups", "pci_mappings", "iommu_groups", "altnames"]),
+    ("options", &["set_permissions", "auto_load_module", "enforce_kernel_list", "allow_absent"]),
+    ("frameworks.<name>", &["requires_vfio", "reference_type", "vendors", "devices"]),
+];
+
+/// Unknown keys in a parsed config table, as dotted paths
+fn unknown_keys(table: &toml::Table, section: &str, path: &str) -> Vec<String> {
+    let known = KNOWN_KEYS
+        .iter()
+        .find(|(name, _)| *name == section)
+        .map(|(_, keys)| *keys)
+        .unwrap_or_default();
+
+    table
+        .keys()
+        .filter(|k| !known.contains(&k.as_str()))
+        .map(|k| if path.is_empty() { k.clone() } else { format!("{}.{}", path, k) })
+        .collect()
+}
+
+/// Check a config file's structure and consistency without looking at any hardware
+/// Returns a message per problem; a file that can't be read or parsed is an error.
+pub fn lint_config_file(path: &str) -> Result<Vec<String>> {
+    let content = fs::read_to_string(path)
+        .context(format!("Failed to read {}", path))?;
+    let table: toml::Table = toml::from_str(&content)
+        .context(format!("{} is not valid TOML", path))?;
+
+    let mut issues = Vec::new();
+
+    // Unknown keys are silently ignored on load, so a typo would otherwise go unnoticed
+    let mut unknown = unknown_keys(&table, "", "");
+    for section in ["devices", "options"] {
+        if let Some(toml::Value::Table(t)) = table.get(section) {
+            unknown.extend(unknown_keys(t, section, section));
+        }
+    }
+    if let Some(toml::Value::Table(frameworks)) = table.get("frameworks") {
+        for (name, definition) in frameworks {
+            if let toml::Value::Table(t) = definition {
+                unknown.extend(unknown_keys(t, "frameworks.<name>", &format!("frameworks.{}", name)));
+            }
+        }
+    }
+    issues.extend(unknown.into_iter().map(|k| format!("unknown key '{}'", k)));
+
+    let config: Config = toml::from_str(&content)
+        .context(format!("{} does not match the config schema", path))?;
+
+    if config.version > CONFIG_VERSION {
+        issues.push(format!("version {} is newer than this vfio-tool supports ({})", config.version, CONFIG_VERSION));
+    }
+
+    for iface in config.devices.vfio.iter().filter(|i| config.devices.kernel.contains(i)) {
+        issues.push(format!("{} is listed as both vfio and kernel", iface));
+    }
+
+    for (list, name) in [(&config.devices.vfio, "vfio"), (&config.devices.kernel, "kernel")] {
+        let mut seen = Vec::new();
+        for iface in list {
+            if seen.contains(&iface) {
+                issues.push(format!("{} appears more than once in devices.{}", iface, name));
+            }
+            seen.push(iface);
+        }
+    }
+
+    let mut mappings: Vec<(&String, &String)> = config.devices.pci_mappings.iter().collect();
+    mappings.sort();
+    for (iface, pci) in &mappings {
+        if !device::is_valid_pci_address(pci) {
+            issues.push(format!("devices.pci_mappings.{} = \"{}\" is not a PCI address (expected e.g. 0000:01:00.0)", iface, pci));
+        }
+    }
+    for (i, (iface, pci)) in mappings.iter().enumerate() {
+        if let Some((other, _)) = mappings[..i].iter().find(|(_, p)| p == pci) {
+            issues.push(format!("{} and {} are both mapped to {}", other, iface, pci));
+        }
+    }
+
+    Ok(issues)
+}
+
+/// Keys addressable by `config set` / `config unset`
+const SETTABLE_KEYS: &[&str] = &[
+    "devices.vfio",
+    "devices.kernel",
+    "devices.groups",
+    "devices.pci_mappings.<interface>",
+    "options.set_permissions",
+    "options.auto_load_module",
+    "options.enforce_kernel_list",
+    "options.allow_absent",
+];
+
+fn unknown_key(key: &str) -> anyhow::Error {
+    anyhow::anyhow!("Unknown config key '{}'. Valid keys:\n  {}", key, SETTABLE_KEYS.join("\n  "))
+}
+
+/// Boolean option addressed by `key`, with its default value
+fn bool_option<'a>(options: &'a mut Options, key: &str) -> Option<(&'a mut bool, bool)> {
+    match key {
+        "options.set_permissions" => Some((&mut options.set_permissions, true)),
+        "options.auto_load_module" => Some((&mut options.auto_load_module, true)),
+        "options.enforce_kernel_list" => Some((&mut options.enforce_kernel_list, false)),
+        "options.allow_absent" => Some((&mut options.allow_absent, false)),
+        _ => None,
+    }
+}
+
+/// Set one config key from the command line and save
+/// Lists are comma-separated ("" for an empty list), booleans are true/false
+pub fn set_config_value(key: &str, value: &str) -> Result<()> {
+    let mut config = load_config()?;
+
+    if let Some((option, _)) = bool_option(&mut config.options, key) {
+        *option = match value {
+            "true" => true,
+            "false" => false,
+            _ => anyhow::bail!("{} expects a boolean (true/false), got '{}'", key, value),
+        };
+    } else if key == "devices.vfio" || key == "devices.kernel" {
+        let list: Vec<String> = value
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        let other = if key == "devices.vfio" { &config.devices.kernel } else { &config.devices.vfio };
+        if let Some(dup) = list.iter().find(|i| other.contains(i)) {
+            anyhow::bail!("{} is already in the other device list; remove it there first", dup);
+        }
+
+        if key == "devices.vfio" {
+            config.devices.vfio = list;
+        } else {
+            config.devices.kernel = list;
+        }
+    } else if key == "devices.groups" {
+        config.devices.groups = value
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|g| g.parse::<u32>()
+                .map_err(|_| anyhow::anyhow!("{} expects IOMMU group numbers (e.g. 14,15), got '{}'", key, g)))
+            .collect::<Result<_>>()?;
+    } else if let Some(interface) = key.strip_prefix("devices.pci_mappings.") {
+        if interface.is_empty() {
+            return Err(unknown_key(key));
+        }
+        if !device::is_valid_pci_address(value) {
+            anyhow::bail!("{} expects a PCI address (e.g. 0000:01:00.0), got '{}'", key, value);
+        }
+        config.devices.pci_mappings.insert(interface.to_string(), value.to_string());
+    } else {
+        return Err(unknown_key(key));
+    }
+
+    save_config_raw(&config)?;
+    println!("{} {} = {}", "✓".bright_green(), key, value);
+
+    Ok(())
+}
+
+/// Reset one config key to its default (empty list, default boolean, removed mapping) and save
+pub fn unset_config_value(key: &str) -> Result<()> {
+    let mut config = load_config()?;
+
+    if let Some((option, default)) = bool_option(&mut config.options, key) {
+        *option = default;
+    } else if key == "devices.vfio" {
+        config.devices.vfio.clear();
+    } else if key == "devices.kernel" {
+        config.devices.kernel.clear();
+    } else if key == "devices.groups" {
+        config.devices.groups.clear();
+    } else if let Some(interface) = key.strip_prefix("devices.pci_mappings.") {
+        if config.devices.pci_mappings.remove(interface).is_none() {
+            anyhow::bail!("No PCI mapping for interface '{}'", interface);
+        }
+    } else {
+        return Err(unknown_key(key));
+    }
+
+    save_config_raw(&config)?;
+    println!("{} Unset {}", "✓".bright_green(), key);
+
+    Ok(())
+}
+
 /// Get config file path
 #[allow(dead_code)]
 pub fn get_config_path() -> PathBuf {
     PathBuf::from(CONFIG_FILE)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(vendor: &str, device: &str) -> (String, String) {
+        (vendor.to_string(), device.to_string())
+    }
+
+    #[test]
+    fn modprobe_ids_from_both_module_spellings() {
+        let content = "\
+# options vfio-pci ids=dead:beef
+options vfio-pci ids=8086:10FB,8086:1521 disable_vga=1
+options vfio_pci ids=8086:10fb,15b3:1017:15b3:0020
+options ixgbe max_vfs=8
+softdep ixgbe pre: vfio-pci
+";
+        assert_eq!(parse_modprobe_ids(content), vec![
+            pair("8086", "10fb"),
+            pair("8086", "1521"),
+            pair("15b3", "1017"),
+        ]);
+    }
+
+    #[test]
+    fn softdeps_only_count_vfio_as_a_pre_dependency() {
+        let content = "\
+softdep ixgbe pre: vfio-pci
+softdep mlx5-core pre: vfio_pci post: mlx5_ib
+softdep i40e post: vfio-pci
+softdep igb pre: crc32c
+# softdep ice pre: vfio-pci
+options vfio-pci ids=8086:10fb
+";
+        assert_eq!(parse_softdeps(content), ["ixgbe", "mlx5_core"]);
+    }
+
+    #[test]
+    fn modprobe_ids_empty_without_vfio_options() {
+        assert!(parse_modprobe_ids("options ixgbe max_vfs=8\n\n# ids=8086:10fb\n").is_empty());
+        assert!(parse_modprobe_ids("options vfio-pci disable_vga=1\n").is_empty());
+    }
+}
@@ -1,6 +1,8 @@
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
 use anyhow::{Result, Context};
+use nix::errno::Errno;
 
 #[derive(Debug, Clone)]
 pub struct NetworkDevice {
@@ -10,9 +12,13 @@ pub struct NetworkDevice {
     pub iommu_group: Option<u32>,
     pub vendor_id: String,
     pub device_id: String,
+    pub subsystem_vendor_id: Option<String>,
+    pub subsystem_device_id: Option<String>,
     pub speed: Option<String>,
     pub max_speed: Option<String>,
     pub status: DeviceStatus,
+    pub sriov_role: SriovRole,
+    pub override_pending: Option<String>,  // Unbound, with driver_override naming an unregistered driver
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -22,18 +28,127 @@ pub enum DeviceStatus {
     Unbound,     // No driver bound
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum SriovRole {
+    None,                     // Not an SR-IOV function (or no VFs enabled)
+    PhysicalFunction(usize),  // PF with N virtual functions
+    VirtualFunction(String),  // VF, with parent PF PCI address
+}
+
+impl SriovRole {
+    pub fn short_name(&self) -> &str {
+        match self {
+            SriovRole::None => "-",
+            SriovRole::PhysicalFunction(_) => "PF",
+            SriovRole::VirtualFunction(_) => "VF",
+        }
+    }
+}
+
 impl NetworkDevice {
     pub fn vendor_device(&self) -> String {
         format!("{}:{}", self.vendor_id, self.device_id)
     }
 
+    /// Subsystem vendor:device (identifies OEM variants of the same chip)
+    pub fn subsystem(&self) -> Option<String> {
+        match (&self.subsystem_vendor_id, &self.subsystem_device_id) {
+            (Some(vendor), Some(device)) => Some(format!("{}:{}", vendor, device)),
+            _ => None,
+        }
+    }
+
+    /// Model description, noting the OEM when the subsystem vendor differs from the chip vendor
+    pub fn description(&self) -> String {
+        let desc = get_device_description(&self.vendor_id, &self.device_id);
+
+        match self.subsystem_vendor_id.as_deref() {
+            Some(sub) if sub != self.vendor_id => match get_oem_name(sub) {
+                Some(oem) => format!("{} ({} OEM)", desc, oem),
+                None => desc,
+            },
+            _ => desc,
+        }
+    }
+
     pub fn is_vfio_bound(&self) -> bool {
         self.status == DeviceStatus::Vfio
     }
+
+    /// Check if the VFIO group device node (/dev/vfio/<group>) exists
+    pub fn has_vfio_node(&self) -> bool {
+        self.iommu_group
+            .map(|g| Path::new(&format!("/dev/vfio/{}", g)).exists())
+            .unwrap_or(false)
+    }
+
+    /// Wi-Fi or WWAN device (excluded from the default device set)
+    pub fn is_wireless(&self) -> bool {
+        get_pci_class(&self.pci_address)
+            .and_then(|c| classify_network_class(&c))
+            == Some(NetworkClass::Wireless)
+    }
+
+    /// Bound to vfio-pci but without a usable group device node
+    /// (e.g. IOMMU not active or the type1 container unavailable)
+    pub fn is_vfio_missing_node(&self) -> bool {
+        self.is_vfio_bound() && !self.has_vfio_node()
+    }
 }
 
-/// List all network devices on the system
+/// Kind of network-capable PCI function
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NetworkClass {
+    Wired,    // Ethernet, InfiniBand, fabric, ... (0x02xxxx)
+    Wireless, // Wi-Fi (0x0280xx) and wireless controllers such as WWAN (0x0dxxxx)
+}
+
+/// Classify a PCI class code (e.g. "0x020000"); None if it is not a network function
+/// Wi-Fi cards report "network controller: other" (0x0280xx)
+pub fn classify_network_class(class_code: &str) -> Option<NetworkClass> {
+    let class_code = class_code.trim().to_lowercase();
+
+    if class_code.starts_with("0x0280") || class_code.starts_with("0x0d") {
+        Some(NetworkClass::Wireless)
+    } else if class_code.starts_with("0x02") {
+        Some(NetworkClass::Wired)
+    } else {
+        None
+    }
+}
+
+/// Normalize a PCI class filter ("02", "0x0300", "0108") to lowercase hex digits
+/// A filter is a prefix of the 6-digit class code: base class, subclass, prog-if
+pub fn parse_class_filter(filter: &str) -> Result<String> {
+    let digits = filter.trim().to_lowercase();
+    let digits = digits.strip_prefix("0x").unwrap_or(&digits);
+
+    if digits.is_empty() || digits.len() > 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        anyhow::bail!("Invalid PCI class filter '{}' (expected hex such as 02, 0300 or 0x0108)", filter);
+    }
+
+    Ok(digits.to_string())
+}
+
+/// Check a class code (e.g. "0x020000") against a filter from `parse_class_filter`
+pub fn class_matches(class_code: &str, filter: &str) -> bool {
+    let class_code = class_code.trim().to_lowercase();
+    class_code
+        .strip_prefix("0x")
+        .is_some_and(|digits| digits.starts_with(filter))
+}
+
+/// List network devices on the system, excluding wireless/WWAN devices
 pub fn list_network_devices() -> Result<Vec<NetworkDevice>> {
+    scan_network_devices(false)
+}
+
+/// List network devices on the system, including wireless/WWAN devices
+pub fn list_all_network_devices() -> Result<Vec<NetworkDevice>> {
+    scan_network_devices(true)
+}
+
+fn scan_network_devices(include_wireless: bool) -> Result<Vec<NetworkDevice>> {
     let mut devices = Vec::new();
     let mut seen_pci_addresses = std::collections::HashSet::new();
 
@@ -55,20 +170,22 @@ pub fn list_network_devices() -> Result<Vec<NetworkDevice>> {
             continue;
         }
 
-        // Check if it's a network device (PCI class 0x020000 = network controller)
-        let class_path = entry.path().join("class");
-        if let Ok(class_str) = fs::read_to_string(&class_path) {
-            let class_code = class_str.trim();
-            // Network controllers have class code 0x02xxxx
-            if !class_code.starts_with("0x02") {
-                continue;
-            }
-        } else {
-            continue;
+        // One uevent read gives class, IDs and driver; fall back to the class file without it
+        let uevent = read_uevent(&pci_address);
+        let class = match uevent {
+            Some(ref u) => Some(u.class.clone()),
+            None => fs::read_to_string(entry.path().join("class")).ok(),
+        };
+
+        // Check if it's a network device (PCI class 0x02xxxx = network controller)
+        match class.and_then(|c| classify_network_class(&c)) {
+            Some(NetworkClass::Wired) => {}
+            Some(NetworkClass::Wireless) if include_wireless => {}
+            _ => continue,
         }
 
         // Found a network device - get its details
-        if let Ok(device) = get_device_info_by_pci(&pci_address, &config) {
+        if let Ok(device) = build_device_info(&pci_address, &config, uevent) {
             // Skip if we already found this device (shouldn't happen, but be safe)
             if !seen_pci_addresses.contains(&pci_address) {
                 seen_pci_addresses.insert(pci_address.clone());
@@ -81,10 +198,69 @@ pub fn list_network_devices() -> Result<Vec<NetworkDevice>> {
     Ok(devices)
 }
 
+/// Identity fields of a PCI device, as found in its `uevent` file
+#[derive(Debug, Clone, PartialEq)]
+struct PciUevent {
+    class: String,           // "0x020000", same format as the `class` file
+    vendor_id: String,       // "0x8086", same format as the `vendor` file
+    device_id: String,       // "0x10fb", same format as the `device` file
+    driver: Option<String>,  // DRIVER= is absent when no driver is bound
+    subsystem: Option<(String, String)>,  // PCI_SUBSYS_ID, same format as `subsystem_*`
+}
+
+/// Parse a PCI `uevent` file (DRIVER=, PCI_CLASS=, PCI_ID=, ...)
+/// Returns None if class or IDs are missing, so callers fall back to the individual files
+fn parse_uevent(content: &str) -> Option<PciUevent> {
+    let mut class = None;
+    let mut ids = None;
+    let mut driver = None;
+    let mut subsystem = None;
+
+    for line in content.lines() {
+        match line.split_once('=') {
+            Some(("DRIVER", d)) => driver = Some(d.to_string()),
+            Some(("PCI_SUBSYS_ID", id)) => {
+                subsystem = id.split_once(':')
+                    .map(|(v, d)| (format!("0x{}", v.to_lowercase()), format!("0x{}", d.to_lowercase())));
+            }
+            Some(("PCI_CLASS", c)) => class = u32::from_str_radix(c, 16).ok(),
+            Some(("PCI_ID", id)) => ids = id.split_once(':').map(|(v, d)| (v.to_lowercase(), d.to_lowercase())),
+            _ => {}
+        }
+    }
+
+    let (vendor, device) = ids?;
+    Some(PciUevent {
+        class: format!("0x{:06x}", class?),
+        vendor_id: format!("0x{}", vendor),
+        device_id: format!("0x{}", device),
+        driver,
+        subsystem,
+    })
+}
+
+fn read_uevent(pci_address: &str) -> Option<PciUevent> {
+    fs::read_to_string(format!("/sys/bus/pci/devices/{}/uevent", pci_address))
+        .ok()
+        .and_then(|c| parse_uevent(&c))
+}
+
 /// Get device info by PCI address (handles kernel, VFIO, and unbound states)
-fn get_device_info_by_pci(pci_address: &str, config: &Option<crate::config::Config>) -> Result<NetworkDevice> {
-    // Get vendor and device IDs
-    let (vendor_id, device_id) = get_vendor_device_id(pci_address)?;
+pub fn get_device_info_by_pci(pci_address: &str, config: &Option<crate::config::Config>) -> Result<NetworkDevice> {
+    build_device_info(pci_address, config, read_uevent(pci_address))
+}
+
+/// Build device info, taking IDs and driver from an already-read uevent when available
+fn build_device_info(pci_address: &str, config: &Option<crate::config::Config>, uevent: Option<PciUevent>) -> Result<NetworkDevice> {
+    // Get vendor, device and subsystem IDs, and driver
+    let (vendor_id, device_id, driver, subsystem) = match uevent {
+        Some(u) => (u.vendor_id, u.device_id, u.driver, u.subsystem),
+        None => {
+            let (vendor_id, device_id) = get_vendor_device_id(pci_address)?;
+            (vendor_id, device_id, get_driver(pci_address), get_subsystem_ids(pci_address))
+        }
+    };
+    let (subsystem_vendor_id, subsystem_device_id) = subsystem.unzip();
 
     // Get IOMMU group
     let iommu_group = get_iommu_group(pci_address);
@@ -92,9 +268,6 @@ fn get_device_info_by_pci(pci_address: &str, config: &Option<crate::config::Conf
     // Get maximum capable speed based on device ID
     let max_speed = get_max_speed(&vendor_id, &device_id);
 
-    // Get driver
-    let driver = get_driver(pci_address);
-
     // Determine status
     let status = match &driver {
         Some(d) if d == "vfio-pci" => DeviceStatus::Vfio,
@@ -148,6 +321,10 @@ fn get_device_info_by_pci(pci_address: &str, config: &Option<crate::config::Conf
         None
     };
 
+    // Get SR-IOV role
+    let sriov_role = get_sriov_role(pci_address);
+    let override_pending = if status == DeviceStatus::Unbound { pending_override(pci_address) } else { None };
+
     Ok(NetworkDevice {
         interface,
         pci_address: pci_address.to_string(),
@@ -155,89 +332,19 @@ fn get_device_info_by_pci(pci_address: &str, config: &Option<crate::config::Conf
         iommu_group,
         vendor_id,
         device_id,
+        subsystem_vendor_id,
+        subsystem_device_id,
         speed,
         max_speed,
         status,
+        sriov_role,
+        override_pending,
     })
 }
 
-/// List network devices bound to vfio-pci
-fn list_vfio_network_devices() -> Result<Vec<NetworkDevice>> {
-    let vfio_driver_path = Path::new("/sys/bus/pci/drivers/vfio-pci");
-    let mut devices = Vec::new();
-
-    if !vfio_driver_path.exists() {
-        return Ok(devices);
-    }
-
-    // Load config to get interface name mappings
-    let config = crate::config::load_config().ok();
-
-    for entry in fs::read_dir(vfio_driver_path)? {
-        let entry = entry?;
-        let name = entry.file_name();
-        let pci_address = name.to_string_lossy().to_string();
-
-        // Only check PCI devices (format: 0000:XX:XX.X)
-        if !pci_address.contains(':') || !pci_address.contains('.') {
-            continue;
-        }
-
-        // Check if it's a network device (PCI class 0x020000 = network controller)
-        let class_path = format!("/sys/bus/pci/devices/{}/class", pci_address);
-        if let Ok(class_str) = fs::read_to_string(&class_path) {
-            let class_code = class_str.trim();
-            // Network controllers have class code 0x02xxxx
-            if !class_code.starts_with("0x02") {
-                continue;
-            }
-        } else {
-            continue;
-        }
-
-        // Try to get interface name from config mappings
-        let interface = if let Some(ref cfg) = config {
-            // Search for this PCI address in mappings
-            cfg.devices.pci_mappings
-                .iter()
-                .find(|(_, pci)| pci.as_str() == pci_address)
-                .map(|(iface, _)| iface.clone())
-                .unwrap_or_else(|| format!("({})", pci_address))
-        } else {
-            format!("({})", pci_address)
-        };
-
-        // Get device info by PCI address
-        if let Ok(device) = get_vfio_device_info(&pci_address, &interface) {
-            devices.push(device);
-        }
-    }
-
-    Ok(devices)
-}
-
-/// Get device info for a VFIO-bound device by PCI address
-fn get_vfio_device_info(pci_address: &str, interface: &str) -> Result<NetworkDevice> {
-    // Get vendor and device IDs
-    let (vendor_id, device_id) = get_vendor_device_id(pci_address)?;
-
-    // Get IOMMU group
-    let iommu_group = get_iommu_group(pci_address);
-
-    // Get maximum capable speed based on device ID
-    let max_speed = get_max_speed(&vendor_id, &device_id);
-
-    Ok(NetworkDevice {
-        interface: interface.to_string(),
-        pci_address: pci_address.to_string(),
-        driver: Some("vfio-pci".to_string()),
-        iommu_group,
-        vendor_id,
-        device_id,
-        speed: None,  // No link speed available when bound to VFIO
-        max_speed,
-        status: DeviceStatus::Vfio,
-    })
+/// Whether a network interface exists, whatever bus (if any) its device is on
+pub fn interface_exists(interface: &str) -> bool {
+    Path::new(&format!("/sys/class/net/{}", interface)).exists()
 }
 
 /// Get detailed information about a specific network interface
@@ -254,7 +361,7 @@ pub fn get_device_info(interface: &str) -> Result<NetworkDevice> {
     }
 
     // Get PCI address
-    let pci_address = get_pci_address(&device_path)?;
+    let pci_address = get_pci_address(interface, &device_path)?;
 
     // Get driver
     let driver = get_driver(&pci_address);
@@ -264,6 +371,7 @@ pub fn get_device_info(interface: &str) -> Result<NetworkDevice> {
 
     // Get vendor and device IDs
     let (vendor_id, device_id) = get_vendor_device_id(&pci_address)?;
+    let (subsystem_vendor_id, subsystem_device_id) = get_subsystem_ids(&pci_address).unzip();
 
     // Get link speed
     let speed = get_link_speed(&base_path);
@@ -278,6 +386,10 @@ pub fn get_device_info(interface: &str) -> Result<NetworkDevice> {
         None => DeviceStatus::Unbound,
     };
 
+    // Get SR-IOV role
+    let sriov_role = get_sriov_role(&pci_address);
+    let override_pending = if status == DeviceStatus::Unbound { pending_override(&pci_address) } else { None };
+
     Ok(NetworkDevice {
         interface: interface.to_string(),
         pci_address,
@@ -285,24 +397,49 @@ pub fn get_device_info(interface: &str) -> Result<NetworkDevice> {
         iommu_group,
         vendor_id,
         device_id,
+        subsystem_vendor_id,
+        subsystem_device_id,
         speed,
         max_speed,
         status,
+        sriov_role,
+        override_pending,
     })
 }
 
-fn get_pci_address(device_path: &Path) -> Result<String> {
+static PCI_ADDRESS: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(r"^[0-9a-fA-F]{4}:[0-9a-fA-F]{2}:[0-1][0-9a-fA-F]\.[0-7]$")
+        .expect("valid PCI address pattern")
+});
+
+/// Whether a string is a full PCI address (domain:bus:device.function, e.g. 0000:01:00.0)
+pub fn is_valid_pci_address(s: &str) -> bool {
+    PCI_ADDRESS.is_match(s)
+}
+
+/// PCI address behind an interface's `device` link
+/// USB NICs and virtual devices hang off another bus; that is an error naming the bus.
+fn get_pci_address(interface: &str, device_path: &Path) -> Result<String> {
     let target = fs::read_link(device_path)
         .context("Failed to read device symlink")?;
 
-    target
+    let name = target
         .file_name()
         .and_then(|n| n.to_str())
-        .map(|s| s.to_string())
-        .context("Invalid PCI address")
+        .context("Invalid device symlink")?;
+
+    if !is_valid_pci_address(name) {
+        let bus = fs::read_link(device_path.join("subsystem"))
+            .ok()
+            .and_then(|t| t.file_name().map(|n| n.to_string_lossy().to_string()))
+            .unwrap_or_else(|| "unknown".to_string());
+        anyhow::bail!("Interface {} is not a PCI device (bus: {}); VFIO only applies to PCI devices", interface, bus);
+    }
+
+    Ok(name.to_string())
 }
 
-fn get_driver(pci_address: &str) -> Option<String> {
+pub fn get_driver(pci_address: &str) -> Option<String> {
     let driver_path = PathBuf::from(format!("/sys/bus/pci/devices/{}/driver", pci_address));
 
     if !driver_path.exists() {
@@ -319,7 +456,76 @@ fn get_driver(pci_address: &str) -> Option<String> {
         })
 }
 
-fn get_iommu_group(pci_address: &str) -> Option<u32> {
+/// Driver named in a driver_override file ("(null)" when unset)
+fn parse_driver_override(content: &str) -> Option<String> {
+    let driver = content.trim();
+    (!driver.is_empty() && driver != "(null)").then(|| driver.to_string())
+}
+
+/// Driver named in a device's driver_override when that driver isn't registered
+/// Such a device matches no other driver, so it stays unbound until the driver loads.
+pub fn pending_override(pci_address: &str) -> Option<String> {
+    let content = fs::read_to_string(format!("/sys/bus/pci/devices/{}/driver_override", pci_address)).ok()?;
+    parse_driver_override(&content)
+        .filter(|driver| !Path::new("/sys/bus/pci/drivers").join(driver).exists())
+}
+
+/// Driverless PCI devices waiting on an unregistered override driver, as (PCI address, driver)
+pub fn pending_overrides() -> Vec<(String, String)> {
+    let Ok(entries) = fs::read_dir("/sys/bus/pci/devices") else {
+        return Vec::new();
+    };
+
+    let mut pending: Vec<(String, String)> = entries
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|pci| get_driver(pci).is_none())
+        .filter_map(|pci| pending_override(&pci).map(|driver| (pci, driver)))
+        .collect();
+    pending.sort();
+    pending
+}
+
+/// Names following "altname" in `ip -o link show` output
+fn parse_altnames(output: &str) -> Vec<String> {
+    let mut tokens = output.split_whitespace();
+    let mut names = Vec::new();
+    while let Some(token) = tokens.next() {
+        if token == "altname" {
+            // One-line output joins the record's lines with a trailing '\'
+            names.extend(tokens.next().map(|n| n.trim_end_matches('\\').to_string()));
+        }
+    }
+    names
+}
+
+/// Other names of an interface: its ifalias, then its kernel altnames
+/// Altnames are only exposed over netlink, so they are read through `ip`.
+pub fn get_interface_altnames(interface: &str) -> Vec<String> {
+    let mut names = Vec::new();
+
+    if let Ok(alias) = fs::read_to_string(format!("/sys/class/net/{}/ifalias", interface)) {
+        let alias = alias.trim();
+        if !alias.is_empty() {
+            names.push(alias.to_string());
+        }
+    }
+
+    let output = std::process::Command::new("ip")
+        .args(["-o", "link", "show", "dev", interface])
+        .output();
+    if let Ok(output) = output && output.status.success() {
+        for name in parse_altnames(&String::from_utf8_lossy(&output.stdout)) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+
+    names
+}
+
+pub fn get_iommu_group(pci_address: &str) -> Option<u32> {
     let iommu_path = PathBuf::from(format!("/sys/bus/pci/devices/{}/iommu_group", pci_address));
 
     if !iommu_path.exists() {
@@ -336,7 +542,35 @@ fn get_iommu_group(pci_address: &str) -> Option<u32> {
         })
 }
 
-fn get_vendor_device_id(pci_address: &str) -> Result<(String, String)> {
+/// Detect SR-IOV role: `physfn` link means VF, `virtfn*` links mean PF with VFs
+fn get_sriov_role(pci_address: &str) -> SriovRole {
+    let base = PathBuf::from(format!("/sys/bus/pci/devices/{}", pci_address));
+
+    if let Ok(target) = fs::read_link(base.join("physfn")) {
+        let pf = target
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        return SriovRole::VirtualFunction(pf);
+    }
+
+    let num_vfs = fs::read_dir(&base)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_name().to_string_lossy().starts_with("virtfn"))
+                .count()
+        })
+        .unwrap_or(0);
+
+    if num_vfs > 0 {
+        SriovRole::PhysicalFunction(num_vfs)
+    } else {
+        SriovRole::None
+    }
+}
+
+pub fn get_vendor_device_id(pci_address: &str) -> Result<(String, String)> {
     let base = PathBuf::from(format!("/sys/bus/pci/devices/{}", pci_address));
 
     let vendor = fs::read_to_string(base.join("vendor"))
@@ -352,39 +586,65 @@ fn get_vendor_device_id(pci_address: &str) -> Result<(String, String)> {
     Ok((vendor, device))
 }
 
+/// Subsystem vendor and device IDs (e.g. "0x1028", "0x1f9c"), if readable
+pub fn get_subsystem_ids(pci_address: &str) -> Option<(String, String)> {
+    let base = PathBuf::from(format!("/sys/bus/pci/devices/{}", pci_address));
+
+    let vendor = fs::read_to_string(base.join("subsystem_vendor")).ok()?;
+    let device = fs::read_to_string(base.join("subsystem_device")).ok()?;
+
+    Some((vendor.trim().to_string(), device.trim().to_string()))
+}
+
 fn get_link_speed(interface_path: &Path) -> Option<String> {
     let speed_path = interface_path.join("speed");
 
     match fs::read_to_string(speed_path) {
-        Ok(s) => {
-            match s.trim().parse::<i32>() {
-                Ok(speed) => {
-                    if speed < 0 {
-                        // -1 typically means no link (no carrier)
-                        Some("no link".to_string())
-                    } else if speed >= 1000 {
-                        Some(format!("{}G", speed / 1000))
-                    } else {
-                        Some(format!("{}M", speed))
-                    }
-                }
-                Err(_) => Some("?".to_string()),
+        Ok(s) => Some(format_link_speed(&s)),
+        Err(e) => {
+            // Map the errno returned by the driver to a meaningful state
+            match e.raw_os_error().map(Errno::from_raw) {
+                // "Invalid argument" means interface is down
+                Some(Errno::EINVAL) => Some("down".to_string()),
+                // Some drivers refuse to report speed while administratively down
+                Some(Errno::EOPNOTSUPP) => Some("unsupported".to_string()),
+                Some(Errno::EACCES) => Some("permission".to_string()),
+                _ if e.kind() == std::io::ErrorKind::InvalidInput => Some("down".to_string()),
+                _ => Some("?".to_string()),
             }
         }
-        Err(e) => {
-            // Check if it's "Invalid argument" which means interface is down
-            if e.kind() == std::io::ErrorKind::InvalidInput {
-                Some("down".to_string())
+    }
+}
+
+/// Maximum length of a non-numeric speed value shown to the user
+const RAW_SPEED_MAX_LEN: usize = 12;
+
+/// Format the contents of a `speed` file (Mb/s)
+/// Non-numeric content, which some virtual/driver-specific interfaces report,
+/// is shown as-is (trimmed and truncated) instead of collapsing to "?"
+fn format_link_speed(raw: &str) -> String {
+    let raw = raw.trim();
+
+    match raw.parse::<i64>() {
+        // -1 typically means no link (no carrier)
+        Ok(speed) if speed < 0 => "no link".to_string(),
+        Ok(speed) if speed >= 1000 => format!("{}G", speed / 1000),
+        Ok(speed) => format!("{}M", speed),
+        Err(_) if raw.is_empty() => "?".to_string(),
+        Err(_) => {
+            let printable: String = raw.chars().filter(|c| !c.is_control()).collect();
+            if printable.chars().count() > RAW_SPEED_MAX_LEN {
+                let truncated: String = printable.chars().take(RAW_SPEED_MAX_LEN - 1).collect();
+                format!("{}…", truncated)
             } else {
-                // Other errors (permission denied, etc.)
-                Some("?".to_string())
+                printable
             }
         }
     }
 }
 
 /// Get maximum capable speed based on vendor:device ID
-fn get_max_speed(vendor_id: &str, device_id: &str) -> Option<String> {
+pub fn get_max_speed(vendor_id: &str, device_id: &str) -> Option<String> {
     // Common network card vendor:device ID mappings
     match (vendor_id, device_id) {
         // Intel XXV710 - 25GbE
@@ -491,6 +751,176 @@ fn get_max_speed(vendor_id: &str, device_id: &str) -> Option<String> {
     }
 }
 
+/// Get a human-readable device description based on vendor:device ID
+pub fn get_device_description(vendor_id: &str, device_id: &str) -> String {
+    // Try to identify vendor/model from vendor:device ID
+    match (vendor_id, device_id) {
+        // Mellanox
+        ("0x15b3", "0x101f") => "Mellanox ConnectX-4 Lx".to_string(),
+        ("0x15b3", "0x1013") => "Mellanox ConnectX-4".to_string(),
+        ("0x15b3", "0x1015") => "Mellanox ConnectX-4".to_string(),
+        ("0x15b3", "0x1017") => "Mellanox ConnectX-5".to_string(),
+
+        // Intel XXV710 - 25GbE
+        ("0x8086", "0x158a") => "Intel XXV710 25GbE".to_string(),
+        ("0x8086", "0x158b") => "Intel XXV710 25GbE".to_string(),
+
+        // Intel X710 - 10GbE
+        ("0x8086", "0x1572") => "Intel X710 10GbE".to_string(),
+        ("0x8086", "0x15ff") => "Intel X710 10GbE".to_string(),
+
+        // Solarflare
+        ("0x1924", _) => "Solarflare NIC".to_string(),
+
+        _ => {
+            if let Some(speed) = get_max_speed(vendor_id, device_id) {
+                format!("{} NIC", speed)
+            } else {
+                "Network Card".to_string()
+            }
+        }
+    }
+}
+
+/// Name of a server OEM by subsystem vendor ID, for cards rebranded by system vendors
+pub fn get_oem_name(subsystem_vendor_id: &str) -> Option<&'static str> {
+    match subsystem_vendor_id {
+        "0x1028" => Some("Dell"),
+        "0x103c" => Some("HP"),
+        "0x1590" => Some("HPE"),
+        "0x17aa" => Some("Lenovo"),
+        "0x1014" => Some("IBM"),
+        "0x15d9" => Some("Supermicro"),
+        "0x1137" => Some("Cisco"),
+        "0x19e5" => Some("Huawei"),
+        "0x152d" => Some("Quanta"),
+        "0x1bd4" => Some("Inspur"),
+        _ => None,
+    }
+}
+
+/// Trigger a PCI bus rescan and wait for enumeration to settle
+/// Returns the PCI addresses that appeared
+pub fn rescan_pci_bus() -> Result<Vec<String>> {
+    fn pci_addresses() -> Vec<String> {
+        let mut addresses: Vec<String> = fs::read_dir("/sys/bus/pci/devices")
+            .map(|entries| entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .collect())
+            .unwrap_or_default();
+        addresses.sort();
+        addresses
+    }
+
+    let before = pci_addresses();
+
+    fs::write("/sys/bus/pci/rescan", "1")
+        .context("Failed to write /sys/bus/pci/rescan")?;
+
+    // Poll until the device count is stable for one interval (at most ~5s)
+    let interval = std::time::Duration::from_millis(500);
+    let mut count = before.len();
+    for _ in 0..10 {
+        std::thread::sleep(interval);
+        let now = pci_addresses().len();
+        if now == count {
+            break;
+        }
+        count = now;
+    }
+
+    Ok(pci_addresses()
+        .into_iter()
+        .filter(|pci| !before.contains(pci))
+        .collect())
+}
+
+/// Get the PCI class code of a device (e.g. 0x020000)
+pub fn get_pci_class(pci_address: &str) -> Option<String> {
+    fs::read_to_string(format!("/sys/bus/pci/devices/{}/class", pci_address))
+        .ok()
+        .map(|c| c.trim().to_string())
+}
+
+/// Count network controllers and how many are bound to vfio-pci
+/// Cheap: one class read and one driver readlink per PCI device, no config lookups
+pub fn count_network_devices() -> (usize, usize) {
+    let Ok(entries) = fs::read_dir("/sys/bus/pci/devices") else {
+        return (0, 0);
+    };
+
+    let mut total = 0;
+    let mut vfio = 0;
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        let is_network = fs::read_to_string(entry.path().join("class"))
+            .ok()
+            .and_then(|c| classify_network_class(&c))
+            == Some(NetworkClass::Wired);
+        if !is_network {
+            continue;
+        }
+
+        total += 1;
+        if let Ok(driver) = fs::read_link(entry.path().join("driver"))
+            && driver.file_name().is_some_and(|n| n == "vfio-pci")
+        {
+            vfio += 1;
+        }
+    }
+
+    (total, vfio)
+}
+
+/// Check if a device is an endpoint (not a PCI bridge, class 0x06xxxx)
+pub fn is_endpoint(pci_address: &str) -> bool {
+    get_pci_class(pci_address)
+        .map(|c| !c.starts_with("0x06"))
+        .unwrap_or(false)
+}
+
+/// NUMA node a device is attached to; None on single-node systems (numa_node is -1)
+pub fn get_numa_node(pci_address: &str) -> Option<u32> {
+    fs::read_to_string(format!("/sys/bus/pci/devices/{}/numa_node", pci_address))
+        .ok()
+        .and_then(|n| n.trim().parse::<i32>().ok())
+        .and_then(|n| u32::try_from(n).ok())
+}
+
+/// Physical slot portion of a PCI address ("0000:03:00.1" -> "0000:03:00")
+pub fn pci_slot(pci_address: &str) -> Option<&str> {
+    let (slot, function) = pci_address.rsplit_once('.')?;
+    if function.is_empty() || !function.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some(slot)
+}
+
+/// Other network PFs on the same physical card (same slot, different function)
+/// Multi-port cards (bnxt, mlx5, i40e, ...) expose one PF per port; VFs are excluded.
+pub fn get_sibling_functions(pci_address: &str) -> Vec<String> {
+    let Some(slot) = pci_slot(pci_address) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir("/sys/bus/pci/devices") else {
+        return Vec::new();
+    };
+
+    let mut siblings: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|pci| pci != pci_address && pci_slot(pci) == Some(slot))
+        .filter(|pci| {
+            get_pci_class(pci).and_then(|c| classify_network_class(&c)) == Some(NetworkClass::Wired)
+        })
+        .filter(|pci| !Path::new("/sys/bus/pci/devices").join(pci).join("physfn").exists())
+        .collect();
+
+    siblings.sort();
+    siblings
+}
+
 /// Get all devices in an IOMMU group
 pub fn get_iommu_group_devices(group_id: u32) -> Result<Vec<String>> {
     let group_path = PathBuf::from(format!("/sys/kernel/iommu_groups/{}/devices", group_id));
@@ -509,3 +939,505 @@ pub fn get_iommu_group_devices(group_id: u32) -> Result<Vec<String>> {
 
     Ok(devices)
 }
+
+/// Interrupt capabilities and current interrupt mode of a PCI device
+#[derive(Debug, Clone, Default)]
+pub struct InterruptInfo {
+    pub capabilities_readable: bool, // Full config space readable (requires root)
+    pub msi_vectors: Option<u32>,    // Max MSI vectors, if MSI capable
+    pub msix_vectors: Option<u32>,   // MSI-X table size, if MSI-X capable
+    pub active_mode: Option<String>, // "msi"/"msix" from msi_irqs, "intx" if only legacy IRQ
+    pub active_vectors: usize,
+}
+
+impl InterruptInfo {
+    /// Device supports neither MSI nor MSI-X (legacy INTx only)
+    pub fn is_intx_only(&self) -> bool {
+        self.capabilities_readable && self.msi_vectors.is_none() && self.msix_vectors.is_none()
+    }
+}
+
+/// Read interrupt capabilities (PCI config space) and current mode (msi_irqs)
+pub fn get_interrupt_info(pci_address: &str) -> InterruptInfo {
+    let base = PathBuf::from(format!("/sys/bus/pci/devices/{}", pci_address));
+    let mut info = InterruptInfo::default();
+
+    // Walk the capability list in config space
+    // Unprivileged reads only return the first 64 bytes, so capabilities are unreadable
+    if let Ok(config) = fs::read(base.join("config")) && config.len() > 0x40 {
+        info.capabilities_readable = true;
+
+        let status = u16::from_le_bytes([config[0x06], config[0x07]]);
+        let has_cap_list = status & 0x10 != 0;
+        let mut ptr = if has_cap_list { (config[0x34] & 0xfc) as usize } else { 0 };
+        let mut visited = 0;
+
+        while ptr >= 0x40 && ptr + 3 < config.len() && visited < 48 {
+            let cap_id = config[ptr];
+            let control = u16::from_le_bytes([config[ptr + 2], config[ptr + 3]]);
+
+            match cap_id {
+                // MSI: Multiple Message Capable (bits 3:1) encodes log2(vectors)
+                0x05 => info.msi_vectors = Some(1 << ((control >> 1) & 0x7)),
+                // MSI-X: Table Size (bits 10:0) is N-1
+                0x11 => info.msix_vectors = Some((control & 0x7ff) as u32 + 1),
+                _ => {}
+            }
+
+            ptr = (config[ptr + 1] & 0xfc) as usize;
+            visited += 1;
+        }
+    }
+
+    // Currently allocated vectors: msi_irqs/<irq> contains "msi" or "msix"
+    if let Ok(entries) = fs::read_dir(base.join("msi_irqs")) {
+        let modes: Vec<String> = entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| fs::read_to_string(e.path()).ok())
+            .map(|m| m.trim().to_string())
+            .collect();
+
+        info.active_vectors = modes.len();
+        info.active_mode = modes.into_iter().next();
+    }
+
+    if info.active_mode.is_none() {
+        let irq = fs::read_to_string(base.join("irq"))
+            .ok()
+            .and_then(|s| s.trim().parse::<u32>().ok())
+            .unwrap_or(0);
+        if irq > 0 && base.join("driver").exists() {
+            info.active_mode = Some("intx".to_string());
+            info.active_vectors = 1;
+        }
+    }
+
+    info
+}
+
+/// A device temperature sensor from hwmon, in °C
+#[derive(Debug, Clone)]
+pub struct TempSensor {
+    pub label: String,
+    pub current: f64,
+    pub limit: Option<f64>, // Critical threshold, else max
+}
+
+/// Degrees below the limit at which a sensor counts as near it
+const THERMAL_MARGIN_C: f64 = 10.0;
+
+impl TempSensor {
+    /// Within THERMAL_MARGIN_C of the limit: likely to throttle under sustained load
+    pub fn near_limit(&self) -> bool {
+        self.limit.is_some_and(|limit| self.current >= limit - THERMAL_MARGIN_C)
+    }
+}
+
+/// Power and thermal state of a device, where the kernel exposes it (best effort)
+#[derive(Debug, Clone, Default)]
+pub struct ThermalInfo {
+    pub power_state: Option<String>,      // D0, D3hot, ... from power_state
+    pub slot_power_limit: Option<f64>,    // Watts, from PCIe Device Capabilities (requires root)
+    pub power_draw: Option<f64>,          // Watts, from hwmon power1_average/power1_input
+    pub power_cap: Option<f64>,           // Watts, from hwmon power1_cap
+    pub temperatures: Vec<TempSensor>,
+}
+
+impl ThermalInfo {
+    pub fn near_thermal_limit(&self) -> bool {
+        self.temperatures.iter().any(TempSensor::near_limit)
+    }
+}
+
+/// Captured Slot Power Limit from the PCI Express capability, in watts
+/// Device Capabilities (cap + 4): value in bits 25:18, scale in bits 27:26
+fn parse_slot_power_limit(config: &[u8]) -> Option<f64> {
+    let status = u16::from_le_bytes([*config.get(0x06)?, *config.get(0x07)?]);
+    if status & 0x10 == 0 {
+        return None;
+    }
+
+    let mut ptr = (*config.get(0x34)? & 0xfc) as usize;
+    let mut visited = 0;
+    while ptr >= 0x40 && ptr + 7 < config.len() && visited < 48 {
+        if config[ptr] == 0x10 {
+            let caps = u32::from_le_bytes([config[ptr + 4], config[ptr + 5], config[ptr + 6], config[ptr + 7]]);
+            let value = ((caps >> 18) & 0xff) as f64;
+            let scale = [1.0, 0.1, 0.01, 0.001][((caps >> 26) & 0x3) as usize];
+            return (value > 0.0).then_some(value * scale);
+        }
+        ptr = (config[ptr + 1] & 0xfc) as usize;
+        visited += 1;
+    }
+    None
+}
+
+/// Read a hwmon value and scale it (millidegrees, microwatts)
+fn read_hwmon(dir: &std::path::Path, name: &str, divisor: f64) -> Option<f64> {
+    fs::read_to_string(dir.join(name))
+        .ok()
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .map(|v| v / divisor)
+}
+
+/// Read power state, slot power limit and hwmon sensors linked to a device
+pub fn get_thermal_info(pci_address: &str) -> ThermalInfo {
+    let base = PathBuf::from(format!("/sys/bus/pci/devices/{}", pci_address));
+    let mut info = ThermalInfo {
+        power_state: fs::read_to_string(base.join("power_state")).ok().map(|s| s.trim().to_string()),
+        slot_power_limit: fs::read(base.join("config")).ok().and_then(|c| parse_slot_power_limit(&c)),
+        ..Default::default()
+    };
+
+    let Ok(hwmons) = fs::read_dir(base.join("hwmon")) else {
+        return info;
+    };
+
+    for hwmon in hwmons.flatten() {
+        let dir = hwmon.path();
+
+        info.power_draw = info.power_draw
+            .or_else(|| read_hwmon(&dir, "power1_average", 1_000_000.0))
+            .or_else(|| read_hwmon(&dir, "power1_input", 1_000_000.0));
+        info.power_cap = info.power_cap.or_else(|| read_hwmon(&dir, "power1_cap", 1_000_000.0));
+
+        let Ok(entries) = fs::read_dir(&dir) else { continue };
+        let mut inputs: Vec<String> = entries
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|n| n.starts_with("temp") && n.ends_with("_input"))
+            .collect();
+        inputs.sort();
+
+        for input in inputs {
+            let sensor = input.trim_end_matches("_input");
+            let Some(current) = read_hwmon(&dir, &input, 1000.0) else { continue };
+            let label = fs::read_to_string(dir.join(format!("{}_label", sensor)))
+                .map(|l| l.trim().to_string())
+                .unwrap_or_else(|_| sensor.to_string());
+            let limit = read_hwmon(&dir, &format!("{}_crit", sensor), 1000.0)
+                .or_else(|| read_hwmon(&dir, &format!("{}_max", sensor), 1000.0))
+                .filter(|l| *l > 0.0);
+
+            info.temperatures.push(TempSensor { label, current, limit });
+        }
+    }
+
+    info
+}
+
+/// AER error counters of one severity, from aer_dev_{correctable,nonfatal,fatal}
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AerCounts {
+    pub total: u64,
+    pub nonzero: Vec<(String, u64)>,  // Individual error types that have occurred
+}
+
+/// Health-relevant PCI state of a device (None = not exposed by this kernel/device)
+#[derive(Debug, Clone, Default)]
+pub struct HealthInfo {
+    pub enabled: Option<bool>,         // enable: a driver has enabled the device
+    pub broken_parity: Option<bool>,   // broken_parity_status
+    pub aer_correctable: Option<AerCounts>,
+    pub aer_nonfatal: Option<AerCounts>,
+    pub aer_fatal: Option<AerCounts>,
+}
+
+impl HealthInfo {
+    /// Any PCIe error logged since boot, of any severity
+    pub fn has_aer_errors(&self) -> bool {
+        [&self.aer_correctable, &self.aer_nonfatal, &self.aer_fatal]
+            .iter()
+            .any(|c| c.as_ref().is_some_and(|c| c.total > 0))
+    }
+}
+
+/// Parse an aer_dev_* file: one "<error> <count>" per line, ending with a TOTAL_ERR_* line
+fn parse_aer_counts(content: &str) -> AerCounts {
+    let mut counts = AerCounts::default();
+    let mut total = None;
+
+    for line in content.lines() {
+        let Some((name, count)) = line.split_once(' ') else { continue };
+        let Ok(count) = count.trim().parse::<u64>() else { continue };
+
+        if name.starts_with("TOTAL_") {
+            total = Some(count);
+        } else if count > 0 {
+            counts.nonzero.push((name.to_string(), count));
+        }
+    }
+
+    counts.total = total.unwrap_or_else(|| counts.nonzero.iter().map(|(_, c)| c).sum());
+    counts
+}
+
+/// Read the enable and parity flags and AER error counters of a device
+pub fn get_health_info(pci_address: &str) -> HealthInfo {
+    let base = PathBuf::from(format!("/sys/bus/pci/devices/{}", pci_address));
+    let flag = |name: &str| fs::read_to_string(base.join(name)).ok().map(|v| v.trim() != "0");
+    let aer = |name: &str| fs::read_to_string(base.join(name)).ok().map(|c| parse_aer_counts(&c));
+
+    HealthInfo {
+        enabled: flag("enable"),
+        broken_parity: flag("broken_parity_status"),
+        aer_correctable: aer("aer_dev_correctable"),
+        aer_nonfatal: aer("aer_dev_nonfatal"),
+        aer_fatal: aer("aer_dev_fatal"),
+    }
+}
+
+/// Offload features of a kernel-mode interface (None = unknown / query failed)
+#[derive(Debug, Clone, Default)]
+pub struct OffloadInfo {
+    pub rx_checksum: Option<bool>,
+    pub tx_checksum: Option<bool>,
+    pub tso: Option<bool>,
+    pub gro: Option<bool>,
+    pub rx_queues: Option<usize>, // RSS queues, from /sys/class/net/<iface>/queues
+    pub tx_queues: Option<usize>,
+}
+
+// Legacy ethtool commands (linux/ethtool.h), each taking a struct ethtool_value
+const ETHTOOL_GRXCSUM: u32 = 0x14;
+const ETHTOOL_GTXCSUM: u32 = 0x16;
+const ETHTOOL_GTSO: u32 = 0x1e;
+const ETHTOOL_GGRO: u32 = 0x2b;
+
+nix::ioctl_readwrite_bad!(siocethtool, 0x8946, IfReqEthtool);
+
+/// struct ethtool_value (linux/ethtool.h)
+#[repr(C)]
+struct EthtoolValue {
+    cmd: u32,
+    data: u32,
+}
+
+/// struct ifreq with the ifr_data member of the union (linux/if.h)
+#[repr(C)]
+pub struct IfReqEthtool {
+    ifr_name: [u8; 16],
+    ifr_data: *mut EthtoolValue,
+    _pad: [u8; 16], // Pad the union to its full 24 bytes
+}
+
+/// Query a single legacy ethtool boolean feature via SIOCETHTOOL
+fn ethtool_get_value(fd: i32, interface: &str, cmd: u32) -> Option<bool> {
+    let name = interface.as_bytes();
+    if name.len() >= 16 {
+        return None;
+    }
+
+    let mut value = EthtoolValue { cmd, data: 0 };
+    let mut ifr = IfReqEthtool {
+        ifr_name: [0; 16],
+        ifr_data: &mut value,
+        _pad: [0; 16],
+    };
+    ifr.ifr_name[..name.len()].copy_from_slice(name);
+
+    // SAFETY: fd is a valid socket; ifr is a correctly laid out ifreq pointing at `value`,
+    // which outlives the call
+    unsafe { siocethtool(fd, &mut ifr) }.ok()?;
+
+    Some(value.data != 0)
+}
+
+/// Count queue directories (rx-N / tx-N) for an interface
+fn count_queues(interface: &str, prefix: &str) -> Option<usize> {
+    let entries = fs::read_dir(format!("/sys/class/net/{}/queues", interface)).ok()?;
+    Some(entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().starts_with(prefix))
+        .count())
+}
+
+/// Read offload features (checksum, TSO, GRO) and queue counts for a kernel-mode interface
+pub fn get_offload_info(interface: &str) -> OffloadInfo {
+    use std::os::fd::AsRawFd;
+
+    let mut info = OffloadInfo {
+        rx_queues: count_queues(interface, "rx-"),
+        tx_queues: count_queues(interface, "tx-"),
+        ..Default::default()
+    };
+
+    // Any socket works as a handle for SIOCETHTOOL
+    if let Ok(socket) = std::net::UdpSocket::bind("0.0.0.0:0") {
+        let fd = socket.as_raw_fd();
+        info.rx_checksum = ethtool_get_value(fd, interface, ETHTOOL_GRXCSUM);
+        info.tx_checksum = ethtool_get_value(fd, interface, ETHTOOL_GTXCSUM);
+        info.tso = ethtool_get_value(fd, interface, ETHTOOL_GTSO);
+        info.gro = ethtool_get_value(fd, interface, ETHTOOL_GGRO);
+    }
+
+    info
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn altnames_from_ip_link() {
+        let output = "2: eth0: <BROADCAST,MULTICAST,UP> mtu 1500 qdisc mq state UP mode DEFAULT \\    link/ether 52:54:00:12:34:56 brd ff:ff:ff:ff:ff:ff\\    altname enp0s4\\    altname ens4\n";
+        assert_eq!(parse_altnames(output), vec!["enp0s4", "ens4"]);
+        assert!(parse_altnames("1: lo: <LOOPBACK,UP> mtu 65536\n").is_empty());
+    }
+
+    #[test]
+    fn aer_counts_use_total_line() {
+        let content = "RxErr 3\nBadTLP 0\nBadDLLP 2\nTimeout 0\nTOTAL_ERR_COR 5\n";
+        let counts = parse_aer_counts(content);
+        assert_eq!(counts.total, 5);
+        assert_eq!(counts.nonzero, vec![("RxErr".to_string(), 3), ("BadDLLP".to_string(), 2)]);
+        assert_eq!(parse_aer_counts("Undefined 0\nTOTAL_ERR_FATAL 0\n").total, 0);
+    }
+
+    #[test]
+    fn pci_address_needs_full_bdf() {
+        assert!(is_valid_pci_address("0000:01:00.0"));
+        assert!(is_valid_pci_address("0000:d1:1f.7"));
+        assert!(!is_valid_pci_address("usb1"));
+        assert!(!is_valid_pci_address("1-2:1.0"));
+        assert!(!is_valid_pci_address("01:00.0"));
+    }
+
+    #[test]
+    fn driver_override_unset_is_null() {
+        assert_eq!(parse_driver_override("(null)\n"), None);
+        assert_eq!(parse_driver_override("\n"), None);
+        assert_eq!(parse_driver_override("vfio-pci\n").as_deref(), Some("vfio-pci"));
+    }
+
+    #[test]
+    fn class_filter_matches_prefix() {
+        let network = parse_class_filter("02").unwrap();
+        assert!(class_matches("0x020000\n", &network));
+        assert!(!class_matches("0x030000", &network));
+
+        let nvme = parse_class_filter("0x0108").unwrap();
+        assert!(class_matches("0x010802", &nvme));
+        assert!(!class_matches("0x010601", &nvme));
+
+        assert!(parse_class_filter("vga").is_err());
+        assert!(parse_class_filter("0x").is_err());
+    }
+
+    #[test]
+    fn link_speed_formatting() {
+        assert_eq!(format_link_speed("25000\n"), "25G");
+        assert_eq!(format_link_speed("100"), "100M");
+        assert_eq!(format_link_speed("-1"), "no link");
+        assert_eq!(format_link_speed(""), "?");
+        assert_eq!(format_link_speed("unknown\n"), "unknown");
+        assert_eq!(format_link_speed("a-very-long-driver-value"), "a-very-long…");
+    }
+
+    #[test]
+    fn slot_power_limit_from_express_capability() {
+        let mut config = vec![0u8; 0x100];
+        config[0x06] = 0x10; // Capabilities list present
+        config[0x34] = 0x40;
+        config[0x40] = 0x05; // MSI, next at 0x60
+        config[0x41] = 0x60;
+        config[0x60] = 0x10; // PCI Express
+        let caps: u32 = (250 << 18) | (1 << 26); // 250 x 0.1 W
+        config[0x64..0x68].copy_from_slice(&caps.to_le_bytes());
+        assert_eq!(parse_slot_power_limit(&config), Some(25.0));
+
+        config[0x64..0x68].copy_from_slice(&0u32.to_le_bytes());
+        assert_eq!(parse_slot_power_limit(&config), None);
+        assert_eq!(parse_slot_power_limit(&config[..0x40]), None);
+    }
+
+    #[test]
+    fn pci_slot_strips_function() {
+        assert_eq!(pci_slot("0000:03:00.1"), Some("0000:03:00"));
+        assert_eq!(pci_slot("0000:af:00.0"), Some("0000:af:00"));
+        assert_eq!(pci_slot("0000:03:00"), None);
+        assert_eq!(pci_slot("0000:03:00."), None);
+    }
+
+    #[test]
+    fn uevent_matches_sysfs_file_format() {
+        let uevent = "DRIVER=ixgbe\nPCI_CLASS=20000\nPCI_ID=8086:10FB\nPCI_SUBSYS_ID=8086:000C\n\
+            PCI_SLOT_NAME=0000:03:00.0\nMODALIAS=pci:v00008086d000010FBsv00008086sd0000000Cbc02sc00i00\n";
+        assert_eq!(parse_uevent(uevent), Some(PciUevent {
+            class: "0x020000".to_string(),
+            vendor_id: "0x8086".to_string(),
+            device_id: "0x10fb".to_string(),
+            driver: Some("ixgbe".to_string()),
+            subsystem: Some(("0x8086".to_string(), "0x000c".to_string())),
+        }));
+
+        // Unbound device: no DRIVER line
+        let unbound = parse_uevent("PCI_CLASS=20000\nPCI_ID=15B3:101F\n").unwrap();
+        assert_eq!(unbound.driver, None);
+
+        // Incomplete uevent falls back to the individual files
+        assert_eq!(parse_uevent("DRIVER=ixgbe\n"), None);
+    }
+
+    #[test]
+    fn uevent_parity_with_sysfs_files() {
+        // Compare against the individual files on whatever PCI devices this host has
+        let Ok(entries) = fs::read_dir("/sys/bus/pci/devices") else {
+            return;
+        };
+
+        for entry in entries.filter_map(|e| e.ok()) {
+            let pci = entry.file_name().to_string_lossy().to_string();
+            let Some(uevent) = read_uevent(&pci) else {
+                continue;
+            };
+            let (vendor, device) = get_vendor_device_id(&pci).unwrap();
+
+            assert_eq!(uevent.vendor_id, vendor, "{}", pci);
+            assert_eq!(uevent.device_id, device, "{}", pci);
+            assert_eq!(Some(uevent.class), get_pci_class(&pci), "{}", pci);
+            assert_eq!(uevent.driver, get_driver(&pci), "{}", pci);
+            assert_eq!(uevent.subsystem, get_subsystem_ids(&pci), "{}", pci);
+        }
+    }
+
+    #[test]
+    fn network_class_separates_wireless() {
+        assert_eq!(classify_network_class("0x020000"), Some(NetworkClass::Wired)); // Ethernet
+        assert_eq!(classify_network_class("0x020700"), Some(NetworkClass::Wired)); // InfiniBand
+        assert_eq!(classify_network_class("0x028000\n"), Some(NetworkClass::Wireless)); // Wi-Fi
+        assert_eq!(classify_network_class("0x0d8000"), Some(NetworkClass::Wireless)); // WWAN
+        assert_eq!(classify_network_class("0x010802"), None); // NVMe
+    }
+
+    #[test]
+    fn max_speed_known_devices() {
+        assert_eq!(get_max_speed("0x15b3", "0x101f").as_deref(), Some("25G")); // ConnectX-4 Lx
+        assert_eq!(get_max_speed("0x8086", "0x1572").as_deref(), Some("10G")); // X710
+        assert_eq!(get_max_speed("0x8086", "0x158b").as_deref(), Some("25G")); // XXV710
+        assert_eq!(get_max_speed("0x8086", "0x1583").as_deref(), Some("40G")); // XL710
+        assert_eq!(get_max_speed("0x8086", "0x1592").as_deref(), Some("100G")); // E810
+        assert_eq!(get_max_speed("0x8086", "0x1521").as_deref(), Some("1G")); // I350
+    }
+
+    #[test]
+    fn max_speed_unknown_device() {
+        assert_eq!(get_max_speed("0x1af4", "0x1041"), None);
+        assert_eq!(get_max_speed("0x8086", "0xffff"), None);
+    }
+
+    #[test]
+    fn description_known_devices() {
+        assert_eq!(get_device_description("0x15b3", "0x101f"), "Mellanox ConnectX-4 Lx");
+        assert_eq!(get_device_description("0x8086", "0x1572"), "Intel X710 10GbE");
+        assert_eq!(get_device_description("0x8086", "0x158a"), "Intel XXV710 25GbE");
+        assert_eq!(get_device_description("0x1924", "0x0803"), "Solarflare NIC");
+    }
+
+    #[test]
+    fn description_falls_back_to_speed() {
+        // Chelsio T5 is in the speed table but has no model name
+        assert_eq!(get_device_description("0x1425", "0x5410"), "40G NIC");
+        assert_eq!(get_device_description("0x1af4", "0x1041"), "Network Card");
+    }
+}
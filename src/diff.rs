@@ -0,0 +1,156 @@
+use std::fs;
+use std::path::Path;
+use anyhow::Result;
+use colored::Colorize;
+
+use crate::config::Config;
+use crate::{device, vfio};
+
+/// Mode a device is configured for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Vfio,
+    Kernel,
+}
+
+/// A configured device that is not in its configured mode
+#[derive(Debug, Clone)]
+pub struct Delta {
+    pub interface: String,
+    pub pci_address: String,
+    pub current: &'static str,  // "vfio", "kernel" or "unbound"
+    pub wanted: Mode,
+}
+
+/// Configured vs current state of every configured device
+#[derive(Debug, Clone, Default)]
+pub struct ConfigDiff {
+    pub deltas: Vec<Delta>,
+    pub in_sync: usize,
+    pub missing: Vec<String>,  // Configured but not present; nothing to converge
+}
+
+/// PCI address of a configured interface: stored mapping first, then the live netdev
+fn resolve_pci(config: &Config, interface: &str) -> Option<String> {
+    config.devices.pci_mappings
+        .get(interface)
+        .cloned()
+        .or_else(|| {
+            fs::read_link(format!("/sys/class/net/{}/device", interface))
+                .ok()
+                .and_then(|t| t.file_name().map(|n| n.to_string_lossy().to_string()))
+        })
+        .filter(|pci| Path::new("/sys/bus/pci/devices").join(pci).exists())
+}
+
+/// Compare each configured device's current driver with its configured mode
+pub fn compute(config: &Config) -> ConfigDiff {
+    let mut diff = ConfigDiff::default();
+
+    let configured = config.devices.vfio.iter().map(|i| (i, Mode::Vfio))
+        .chain(config.devices.kernel.iter().map(|i| (i, Mode::Kernel)));
+
+    for (interface, wanted) in configured {
+        let Some(pci_address) = resolve_pci(config, interface) else {
+            diff.missing.push(interface.clone());
+            continue;
+        };
+
+        let current = match device::get_driver(&pci_address).as_deref() {
+            Some("vfio-pci") => "vfio",
+            Some(_) => "kernel",
+            None => "unbound",
+        };
+
+        let matches = match wanted {
+            Mode::Vfio => current == "vfio",
+            Mode::Kernel => current == "kernel",
+        };
+
+        if matches {
+            diff.in_sync += 1;
+        } else {
+            diff.deltas.push(Delta { interface: interface.clone(), pci_address, current, wanted });
+        }
+    }
+
+    diff
+}
+
+fn mode_name(mode: Mode) -> &'static str {
+    match mode {
+        Mode::Vfio => "vfio",
+        Mode::Kernel => "kernel",
+    }
+}
+
+/// Print the deltas between config and current state
+pub fn show(diff: &ConfigDiff) {
+    for delta in &diff.deltas {
+        println!("  {} {} ({}): {} → {}",
+            "~".bright_yellow(),
+            delta.interface.bright_white(),
+            delta.pci_address,
+            delta.current,
+            mode_name(delta.wanted).bright_green());
+    }
+
+    for interface in &diff.missing {
+        println!("  {} {}: {}", "?".bright_red(), interface.bright_white(), "not present".bright_red());
+    }
+
+    if diff.deltas.is_empty() && diff.missing.is_empty() {
+        println!("{}", format!("✓ All {} configured device(s) are in their configured mode", diff.in_sync).bright_green());
+    } else {
+        println!();
+        println!("{} to change, {} in sync, {} missing", diff.deltas.len(), diff.in_sync, diff.missing.len());
+    }
+}
+
+/// Bind/unbind exactly the devices in `diff.deltas`, leaving everything else untouched
+/// With `dry_run`, only print what would change; `force` skips the bind safety checks
+pub fn converge(config: &Config, diff: &ConfigDiff, dry_run: bool, force: bool) -> Result<()> {
+    if diff.deltas.is_empty() {
+        println!("{}", "Nothing to change.".bright_green());
+        return Ok(());
+    }
+
+    let to_vfio: Vec<&str> = diff.deltas
+        .iter()
+        .filter(|d| d.wanted == Mode::Vfio)
+        .map(|d| d.interface.as_str())
+        .collect();
+
+    // By PCI address: these may have no netdev (bound to vfio-pci or driverless)
+    let to_kernel: Vec<&str> = diff.deltas
+        .iter()
+        .filter(|d| d.wanted == Mode::Kernel)
+        .map(|d| d.pci_address.as_str())
+        .collect();
+
+    if dry_run {
+        println!("{}", "Dry run - no changes made".bright_yellow());
+        if !to_vfio.is_empty() {
+            println!("  Would bind to vfio-pci: {}", to_vfio.join(", "));
+        }
+        if !to_kernel.is_empty() {
+            println!("  Would return to kernel: {}", to_kernel.join(", "));
+        }
+        return Ok(());
+    }
+
+    println!();
+    if !to_kernel.is_empty() {
+        vfio::unbind_interfaces(&to_kernel, true)?;
+    }
+    if !to_vfio.is_empty() {
+        vfio::bind_interfaces(&to_vfio, force)?;
+        if config.options.set_permissions {
+            vfio::set_vfio_permissions()?;
+        }
+    }
+
+    println!();
+    println!("{}", format!("✓ Converged {} device(s)", diff.deltas.len()).bright_green());
+    Ok(())
+}
@@ -1,13 +1,14 @@
 use anyhow::Result;
 use colored::Colorize;
-use tabled::{Table, Tabled, settings::Style};
+use tabled::{Table, Tabled, settings::{Disable, Style, location::ByColumnName}};
 use serde_json::json;
 
-use crate::device::{NetworkDevice, DeviceStatus};
-use crate::iommu::{SystemStatus, SystemIssue};
-use crate::config::Config;
+use crate::device::{NetworkDevice, DeviceStatus, SriovRole, InterruptInfo, OffloadInfo, ThermalInfo, HealthInfo};
+use crate::iommu::{SystemStatus, IommuHardware, KernelInfo, SystemIssue, IssueSeverity, ContainerProbe, IommuGroup, VFIO_API_VERSION};
+use crate::config::{Config, ValidationIssue};
 use crate::grub::CpuVendor;
-use crate::frameworks::{Framework, FrameworkDevice};
+use crate::frameworks::{Dependency, Framework, FrameworkDevice};
+use crate::snapshot::{DeviceFingerprint, InventoryChange};
 
 #[derive(Tabled)]
 struct DeviceRow {
@@ -29,11 +30,17 @@ struct DeviceRow {
     #[tabled(rename = "STATUS")]
     status: String,
 
+    #[tabled(rename = "SR-IOV")]
+    sriov: String,
+
     #[tabled(rename = "MAX SPEED")]
     max_speed: String,
 
     #[tabled(rename = "LINK")]
     speed: String,
+
+    #[tabled(rename = "BOUND FOR")]
+    bound_since: String,
 }
 
 /// Show device table
@@ -53,14 +60,19 @@ pub fn show_device_table(devices: &[NetworkDevice], verbose: bool) -> Result<()>
                 .map(|g| g.to_string())
                 .unwrap_or_else(|| "N/A".to_string()),
             vendor_device: d.vendor_device(),
-            status: status_to_string(&d.status),
+            status: device_status_label(d),
+            sriov: d.sriov_role.short_name().to_string(),
             max_speed: d.max_speed.clone().unwrap_or_else(|| "?".to_string()),
             speed: d.speed.clone().unwrap_or_else(|| "-".to_string()),
+            bound_since: if d.status == DeviceStatus::Vfio { bound_duration(&d.pci_address) } else { "-".to_string() },
         })
         .collect();
 
     let mut table = Table::new(rows);
     table.with(Style::modern());
+    if !verbose {
+        table.with(Disable::column(ByColumnName::new("BOUND FOR")));
+    }
 
     println!("{}", table);
 
@@ -70,11 +82,63 @@ pub fn show_device_table(devices: &[NetworkDevice], verbose: bool) -> Result<()>
         println!("  {} - Bound to vfio-pci (kernel bypass)", "VFIO".bright_green());
         println!("  {} - Bound to kernel driver (normal networking)", "kernel".bright_yellow());
         println!("  {} - No driver bound", "unbound".bright_red());
+        println!("  BOUND FOR - time on vfio-pci (~ = estimated from the driver link, not recorded by vfio-tool)");
     }
 
     Ok(())
 }
 
+/// Print devices in the stable `--porcelain` format: one tab-separated line per device
+/// Fields: pci_address, interface, status, driver, iommu_group, vendor:device, sriov.
+/// Missing values are "-". The fields and their order are a public interface; only append.
+pub fn show_device_porcelain(devices: &[NetworkDevice]) {
+    for d in devices {
+        let interface = if d.interface.starts_with('(') { "-" } else { d.interface.as_str() };
+        let id = format!("{}:{}",
+            d.vendor_id.trim_start_matches("0x").to_lowercase(),
+            d.device_id.trim_start_matches("0x").to_lowercase());
+
+        println!("{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            d.pci_address,
+            interface,
+            status_to_string(&d.status),
+            d.driver.as_deref().unwrap_or("-"),
+            d.iommu_group.map(|g| g.to_string()).unwrap_or_else(|| "-".to_string()),
+            id,
+            d.sriov_role.short_name());
+    }
+}
+
+/// Compact duration, e.g. "3d 4h", "2h 5m", "45s"
+pub fn format_duration(secs: u64) -> String {
+    let (days, hours, mins) = (secs / 86400, secs % 86400 / 3600, secs % 3600 / 60);
+    match (days, hours, mins) {
+        (0, 0, 0) => format!("{}s", secs),
+        (0, 0, m) => format!("{}m", m),
+        (0, h, m) => format!("{}h {}m", h, m),
+        (d, h, _) => format!("{}d {}h", d, h),
+    }
+}
+
+/// How long a vfio-pci device has been bound ("unknown" if no time is available)
+/// Times from the driver symlink are estimates and marked with "~"
+fn bound_duration(pci_address: &str) -> String {
+    let Some((since, source)) = crate::vfio::bound_since(pci_address) else {
+        return "unknown".to_string();
+    };
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(since);
+    let duration = format_duration(now.saturating_sub(since));
+
+    match source {
+        crate::vfio::BoundSinceSource::Recorded => duration,
+        crate::vfio::BoundSinceSource::DriverLink => format!("~{}", duration),
+    }
+}
+
 /// Show system status
 pub fn show_system_status(status: &SystemStatus) -> Result<()> {
     println!("{}", "═══════════════════════════════════════".bright_cyan());
@@ -82,14 +146,57 @@ pub fn show_system_status(status: &SystemStatus) -> Result<()> {
     println!("{}", "═══════════════════════════════════════".bright_cyan());
     println!();
 
-    let check = if status.iommu_enabled { "✓".bright_green() } else { "✗".bright_red() };
+    match status.iommu_hardware {
+        IommuHardware::Present(table) => println!("{} IOMMU Hardware: {}", "✓".bright_green(), table.bright_green()),
+        IommuHardware::Absent => println!("{} IOMMU Hardware: {}", "✗".bright_red(), "None reported by firmware (no DMAR/IVRS ACPI table)".bright_red()),
+        IommuHardware::Unknown => println!("{} IOMMU Hardware: {}", "ℹ".bright_blue(), "unknown (no ACPI tables)".bright_black()),
+    }
+
+    let check = if status.iommu_on { "✓".bright_green() } else { "✗".bright_red() };
     println!("{} IOMMU Enabled: {}", check,
-        if status.iommu_enabled { "Yes".bright_green() } else { "No".bright_red() });
+        if status.iommu_on { "Yes".bright_green() } else { "No".bright_red() });
+
+    let check = if status.passthrough { "✓".bright_green() } else { "⚠".bright_yellow() };
+    println!("{} IOMMU Passthrough (iommu=pt): {}", check,
+        if status.passthrough { "Yes".bright_green() } else { "No (recommended for performance)".bright_yellow() });
+
+    if !status.pending_params.is_empty() {
+        println!("{} Reboot pending: {} configured in GRUB but not active", "⟳".bright_yellow(),
+            status.pending_params.join(" ").bright_cyan());
+    }
+
+    if !status.passthrough_flags.is_empty() {
+        println!("{} Passthrough flags:", "ℹ".bright_blue());
+        for flag in &status.passthrough_flags {
+            println!("    {:32} {}", flag.param.bright_cyan(), flag.meaning.bright_black());
+        }
+    }
 
     let check = if status.vfio_module_loaded { "✓".bright_green() } else { "✗".bright_red() };
     println!("{} VFIO Module Loaded: {}", check,
         if status.vfio_module_loaded { "Yes".bright_green() } else { "No".bright_red() });
 
+    let backend_loaded = status.modules.iter()
+        .any(|m| (m.name == "vfio_iommu_type1" || m.name == "iommufd") && m.loaded);
+    for module in &status.modules {
+        // Missing optional modules are informational, not failures
+        let optional = match module.name {
+            "vfio_virqfd" => true,
+            "vfio_iommu_type1" | "iommufd" => backend_loaded,
+            _ => false,
+        };
+        let check = if module.loaded {
+            "✓".bright_green()
+        } else if optional {
+            "○".bright_black()
+        } else {
+            "✗".bright_red()
+        };
+        println!("    {} {:18} {}", check, module.name, module.role.bright_black());
+    }
+
+    show_kernel_info(&status.kernel);
+
     let cpu_str = match status.cpu_vendor {
         CpuVendor::Intel => "Intel",
         CpuVendor::AMD => "AMD",
@@ -105,16 +212,219 @@ pub fn show_system_status(status: &SystemStatus) -> Result<()> {
 
     println!();
 
-    if status.iommu_enabled && status.vfio_module_loaded && status.iommu_groups_count > 0 {
+    if status.is_ready() {
         println!("{}", "System is ready for VFIO!".bright_green().bold());
+        if !status.passthrough {
+            println!("Add {} to kernel parameters for better DMA performance ({}).",
+                "iommu=pt".bright_cyan(), "vfio-tool setup-grub".bright_cyan());
+        }
     } else {
         println!("{}", "System is NOT ready for VFIO.".bright_red().bold());
+        if status.iommu_hardware == IommuHardware::Absent {
+            println!("{}", "No IOMMU reported by firmware - enable VT-d/AMD-Vi in BIOS/UEFI if the platform has one.".bright_red());
+        }
+        if !status.pending_params.is_empty() {
+            println!("{}", "Reboot to activate the pending IOMMU changes.".bright_yellow());
+        }
         println!("Run {} to check for issues.", "vfio-tool check".bright_cyan());
     }
 
     Ok(())
 }
 
+/// Print system status in the stable `--porcelain` format: one `key<TAB>value` line per item
+/// Keys are printed in a fixed order and never renamed; new keys are only appended.
+pub fn show_system_status_porcelain(status: &SystemStatus) {
+    let yes_no = |b: bool| if b { "yes" } else { "no" };
+    let hardware = match status.iommu_hardware {
+        IommuHardware::Present(_) => "present",
+        IommuHardware::Absent => "absent",
+        IommuHardware::Unknown => "unknown",
+    };
+    let cpu = match status.cpu_vendor {
+        CpuVendor::Intel => "intel",
+        CpuVendor::AMD => "amd",
+        CpuVendor::Unknown => "unknown",
+    };
+
+    println!("ready\t{}", yes_no(status.is_ready()));
+    println!("iommu_hardware\t{}", hardware);
+    println!("iommu_enabled\t{}", yes_no(status.iommu_on));
+    println!("iommu_passthrough\t{}", yes_no(status.passthrough));
+    println!("reboot_pending\t{}", yes_no(!status.pending_params.is_empty()));
+    println!("vfio_module_loaded\t{}", yes_no(status.vfio_module_loaded));
+    println!("iommu_groups\t{}", status.iommu_groups_count);
+    println!("vfio_devices\t{}", status.vfio_devices_count);
+    println!("cpu_vendor\t{}", cpu);
+    println!("kernel\t{}", status.kernel.release);
+}
+
+/// Show kernel release and VFIO-relevant build options
+fn show_kernel_info(kernel: &KernelInfo) {
+    println!("{} Kernel: {}", "ℹ".bright_blue(), kernel.release.bright_cyan());
+
+    let Some(ref source) = kernel.config_source else {
+        println!("    {} build config not available (no /boot/config-{} or /proc/config.gz)",
+            "?".bright_black(), kernel.release);
+        return;
+    };
+
+    for (name, value) in &kernel.options {
+        // Passthrough-by-default and iommufd are optional; VFIO itself is required
+        let optional = matches!(*name, "CONFIG_IOMMU_DEFAULT_PASSTHROUGH" | "CONFIG_IOMMUFD" | "CONFIG_VFIO_IOMMU_TYPE1");
+        let (check, value) = match value {
+            Some(v) => ("✓".bright_green(), v.as_str()),
+            None if optional => ("○".bright_black(), "not set"),
+            None => ("✗".bright_red(), "not set"),
+        };
+        println!("    {} {:34} {}", check, name, value);
+    }
+    println!("    {}", format!("(from {})", source).bright_black());
+}
+
+/// Show VFIO container probe result
+pub fn show_container_probe(probe: &ContainerProbe) -> Result<()> {
+    println!("{} Opened /dev/vfio/vfio", "✓".bright_green());
+
+    if probe.api_version == VFIO_API_VERSION {
+        println!("{} VFIO API version: {}", "✓".bright_green(), probe.api_version);
+    } else {
+        println!("{} VFIO API version: {} (expected {})", "⚠".bright_yellow(),
+            probe.api_version, VFIO_API_VERSION);
+    }
+
+    if probe.type1_supported {
+        println!("{} Type1 IOMMU: {}", "✓".bright_green(), "supported".bright_green());
+    } else {
+        println!("{} Type1 IOMMU: {}", "✗".bright_red(), "not supported".bright_red());
+        println!("  Try: {}", "sudo vfio-tool load-module vfio_iommu_type1".bright_cyan());
+    }
+
+    Ok(())
+}
+
+/// Show IOMMU groups and their member devices
+pub fn show_iommu_groups(groups: &[IommuGroup]) -> Result<()> {
+    if groups.is_empty() {
+        println!("{}", "No IOMMU groups found. Is IOMMU enabled?".bright_yellow());
+        return Ok(());
+    }
+
+    for group in groups {
+        let isolation = if group.is_isolated() {
+            "isolated".bright_green()
+        } else {
+            "shared".bright_yellow()
+        };
+        let viable = if group.is_viable() {
+            "yes".bright_green()
+        } else {
+            "no".bright_yellow()
+        };
+        println!("{} {} ({}, VFIO viable: {})", "IOMMU Group".bright_cyan(), group.id.to_string().bright_white(), isolation, viable);
+
+        for dev in &group.devices {
+            let kind = if dev.is_endpoint { "" } else { " [bridge]" };
+            let numa = dev.numa_node.map(|n| format!("  node {}", n)).unwrap_or_default();
+            println!("  {}  {}  {}  {}{}{}",
+                dev.pci_address,
+                dev.class,
+                dev.vendor_device,
+                dev.driver.as_deref().unwrap_or("(none)"),
+                numa,
+                kind.bright_black());
+        }
+
+        if group.spans_numa_nodes() {
+            let nodes: Vec<String> = group.numa_nodes().iter().map(u32::to_string).collect();
+            println!("  {} Group spans NUMA nodes {} - no single node is local to all members",
+                "⚠".bright_yellow(), nodes.join(", "));
+        }
+    }
+
+    Ok(())
+}
+
+/// Show IOMMU groups in JSON format
+pub fn show_iommu_groups_json(groups: &[IommuGroup]) -> Result<()> {
+    let output: Vec<_> = groups
+        .iter()
+        .map(|g| {
+            json!({
+                "group_id": g.id,
+                "isolated": g.is_isolated(),
+                "viable": g.is_viable(),
+                "numa_nodes": g.numa_nodes(),
+                "devices": g.devices.iter().map(|d| json!({
+                    "pci": d.pci_address,
+                    "class": d.class,
+                    "vendor_device": d.vendor_device,
+                    "driver": d.driver,
+                    "is_endpoint": d.is_endpoint,
+                    "numa_node": d.numa_node,
+                })).collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
+
+/// Print validation issues as JSON
+pub fn show_validation_json(issues: &[ValidationIssue]) -> Result<()> {
+    let output = json!({
+        "valid": !issues.iter().any(ValidationIssue::is_failure),
+        "issues": issues.iter().map(|i| {
+            let mut entry = json!({
+                "kind": i.kind(),
+                "interface": i.interface(),
+                "failure": i.is_failure(),
+                "message": i.describe(),
+            });
+            match i {
+                ValidationIssue::Unconfigured { pci_address, driver, .. } => {
+                    entry["pci"] = json!(pci_address);
+                    entry["driver"] = json!(driver);
+                }
+                ValidationIssue::AbsentKnown { pci_address, .. } => {
+                    entry["pci"] = json!(pci_address);
+                }
+                ValidationIssue::StubClaimed { id, .. } => {
+                    entry["id"] = json!(id);
+                }
+                ValidationIssue::MissingGroup { group } => {
+                    entry["group"] = json!(group);
+                }
+                _ => {}
+            }
+            entry
+        }).collect::<Vec<_>>(),
+    });
+
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
+
+/// One-line status for shell prompts / tmux, e.g. "VFIO: 4/12 bound, IOMMU:on, ready"
+pub fn show_summary(status: &SystemStatus, total: usize, bound: usize, color: bool) {
+    let ready = status.is_ready();
+    let iommu = if status.iommu_on { "on" } else { "off" };
+    let state = match (ready, status.pending_params.is_empty()) {
+        (true, _) => "ready",
+        (false, true) => "not ready",
+        (false, false) => "not ready (reboot pending)",
+    };
+
+    if color {
+        let state = if ready { state.green() } else { state.red() };
+        let iommu = if status.iommu_on { iommu.green() } else { iommu.red() };
+        println!("VFIO: {}/{} bound, IOMMU:{}, {}", bound, total, iommu, state);
+    } else {
+        println!("VFIO: {}/{} bound, IOMMU:{}, {}", bound, total, iommu, state);
+    }
+}
+
 /// Show device details
 pub fn show_device_details(device: &NetworkDevice) -> Result<()> {
     println!("{}", "═══════════════════════════════════════".bright_cyan());
@@ -124,6 +434,10 @@ pub fn show_device_details(device: &NetworkDevice) -> Result<()> {
 
     println!("{:20} {}", "PCI Address:", device.pci_address);
     println!("{:20} {}", "Vendor:Device:", device.vendor_device());
+    if let Some(subsystem) = device.subsystem() {
+        println!("{:20} {}", "Subsystem:", subsystem);
+    }
+    println!("{:20} {}", "Model:", device.description());
 
     if let Some(ref driver) = device.driver {
         println!("{:20} {}", "Driver:", driver);
@@ -131,8 +445,27 @@ pub fn show_device_details(device: &NetworkDevice) -> Result<()> {
         println!("{:20} {}", "Driver:", "(none)".bright_red());
     }
 
+    if device.status == DeviceStatus::Vfio {
+        println!("{:20} {}", "Bound for:", bound_duration(&device.pci_address));
+    }
+
+    // Names recorded before binding; a bound device has no netdev to read them from
+    if device.status != DeviceStatus::Kernel {
+        let altnames = crate::config::load_config()
+            .ok()
+            .and_then(|c| c.devices.altnames.get(&device.interface).cloned())
+            .unwrap_or_default();
+        if !altnames.is_empty() {
+            println!("{:20} {}", "Also known as:", altnames.join(", "));
+        }
+    }
+
     if let Some(group) = device.iommu_group {
         println!("{:20} {}", "IOMMU Group:", group);
+        if let Ok(viable) = crate::iommu::group_viable(group) {
+            let viable = if viable { "yes".bright_green() } else { "no".bright_yellow() };
+            println!("{:20} {}", "VFIO viable:", viable);
+        }
 
         // Show other devices in the same group
         if let Ok(group_devices) = crate::device::get_iommu_group_devices(group) {
@@ -155,17 +488,243 @@ pub fn show_device_details(device: &NetworkDevice) -> Result<()> {
         println!("{:20} {}", "Link Speed:", speed);
     }
 
-    println!("{:20} {}", "Status:", status_to_string(&device.status));
+    show_interrupt_info(&crate::device::get_interrupt_info(&device.pci_address));
+    show_thermal_info(&crate::device::get_thermal_info(&device.pci_address));
+    show_health_info(&crate::device::get_health_info(&device.pci_address));
+
+    match device.sriov_role {
+        SriovRole::None => {}
+        SriovRole::PhysicalFunction(num_vfs) => {
+            println!("{:20} Physical Function ({} VFs)", "SR-IOV:", num_vfs);
+        }
+        SriovRole::VirtualFunction(ref pf) => {
+            println!("{:20} Virtual Function (PF: {})", "SR-IOV:", pf);
+        }
+    }
+
+    println!("{:20} {}", "Status:", device_status_label(device));
+
+    // Offloads only exist while a kernel driver owns the interface
+    if device.status == DeviceStatus::Kernel && !device.interface.starts_with('(') {
+        show_offload_info(&crate::device::get_offload_info(&device.interface));
+    }
 
     if device.is_vfio_bound() {
-        if let Some(group) = device.iommu_group {
-            println!("{:20} /dev/vfio/{}", "Device Node:", group);
+        if device.has_vfio_node() {
+            if let Some(group) = device.iommu_group {
+                println!("{:20} /dev/vfio/{}", "Device Node:", group);
+            }
+        } else {
+            println!("{:20} {}", "Device Node:",
+                "bound but no device node — check IOMMU/driver".bright_red());
         }
     }
 
     Ok(())
 }
 
+/// Show device details as a JSON array, one object per device
+pub fn show_device_details_json(devices: &[NetworkDevice]) -> Result<()> {
+    let config = crate::config::load_config().ok();
+
+    let output: Vec<serde_json::Value> = devices
+        .iter()
+        .map(|d| {
+            let health = crate::device::get_health_info(&d.pci_address);
+            let altnames = config
+                .as_ref()
+                .and_then(|c| c.devices.altnames.get(&d.interface).cloned())
+                .unwrap_or_default();
+
+            json!({
+                "interface": d.interface,
+                "pci_address": d.pci_address,
+                "status": status_to_string(&d.status),
+                "override_pending": d.override_pending,
+                "driver": d.driver,
+                "vendor": d.vendor_device(),
+                "subsystem": d.subsystem(),
+                "model": d.description(),
+                "iommu_group": d.iommu_group,
+                "link_speed": d.speed,
+                "max_speed": d.max_speed,
+                "sriov_role": match d.sriov_role {
+                    SriovRole::None => None,
+                    _ => Some(d.sriov_role.short_name()),
+                },
+                "altnames": altnames,
+                "pci_enabled": health.enabled,
+                "broken_parity": health.broken_parity,
+                "aer_errors": health.has_aer_errors(),
+            })
+        })
+        .collect();
+
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
+
+/// Show full details of every other device sharing the device's IOMMU group
+/// Everything in the group is passed through together, so this is what a guest would also get
+pub fn show_group_members(device: &NetworkDevice) -> Result<()> {
+    let Some(group) = device.iommu_group else {
+        return Ok(());
+    };
+
+    let config = crate::config::load_config().ok();
+    let members: Vec<String> = crate::device::get_iommu_group_devices(group)?
+        .into_iter()
+        .filter(|pci| *pci != device.pci_address)
+        .collect();
+
+    println!();
+    println!("{}", format!("IOMMU Group {} Members:", group).bright_cyan());
+    if members.is_empty() {
+        println!("  {}", "(isolated - only device in group)".bright_green());
+        return Ok(());
+    }
+
+    for pci in &members {
+        println!();
+        let member = match crate::device::get_device_info_by_pci(pci, &config) {
+            Ok(member) => member,
+            Err(e) => {
+                println!("  {} {}: {}", "✗".bright_red(), pci.bright_white(), e);
+                continue;
+            }
+        };
+
+        let class = crate::device::get_pci_class(pci).unwrap_or_else(|| "unknown".to_string());
+        println!("  {}", pci.bright_white());
+        println!("    {:18} {}", "Class:", class);
+        println!("    {:18} {}:{}", "Vendor:Device:", member.vendor_id, member.device_id);
+        println!("    {:18} {}", "Driver:", member.driver.as_deref().unwrap_or("(none)"));
+        println!("    {:18} {}", "Description:", member.description());
+        if !member.interface.starts_with('(') {
+            println!("    {:18} {}", "Interface:", member.interface);
+        }
+        println!("    {:18} {}", "Status:", device_status_label(&member));
+    }
+
+    Ok(())
+}
+
+/// Show power and thermal readings, warning when a sensor is near its limit
+fn show_thermal_info(info: &ThermalInfo) {
+    if let Some(ref state) = info.power_state {
+        println!("{:20} {}", "Power State:", state);
+    }
+    if let Some(limit) = info.slot_power_limit {
+        println!("{:20} {:.1} W", "Slot Power Limit:", limit);
+    }
+    if let Some(draw) = info.power_draw {
+        match info.power_cap {
+            Some(cap) => println!("{:20} {:.1} W (cap {:.1} W)", "Power Draw:", draw, cap),
+            None => println!("{:20} {:.1} W", "Power Draw:", draw),
+        }
+    }
+    for sensor in &info.temperatures {
+        let reading = match sensor.limit {
+            Some(limit) => format!("{:.1}°C (limit {:.1}°C)", sensor.current, limit),
+            None => format!("{:.1}°C", sensor.current),
+        };
+        println!("{:20} {} [{}]", "Temperature:", reading, sensor.label);
+    }
+    if info.near_thermal_limit() {
+        println!("{:20} {}", "",
+            "⚠ near thermal limit - may throttle under sustained load (check chassis airflow)".bright_yellow());
+    }
+}
+
+/// Show PCI enable/parity flags and AER error counts
+fn show_health_info(info: &HealthInfo) {
+    if let Some(enabled) = info.enabled {
+        println!("{:20} {}", "PCI Enabled:", if enabled { "yes" } else { "no" });
+    }
+    if info.broken_parity == Some(true) {
+        println!("{:20} {}", "Parity:", "broken (device reports parity errors)".bright_yellow());
+    }
+
+    let severities = [
+        ("correctable", &info.aer_correctable),
+        ("non-fatal", &info.aer_nonfatal),
+        ("fatal", &info.aer_fatal),
+    ];
+    let reported: Vec<String> = severities
+        .iter()
+        .filter_map(|(name, counts)| counts.as_ref().map(|c| {
+            if c.nonzero.is_empty() {
+                format!("{} {}", name, c.total)
+            } else {
+                let detail: Vec<String> = c.nonzero.iter().map(|(e, n)| format!("{} {}", e, n)).collect();
+                format!("{} {} ({})", name, c.total, detail.join(", "))
+            }
+        }))
+        .collect();
+    if !reported.is_empty() {
+        println!("{:20} {}", "AER Errors:", reported.join(", "));
+    }
+
+    if info.has_aer_errors() {
+        println!("{:20} {}", "",
+            "⚠ PCIe errors logged since boot - a poor passthrough candidate (check seating, riser, slot)".bright_yellow());
+    }
+}
+
+/// Show interrupt capabilities relevant to VFIO passthrough
+fn show_interrupt_info(info: &InterruptInfo) {
+    if info.capabilities_readable {
+        let mut caps = vec!["INTx".to_string()];
+        if let Some(n) = info.msi_vectors {
+            caps.push(format!("MSI ({} vectors)", n));
+        }
+        if let Some(n) = info.msix_vectors {
+            caps.push(format!("MSI-X ({} vectors)", n));
+        }
+        println!("{:20} {}", "Interrupts:", caps.join(", "));
+    } else {
+        println!("{:20} {}", "Interrupts:", "unknown (reading capabilities requires root)".bright_black());
+    }
+
+    if let Some(ref mode) = info.active_mode {
+        let mode_str = match mode.as_str() {
+            "msix" => "MSI-X",
+            "msi" => "MSI",
+            _ => "INTx",
+        };
+        println!("{:20} {} ({} vector(s) in use)", "  Current mode:", mode_str, info.active_vectors);
+    }
+
+    if info.is_intx_only() {
+        println!("{:20} {}", "",
+            "⚠ Legacy INTx only - may need vfio-pci nointxmask=1 on some hardware".bright_yellow());
+    }
+}
+
+/// Show kernel offload features (what moving the device to VFIO gives up)
+fn show_offload_info(info: &OffloadInfo) {
+    fn flag(value: Option<bool>) -> String {
+        match value {
+            Some(true) => "on".bright_green().to_string(),
+            Some(false) => "off".bright_black().to_string(),
+            None => "?".bright_black().to_string(),
+        }
+    }
+
+    println!(
+        "{:20} rx-csum {}, tx-csum {}, TSO {}, GRO {}",
+        "Offloads:",
+        flag(info.rx_checksum),
+        flag(info.tx_checksum),
+        flag(info.tso),
+        flag(info.gro)
+    );
+
+    if let (Some(rx), Some(tx)) = (info.rx_queues, info.tx_queues) {
+        println!("{:20} {} rx (RSS) / {} tx", "  Queues:", rx, tx);
+    }
+}
+
 /// Show configuration
 pub fn show_config(config: &Config) -> Result<()> {
     println!("{}", "Current Configuration:".bright_cyan());
@@ -190,37 +749,241 @@ pub fn show_config(config: &Config) -> Result<()> {
         }
     }
 
+    if !config.devices.groups.is_empty() {
+        println!();
+        println!("{}", "VFIO IOMMU Groups (all endpoints):".bright_green());
+        for group in &config.devices.groups {
+            println!("  - group {}", group);
+        }
+    }
+
     println!();
     println!("{}", "Options:".bright_cyan());
     println!("  Set permissions: {}", config.options.set_permissions);
     println!("  Auto-load module: {}", config.options.auto_load_module);
+    println!("  Enforce kernel list: {}", config.options.enforce_kernel_list);
+    println!("  Allow absent: {}", config.options.allow_absent);
 
     Ok(())
 }
 
-/// Show issues
+/// Show issues, most severe first
 pub fn show_issues(issues: &[SystemIssue]) -> Result<()> {
     println!("{}", "Found issues:".bright_red().bold());
     println!();
 
-    for (i, issue) in issues.iter().enumerate() {
-        println!("{}{} {}", "  ".to_string(), format!("{}.", i + 1).bright_red(), issue.description());
+    let mut sorted: Vec<&SystemIssue> = issues.iter().collect();
+    sorted.sort_by_key(|issue| issue.severity());
+
+    for (i, issue) in sorted.iter().enumerate() {
+        let severity = issue.severity();
+        let label = format!("[{}]", severity.label());
+        let label = match severity {
+            IssueSeverity::Blocker => label.bright_red().bold(),
+            IssueSeverity::BlockerNeedsReboot => label.bright_magenta().bold(),
+            IssueSeverity::AutoFixable => label.bright_yellow().bold(),
+        };
+
+        println!("  {} {} {}", format!("{}.", i + 1).bright_red(), label, issue.description());
         println!("     {}: {}", "Fix".bright_cyan(), issue.fix_command());
+        if matches!(issue, SystemIssue::DriverOverridePending) {
+            for (pci, driver) in crate::device::pending_overrides() {
+                println!("     {} → {}", pci, driver.bright_white());
+            }
+        }
+        if matches!(issue, SystemIssue::StubIdConflict) {
+            let cfg = crate::config::load_config().ok();
+            for conflict in crate::config::pci_stub_conflicts(cfg.as_ref()) {
+                println!("     {}", conflict.describe());
+            }
+        }
+        if issue.is_auto_fixable() {
+            println!("     {}", "Can be fixed automatically with --fix".bright_green());
+        } else {
+            println!("     {}", "Requires manual action".bright_black());
+        }
         println!();
     }
 
     Ok(())
 }
 
+/// One entry of the `check --format json` report
+fn health_check(
+    name: &str,
+    status: &str,
+    detail: &str,
+    remediation: Option<&str>,
+    auto_fixable: bool,
+    needs_reboot: bool,
+) -> serde_json::Value {
+    json!({
+        "name": name,
+        "status": status,
+        "detail": detail,
+        "remediation": remediation,
+        "auto_fixable": auto_fixable,
+        "needs_reboot": needs_reboot,
+    })
+}
+
+/// Show the full health report as JSON: system checks, then performance and config warnings
+/// Each check has a status of pass, warn or fail
+pub fn show_issues_json(issues: &[SystemIssue]) -> Result<()> {
+    let mut checks: Vec<serde_json::Value> = crate::iommu::SYSTEM_CHECKS
+        .iter()
+        .map(|name| match issues.iter().find(|i| i.check_name() == *name) {
+            Some(issue) => health_check(
+                name,
+                "fail",
+                issue.description(),
+                Some(issue.fix_command()),
+                issue.is_auto_fixable(),
+                issue.needs_reboot(),
+            ),
+            None => health_check(name, "pass", "ok", None, false, false),
+        })
+        .collect();
+
+    if let Ok(status) = crate::iommu::get_system_status() {
+        checks.push(if status.passthrough {
+            health_check("iommu_passthrough", "pass", "iommu=pt is set", None, false, false)
+        } else {
+            health_check("iommu_passthrough", "warn", "iommu=pt is not set (recommended for performance)",
+                Some("sudo vfio-tool setup-grub"), false, true)
+        });
+    }
+
+    match crate::config::load_config() {
+        Ok(cfg) => {
+            let failures: Vec<String> = crate::config::validate_config(&cfg)?
+                .iter()
+                .filter(|i| i.is_failure())
+                .map(ValidationIssue::describe)
+                .collect();
+            checks.push(if failures.is_empty() {
+                health_check("config", "pass", "configuration matches current hardware", None, false, false)
+            } else {
+                health_check("config", "warn", &failures.join("; "), Some("sudo vfio-tool update"), false, false)
+            });
+
+            let early: Vec<String> = crate::config::check_early_binding(&cfg)
+                .iter()
+                .map(|i| i.describe())
+                .collect();
+            checks.push(if early.is_empty() {
+                health_check("early_binding", "pass", "all VFIO devices are claimed by vfio-pci at boot", None, false, false)
+            } else {
+                health_check("early_binding", "warn", &early.join("; "), Some("Add the IDs to 'options vfio-pci ids=' (and softdeps) in /etc/modprobe.d/"), false, true)
+            });
+        }
+        Err(_) => {
+            checks.push(health_check("config", "warn", "no configuration file", Some("sudo vfio-tool configure"), false, false));
+        }
+    }
+
+    let output = json!({
+        "ready": issues.is_empty(),
+        "checks": checks,
+    });
+
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
+
+/// Operation previewed by `explain`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExplainAction {
+    Bind,
+    Unbind,
+}
+
+impl ExplainAction {
+    /// The operation that changes the device's current state
+    pub fn for_device(device: &NetworkDevice) -> Self {
+        if device.is_vfio_bound() { ExplainAction::Unbind } else { ExplainAction::Bind }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            ExplainAction::Bind => "bind",
+            ExplainAction::Unbind => "unbind",
+        }
+    }
+
+    fn steps(&self, device: &NetworkDevice) -> Vec<String> {
+        match self {
+            ExplainAction::Bind => vec![
+                format!("Unbind from {} driver", device.driver.as_deref().unwrap_or("current")),
+                "Bind to vfio-pci driver".to_string(),
+                "Create device node in /dev/vfio/".to_string(),
+                "Interface disappears from 'ip link'".to_string(),
+                "Available for userspace applications".to_string(),
+            ],
+            ExplainAction::Unbind => vec![
+                "Unbind from vfio-pci driver".to_string(),
+                "Reprobe kernel drivers".to_string(),
+                "Interface reappears in 'ip link'".to_string(),
+            ],
+        }
+    }
+}
+
+/// Other members of the device's IOMMU group that the operation also affects,
+/// with their current driver
+fn group_co_binding(device: &NetworkDevice, action: ExplainAction) -> Vec<String> {
+    let Some(group) = device.iommu_group else {
+        return Vec::new();
+    };
+
+    crate::device::get_iommu_group_devices(group)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|pci| *pci != device.pci_address && crate::device::is_endpoint(pci))
+        .filter_map(|pci| {
+            let driver = crate::device::get_driver(&pci);
+            let on_vfio = driver.as_deref() == Some("vfio-pci");
+            let driver = driver.unwrap_or_else(|| "no driver".to_string());
+            match action {
+                ExplainAction::Bind if !on_vfio => Some(format!("{} ({}) must also be bound to vfio-pci", pci, driver)),
+                ExplainAction::Unbind if on_vfio => Some(format!("{} stays on vfio-pci; the group is only usable by VFIO while every member is", pci)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Other PFs on the same physical card with their current driver
+fn sibling_functions(device: &NetworkDevice) -> Vec<(String, Option<String>)> {
+    crate::device::get_sibling_functions(&device.pci_address)
+        .into_iter()
+        .map(|pci| {
+            let driver = crate::device::get_driver(&pci);
+            (pci, driver)
+        })
+        .collect()
+}
+
+/// True if `action` would leave the card with some ports on vfio-pci and some not
+fn is_partial_card_binding(siblings: &[(String, Option<String>)], action: ExplainAction) -> bool {
+    let target_vfio = action == ExplainAction::Bind;
+    siblings.iter().any(|(_, driver)| (driver.as_deref() == Some("vfio-pci")) != target_vfio)
+}
+
 /// Explain what would happen to a device
-pub fn explain_device(device: &NetworkDevice) -> Result<()> {
+/// `what_if` previews a specific operation; by default the one that changes the current state
+pub fn explain_device(device: &NetworkDevice, what_if: Option<ExplainAction>) -> Result<()> {
     println!("{}", "═══════════════════════════════════════".bright_cyan());
     println!("{}  {}", "Explanation for:".bright_cyan().bold(), device.interface.bright_white());
     println!("{}", "═══════════════════════════════════════".bright_cyan());
     println!();
 
     println!("{}", "Current State:".bright_cyan());
-    println!("  Status: {}", status_to_string(&device.status));
+    println!("  Status: {}", device_status_label(device));
+    if let Some(ref driver) = device.override_pending {
+        println!("  {} driver_override is {} but that driver isn't loaded; run: sudo modprobe {}",
+            "⚠".bright_yellow(), driver, driver);
+    }
 
     if let Some(ref driver) = device.driver {
         println!("  Driver: {}", driver);
@@ -232,6 +995,10 @@ pub fn explain_device(device: &NetworkDevice) -> Result<()> {
         println!("  IOMMU Group: {}", group);
     }
 
+    if let Some(node) = crate::device::get_numa_node(&device.pci_address) {
+        println!("  NUMA Node: {}", node);
+    }
+
     println!();
 
     if device.is_vfio_bound() {
@@ -239,51 +1006,178 @@ pub fn explain_device(device: &NetworkDevice) -> Result<()> {
         println!("  • Device is in kernel bypass mode");
         println!("  • NOT visible to kernel networking (ip link, ifconfig)");
         println!("  • Accessible by userspace applications (DPDK, SPDK)");
-        println!("  • Direct hardware access via /dev/vfio/{}", device.iommu_group.unwrap());
-        println!();
-
-        println!("{}", "To return to kernel:".bright_cyan());
-        println!("  vfio-tool unbind {}", device.interface);
+        if let Some(group) = device.iommu_group {
+            println!("  • Direct hardware access via /dev/vfio/{}", group);
+        }
     } else {
         println!("{}", "Using kernel networking:".bright_yellow());
         println!("  • Device controlled by kernel driver");
         println!("  • Visible to standard tools (ip, ping, etc.)");
         println!("  • Normal socket-based networking");
+    }
+    println!();
+
+    let action = what_if.unwrap_or_else(|| ExplainAction::for_device(device));
+    if action != ExplainAction::for_device(device) {
+        let state = if device.is_vfio_bound() { "bound to vfio-pci" } else { "not bound to vfio-pci" };
+        println!("{}", format!("ℹ Device is already {} - '{}' would be a no-op, shown for planning", state, action.name()).bright_blue());
         println!();
+    }
 
-        println!("{}", "To enable VFIO (kernel bypass):".bright_cyan());
-        println!("  vfio-tool bind {}", device.interface);
+    let heading = match action {
+        ExplainAction::Bind => "To enable VFIO (kernel bypass):",
+        ExplainAction::Unbind => "To return to kernel:",
+    };
+    println!("{}", heading.bright_cyan());
+    println!("  vfio-tool {} {}", action.name(), device.interface);
+    println!();
+
+    println!("{}", "This will:".bright_cyan());
+    for (i, step) in action.steps(device).iter().enumerate() {
+        println!("  {}. {}", i + 1, step);
+    }
+
+    let co_binding = group_co_binding(device, action);
+    if !co_binding.is_empty() {
         println!();
+        println!("{}", format!("IOMMU group {}:", device.iommu_group.unwrap_or_default()).bright_cyan());
+        for note in &co_binding {
+            println!("  • {}", note);
+        }
+    }
 
-        println!("{}", "This will:".bright_cyan());
-        println!("  1. Unbind from {} driver", device.driver.as_deref().unwrap_or("current"));
-        println!("  2. Bind to vfio-pci driver");
-        println!("  3. Create device node in /dev/vfio/");
-        println!("  4. Interface disappears from 'ip link'");
-        println!("  5. Available for userspace applications");
+    // SR-IOV notes
+    match device.sriov_role {
+        SriovRole::None => {}
+        SriovRole::PhysicalFunction(num_vfs) => {
+            println!();
+            println!("{}", "SR-IOV Physical Function:".bright_cyan());
+            println!("  • This device has {} virtual function(s)", num_vfs);
+            println!("  • Binding the PF to VFIO affects all of its VFs");
+        }
+        SriovRole::VirtualFunction(ref pf) => {
+            println!();
+            println!("{}", "SR-IOV Virtual Function:".bright_cyan());
+            println!("  • Parent physical function: {}", pf);
+            println!("  • Binding/unbinding this VF does not affect the PF");
+            println!("  • Unbinding returns it to the VF driver (e.g. iavf, mlx5_core), not the PF");
+        }
+    }
+
+    // Multi-port cards: sibling PFs on the same slot (informational, not IOMMU grouping)
+    let siblings = sibling_functions(device);
+    if !siblings.is_empty() {
+        println!();
+        println!("{}", format!("Multi-port card (slot {}):",
+            crate::device::pci_slot(&device.pci_address).unwrap_or_default()).bright_cyan());
+        println!("  • {} (this device)", device.pci_address);
+        for (pci, driver) in &siblings {
+            println!("  • {} ({})", pci, driver.as_deref().unwrap_or("no driver"));
+        }
+        if is_partial_card_binding(&siblings, action) {
+            println!("  {} Only some ports of this card would be on vfio-pci; partial binding may not behave as expected", "⚠".bright_yellow());
+        }
     }
 
     // Check IOMMU group isolation
-    if let Some(group) = device.iommu_group {
-        if let Ok(group_devices) = crate::device::get_iommu_group_devices(group) {
-            if group_devices.len() > 1 {
-                println!();
-                println!("{}", "⚠ WARNING:".bright_yellow().bold());
-                println!("  This device shares IOMMU group {} with:", group);
-                for dev in group_devices {
-                    if dev != device.pci_address {
-                        println!("    - {}", dev);
-                    }
+    if let Some(group) = device.iommu_group
+        && let Ok(group_devices) = crate::device::get_iommu_group_devices(group)
+        && group_devices.len() > 1
+    {
+        println!();
+        println!("{}", "⚠ WARNING:".bright_yellow().bold());
+        println!("  This device shares IOMMU group {} with:", group);
+        for dev in &group_devices {
+            if *dev != device.pci_address {
+                match crate::device::get_numa_node(dev) {
+                    Some(node) => println!("    - {} (NUMA node {})", dev, node),
+                    None => println!("    - {}", dev),
                 }
-                println!("  All devices in the group must be bound to VFIO together.");
             }
         }
+        println!("  All devices in the group must be bound to VFIO together.");
+
+        let mut nodes: Vec<u32> = group_devices.iter().filter_map(|d| crate::device::get_numa_node(d)).collect();
+        nodes.sort();
+        nodes.dedup();
+        if nodes.len() > 1 {
+            let nodes: Vec<String> = nodes.iter().map(u32::to_string).collect();
+            println!("  {} The group spans NUMA nodes {}; pin the guest/application with that in mind.",
+                "⚠".bright_yellow(), nodes.join(", "));
+        }
     }
 
     Ok(())
 }
 
-fn status_to_string(status: &DeviceStatus) -> String {
+/// Explain what would happen to a device in JSON format
+pub fn explain_device_json(device: &NetworkDevice, what_if: Option<ExplainAction>) -> Result<()> {
+    let transition = what_if.unwrap_or_else(|| ExplainAction::for_device(device));
+    let action = transition.name();
+    let steps = transition.steps(device);
+
+    let group_members: Vec<String> = device.iommu_group
+        .and_then(|g| crate::device::get_iommu_group_devices(g).ok())
+        .unwrap_or_default();
+
+    let others: Vec<&String> = group_members
+        .iter()
+        .filter(|d| **d != device.pci_address)
+        .collect();
+
+    let mut warnings = Vec::new();
+    if !others.is_empty() {
+        warnings.push(format!(
+            "Device shares IOMMU group {} with {} other device(s). All devices in the group must be bound to VFIO together.",
+            device.iommu_group.unwrap_or_default(),
+            others.len()
+        ));
+    }
+    if device.iommu_group.is_none() {
+        warnings.push("Device has no IOMMU group. Is IOMMU enabled?".to_string());
+    }
+    if device.is_vfio_missing_node() {
+        warnings.push("Bound to vfio-pci but no device node exists. Check IOMMU/driver.".to_string());
+    }
+
+    let siblings = sibling_functions(device);
+    if is_partial_card_binding(&siblings, transition) {
+        warnings.push("Only some ports of this multi-port card would be on vfio-pci; partial binding may not behave as expected.".to_string());
+    }
+
+    let output = json!({
+        "interface": device.interface,
+        "current_state": {
+            "status": status_to_string(&device.status),
+            "override_pending": device.override_pending,
+            "driver": device.driver,
+            "pci_address": device.pci_address,
+            "vendor": device.vendor_device(),
+            "subsystem": device.subsystem(),
+            "iommu_group": device.iommu_group,
+        },
+        "transition": {
+            "action": action,
+            "command": format!("vfio-tool {} {}", action, device.interface),
+            "steps": steps,
+            "no_op": transition != ExplainAction::for_device(device),
+            "co_binding": group_co_binding(device, transition),
+        },
+        "sriov_role": match device.sriov_role {
+            SriovRole::None => None,
+            _ => Some(device.sriov_role.short_name()),
+        },
+        "group_members": group_members,
+        "isolated": others.is_empty(),
+        "sibling_functions": siblings.iter().map(|(pci, _)| pci).collect::<Vec<_>>(),
+        "warnings": warnings,
+    });
+
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
+
+pub fn status_to_string(status: &DeviceStatus) -> String {
     match status {
         DeviceStatus::Vfio => "vfio".to_string(),
         DeviceStatus::Kernel => "kernel".to_string(),
@@ -291,24 +1185,137 @@ fn status_to_string(status: &DeviceStatus) -> String {
     }
 }
 
+/// Status for display, naming the driver an unbound device's override is waiting for
+pub fn device_status_label(device: &NetworkDevice) -> String {
+    match device.override_pending {
+        Some(ref driver) => format!("unbound (override pending: {})", driver),
+        None => status_to_string(&device.status),
+    }
+}
+
 /// Show framework-specific device list
 pub fn show_framework_devices(
-    framework: Framework,
+    framework: &Framework,
     devices: &[FrameworkDevice],
+    incapable: &[FrameworkDevice],
     show_capable: bool,
     format: &str,
 ) -> Result<()> {
     match format {
-        "json" => show_framework_json(framework, devices, show_capable),
+        "json" => show_framework_json(framework, devices, incapable, show_capable),
         "args" => show_framework_args(framework, devices),
-        _ => show_framework_default(framework, devices, show_capable),
+        "tree" => show_framework_tree(framework, devices),
+        _ => show_framework_default(framework, devices, incapable, show_capable),
     }
 }
 
+/// Show which of a framework's userspace dependencies are installed
+pub fn show_framework_dependencies(framework: &Framework, deps: &[Dependency]) {
+    println!("{}", format!("{} userspace dependencies:", framework.name()).bright_cyan().bold());
+
+    if deps.is_empty() {
+        println!("  {}", "(no known dependencies to check)".bright_black());
+        return;
+    }
+
+    for dep in deps {
+        match dep.found {
+            Some(ref location) => println!("  {} {:22} {}", "✓".bright_green(), dep.name, location.bright_black()),
+            None => println!("  {} {:22} {}", "✗".bright_red(), dep.name,
+                format!("missing (looked for {})", dep.expected).bright_red()),
+        }
+    }
+
+    let missing = deps.iter().filter(|d| d.found.is_none()).count();
+    println!();
+    if missing == 0 {
+        println!("{}", format!("✓ All {} userspace dependencies found", framework.name()).bright_green());
+    } else {
+        println!("{}", format!("⚠ {} of {} dependencies missing - ready devices alone are not enough to run {}",
+            missing, deps.len(), framework.name()).bright_yellow());
+    }
+}
+
+/// Show capable devices as a tree: IOMMU group, then readiness
+/// Groups are classified as ready, partial or not ready so incomplete groups stand out
+fn show_framework_tree(framework: &Framework, devices: &[FrameworkDevice]) -> Result<()> {
+    if devices.is_empty() {
+        println!("{}", format!("No {}-capable devices found.", framework.name()).bright_yellow());
+        return Ok(());
+    }
+
+    let mut groups: std::collections::BTreeMap<Option<u32>, Vec<&FrameworkDevice>> = std::collections::BTreeMap::new();
+    for dev in devices {
+        groups.entry(dev.device.iommu_group).or_default().push(dev);
+    }
+
+    // Devices without a group go last
+    let mut ordered: Vec<(Option<u32>, Vec<&FrameworkDevice>)> = groups.into_iter().collect();
+    ordered.sort_by_key(|(group, _)| (group.is_none(), *group));
+
+    let needs_label = if framework.requires_vfio() { "Needs binding" } else { "Needs unbinding" };
+
+    println!("{}", format!("{} devices by IOMMU group:", framework.name()).bright_cyan().bold());
+
+    for (i, (group, members)) in ordered.iter().enumerate() {
+        let last_group = i + 1 == ordered.len();
+        let (branch, indent) = if last_group { ("└──", "    ") } else { ("├──", "│   ") };
+
+        let ready = members.iter().filter(|d| d.is_ready).count();
+        let state = match ready {
+            r if r == members.len() => "ready".bright_green(),
+            0 => "not ready".bright_red(),
+            r => format!("partial: {}/{} ready", r, members.len()).bright_yellow(),
+        };
+        let name = match group {
+            Some(id) => format!("Group {}", id),
+            None => "No IOMMU group".to_string(),
+        };
+        println!("{} {} ({})", branch, name.bright_white(), state);
+
+        if let Some(id) = group
+            && framework.requires_vfio() && matches!(crate::iommu::group_viable(*id), Ok(false))
+        {
+            println!("{}{} group is not VFIO viable - other members are on host drivers", indent, "⚠".bright_yellow());
+        }
+
+        let sections: Vec<(&str, Vec<&&FrameworkDevice>)> = [
+            ("Ready", members.iter().filter(|d| d.is_ready).collect::<Vec<_>>()),
+            (needs_label, members.iter().filter(|d| !d.is_ready).collect::<Vec<_>>()),
+        ]
+        .into_iter()
+        .filter(|(_, devs)| !devs.is_empty())
+        .collect();
+
+        for (j, (label, devs)) in sections.iter().enumerate() {
+            let last_section = j + 1 == sections.len();
+            let (branch, sub_indent) = if last_section { ("└──", "    ") } else { ("├──", "│   ") };
+            let label = if *label == "Ready" { label.bright_green() } else { label.bright_yellow() };
+            println!("{}{} {}", indent, branch, label);
+
+            for (k, dev) in devs.iter().enumerate() {
+                let branch = if k + 1 == devs.len() { "└──" } else { "├──" };
+                println!("{}{}{} {} → {} ({})",
+                    indent, sub_indent, branch,
+                    dev.device.interface,
+                    dev.reference_string,
+                    dev.device.driver.as_deref().unwrap_or("(none)"));
+                if let Some(reason) = &dev.reason {
+                    let cont = if k + 1 == devs.len() { "    " } else { "│   " };
+                    println!("{}{}{}{}", indent, sub_indent, cont, reason.dimmed());
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Show framework devices in default (human-readable) format
 fn show_framework_default(
-    framework: Framework,
+    framework: &Framework,
     devices: &[FrameworkDevice],
+    incapable: &[FrameworkDevice],
     show_capable: bool,
 ) -> Result<()> {
     if show_capable {
@@ -320,7 +1327,9 @@ fn show_framework_default(
         let needs_action: Vec<_> = devices.iter().filter(|d| !d.is_ready).collect();
 
         if !ready.is_empty() {
-            let ready_label = if framework.requires_vfio() {
+            let ready_label = if framework.accepts_uio() {
+                "Ready (VFIO/UIO mode):"
+            } else if framework.requires_vfio() {
                 "Ready (VFIO mode):"
             } else {
                 "Ready (kernel mode):"
@@ -328,6 +1337,7 @@ fn show_framework_default(
             println!("{}", ready_label.bright_green());
             for dev in &ready {
                 print_device_line(&dev.device, &dev.reference_string);
+                print_reason_line(dev);
             }
             println!();
         }
@@ -341,6 +1351,16 @@ fn show_framework_default(
             println!("{}", needs_label.bright_yellow());
             for dev in &needs_action {
                 print_device_line(&dev.device, &dev.reference_string);
+                print_reason_line(dev);
+            }
+            println!();
+        }
+
+        if !incapable.is_empty() {
+            println!("{}", "Not Capable:".bright_red());
+            for dev in incapable {
+                print_device_line(&dev.device, &dev.reference_string);
+                print_reason_line(dev);
             }
             println!();
         }
@@ -369,6 +1389,7 @@ fn show_framework_default(
         println!("{}", format!("{}-Ready Devices:", framework.name()).bright_cyan().bold());
         for dev in devices {
             print_device_line(&dev.device, &dev.reference_string);
+            print_reason_line(dev);
         }
         println!();
         println!("{}", format!("{} device(s) ready for {}", devices.len(), framework.name()).bright_green());
@@ -380,7 +1401,7 @@ fn show_framework_default(
 /// Print a single device line in human-readable format
 fn print_device_line(device: &NetworkDevice, reference: &str) {
     let driver = device.driver.as_deref().unwrap_or("(none)");
-    let desc = get_device_description(device);
+    let desc = device.description();
 
     println!(
         "  {:15} → {:15}  ({:12}) {}",
@@ -389,46 +1410,22 @@ fn print_device_line(device: &NetworkDevice, reference: &str) {
         driver,
         desc
     );
-}
-
-/// Get a human-readable device description
-fn get_device_description(device: &NetworkDevice) -> String {
-    // Try to identify vendor/model from vendor:device ID
-    match (device.vendor_id.as_str(), device.device_id.as_str()) {
-        // Mellanox
-        ("0x15b3", "0x101f") => "Mellanox ConnectX-4 Lx".to_string(),
-        ("0x15b3", "0x1013") => "Mellanox ConnectX-4".to_string(),
-        ("0x15b3", "0x1015") => "Mellanox ConnectX-4".to_string(),
-        ("0x15b3", "0x1017") => "Mellanox ConnectX-5".to_string(),
 
-        // Intel XXV710 - 25GbE
-        ("0x8086", "0x158a") => "Intel XXV710 25GbE".to_string(),
-        ("0x8086", "0x158b") => "Intel XXV710 25GbE".to_string(),
-
-        // Intel X710 - 10GbE
-        ("0x8086", "0x1572") => "Intel X710 10GbE".to_string(),
-        ("0x8086", "0x15ff") => "Intel X710 10GbE".to_string(),
-
-        // Solarflare
-        ("0x1924", _) => "Solarflare NIC".to_string(),
+    if device.is_vfio_missing_node() {
+        println!("  {:15}   {}", "", "⚠ bound but no device node — check IOMMU/driver".bright_yellow());
+    }
+}
 
-        _ => {
-            if let Some(ref speed) = device.max_speed {
-                format!("{} NIC", speed)
-            } else {
-                "Network Card".to_string()
-            }
-        }
+/// Print why a device is not ready (or not capable), or its XDP mode, under its device line
+fn print_reason_line(dev: &FrameworkDevice) {
+    if let Some(reason) = &dev.reason {
+        println!("  {:15}   {}", "", reason.dimmed());
     }
 }
 
-/// Show framework devices in JSON format
-fn show_framework_json(
-    framework: Framework,
-    devices: &[FrameworkDevice],
-    show_capable: bool,
-) -> Result<()> {
-    let device_list: Vec<_> = devices
+/// Convert framework devices to JSON values
+fn framework_devices_json(devices: &[FrameworkDevice]) -> Vec<serde_json::Value> {
+    devices
         .iter()
         .map(|d| {
             json!({
@@ -437,16 +1434,29 @@ fn show_framework_json(
                 "pci_address": d.device.pci_address,
                 "driver": d.device.driver,
                 "vendor": d.device.vendor_device(),
+                "subsystem": d.device.subsystem(),
                 "ready": d.is_ready,
+                "reason": d.reason,
                 "max_speed": d.device.max_speed,
             })
         })
-        .collect();
+        .collect()
+}
+
+/// Show framework devices in JSON format
+fn show_framework_json(
+    framework: &Framework,
+    devices: &[FrameworkDevice],
+    incapable: &[FrameworkDevice],
+    show_capable: bool,
+) -> Result<()> {
+    let device_list = framework_devices_json(devices);
 
     let output = if show_capable {
         json!({
             "framework": framework.name(),
             "capable": device_list,
+            "incapable": framework_devices_json(incapable),
         })
     } else {
         json!({
@@ -460,8 +1470,47 @@ fn show_framework_json(
 }
 
 /// Show framework devices in args format (comma-separated)
-fn show_framework_args(_framework: Framework, devices: &[FrameworkDevice]) -> Result<()> {
+fn show_framework_args(_framework: &Framework, devices: &[FrameworkDevice]) -> Result<()> {
     let refs: Vec<_> = devices.iter().map(|d| d.reference_string.as_str()).collect();
     println!("{}", refs.join(","));
     Ok(())
 }
+
+/// Show differences between a saved snapshot and the current device inventory
+pub fn show_inventory_changes(changes: &[InventoryChange]) -> Result<()> {
+    if changes.is_empty() {
+        println!("{}", "✓ No hardware changes since snapshot".bright_green());
+        return Ok(());
+    }
+
+    println!("{}", "Hardware changes since snapshot:".bright_cyan().bold());
+    println!();
+
+    for change in changes {
+        match change {
+            InventoryChange::Added(dev) => {
+                println!("  {} {} {}", "+".bright_green(), dev.pci_address.bright_white(), describe_fingerprint(dev));
+            }
+            InventoryChange::Removed(dev) => {
+                println!("  {} {} {}", "-".bright_red(), dev.pci_address.bright_white(), describe_fingerprint(dev));
+            }
+            InventoryChange::Changed { old, new } => {
+                println!("  {} {} {} → {}", "~".bright_yellow(), new.pci_address.bright_white(),
+                    describe_fingerprint(old), describe_fingerprint(new));
+            }
+        }
+    }
+
+    println!();
+    println!("{}", format!("{} change(s) detected", changes.len()).bright_yellow());
+
+    Ok(())
+}
+
+/// Describe a snapshot entry (interface, vendor:device and model)
+fn describe_fingerprint(dev: &DeviceFingerprint) -> String {
+    format!("{} ({} - {})",
+        dev.interface,
+        dev.vendor_device(),
+        crate::device::get_device_description(&dev.vendor_id, &dev.device_id))
+}
@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+use anyhow::{Result, Context};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::watch::{self, DeviceState};
+
+const APPLIED_DIR: &str = "/run/vfio-tool";
+const APPLIED_FILE: &str = "/run/vfio-tool/applied.json";
+
+/// What the last successful `apply` left behind (kept in /run, so reset at boot)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppliedState {
+    pub config_hash: String,
+    pub applied_at: u64,
+    /// Device states right after the apply, keyed by PCI address
+    pub devices: HashMap<String, DeviceState>,
+}
+
+/// Stable hash of the config's serialized form (FNV-1a, 64-bit)
+///
+/// The config holds HashMaps, whose iteration order changes from run to run, so it
+/// goes through a `toml::Value` first: its tables are sorted by key.
+fn config_hash(config: &Config) -> Result<String> {
+    let canonical = toml::Value::try_from(config)
+        .context("Failed to serialize config")?;
+    let content = toml::to_string(&canonical)
+        .context("Failed to serialize config")?;
+
+    let hash = content.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    Ok(format!("{:016x}", hash))
+}
+
+/// Record the config and resulting device states after a successful apply
+pub fn record_applied(config: &Config) -> Result<()> {
+    let state = AppliedState {
+        config_hash: config_hash(config)?,
+        applied_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default(),
+        devices: watch::poll_states()?,
+    };
+
+    fs::create_dir_all(APPLIED_DIR)
+        .context(format!("Failed to create {}", APPLIED_DIR))?;
+    fs::write(APPLIED_FILE, serde_json::to_string_pretty(&state)?)
+        .context(format!("Failed to write {}", APPLIED_FILE))?;
+
+    Ok(())
+}
+
+/// Load the last applied state (None if nothing was applied since boot)
+pub fn load_applied() -> Result<Option<AppliedState>> {
+    let content = match fs::read_to_string(APPLIED_FILE) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).context(format!("Failed to read {}", APPLIED_FILE)),
+    };
+
+    serde_json::from_str(&content)
+        .map(Some)
+        .context(format!("Failed to parse {}", APPLIED_FILE))
+}
+
+/// Compare the live device states against the last apply and print every
+/// out-of-band change. Returns true if anything drifted.
+pub fn check_drift(applied: &AppliedState, config: Option<&Config>) -> Result<bool> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    println!("Last apply: {} ago (config {})",
+        crate::display::format_duration(now.saturating_sub(applied.applied_at)),
+        &applied.config_hash[..8.min(applied.config_hash.len())]);
+
+    if let Some(cfg) = config && config_hash(cfg)? != applied.config_hash {
+        println!("{} Config changed since the last apply (see 'vfio-tool diff')", "ℹ".bright_blue());
+    }
+    println!();
+
+    let transitions = watch::diff_states(&applied.devices, &watch::poll_states()?);
+    if transitions.is_empty() {
+        println!("{}", format!("✓ No drift: all {} device(s) are as apply left them", applied.devices.len()).bright_green());
+        return Ok(false);
+    }
+
+    for transition in &transitions {
+        println!("  {} {} ({}): {} → {}",
+            "~".bright_yellow(),
+            transition.interface.bright_white(),
+            transition.pci_address,
+            transition.old_state,
+            transition.new_state.bright_red());
+    }
+    println!();
+    println!("{}", format!("⚠ {} device(s) changed since the last apply", transitions.len()).bright_yellow());
+    println!("Run {} to restore the applied state.", "sudo vfio-tool apply".bright_cyan());
+
+    Ok(true)
+}
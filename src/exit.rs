@@ -0,0 +1,135 @@
+use anyhow::Result;
+use colored::Colorize;
+use serde_json::json;
+
+/// Why a command exited; each reason has exactly one exit code
+/// Commands exit through these so the codes and `vfio-tool exit-codes` can't drift apart.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Exit {
+    Success,
+    Error,
+    NotRoot,
+    IssuesFound,
+    InventoryChanged,
+    OutOfSync,
+    Drifted,
+    ResetIncomplete,
+    ContainerUnusable,
+    VerifyFailed,
+    NotEnoughReady,
+    InterfaceNotFound,
+    WrongMode,
+    BindFailed,
+    ValidationFailed,
+    ConfigMissing,
+    TestFailed,
+    UsageError,
+    OtherError,
+    TimedOut,
+}
+
+impl Exit {
+    pub fn code(self) -> i32 {
+        match self {
+            Exit::Success => 0,
+            Exit::Error
+            | Exit::IssuesFound
+            | Exit::InventoryChanged
+            | Exit::OutOfSync
+            | Exit::Drifted
+            | Exit::ResetIncomplete
+            | Exit::ContainerUnusable
+            | Exit::VerifyFailed
+            | Exit::NotEnoughReady
+            | Exit::InterfaceNotFound => 1,
+            Exit::WrongMode
+            | Exit::BindFailed
+            | Exit::ValidationFailed
+            | Exit::ConfigMissing => 2,
+            Exit::TestFailed
+            | Exit::UsageError
+            | Exit::OtherError => 3,
+            Exit::NotRoot => 4,
+            Exit::TimedOut => 5,
+        }
+    }
+
+    pub fn meaning(self) -> &'static str {
+        match self {
+            Exit::Success => "success",
+            Exit::Error => "error (message on stderr)",
+            Exit::NotRoot => "requires root privileges",
+            Exit::IssuesFound => "system has issues",
+            Exit::InventoryChanged => "device inventory differs from the snapshot",
+            Exit::OutOfSync => "devices not in their configured mode, or missing",
+            Exit::Drifted => "devices changed since the last apply",
+            Exit::ResetIncomplete => "some devices did not return to a kernel driver",
+            Exit::ContainerUnusable => "/dev/vfio/vfio cannot be opened or queried",
+            Exit::VerifyFailed => "device is not usable via VFIO",
+            Exit::NotEnoughReady => "fewer ready devices than required",
+            Exit::InterfaceNotFound => "interface not found",
+            Exit::WrongMode => "interface in the wrong mode",
+            Exit::BindFailed => "failed to bind",
+            Exit::ValidationFailed => "config missing, unreadable or does not match the hardware",
+            Exit::ConfigMissing => "no valid configuration",
+            Exit::TestFailed => "applying the configuration failed; service not installed",
+            Exit::UsageError => "invalid or missing interface list",
+            Exit::OtherError => "other error",
+            Exit::TimedOut => "did not finish within --timeout",
+        }
+    }
+
+    /// Exit the process with this reason's code
+    pub fn exit(self) -> ! {
+        std::process::exit(self.code())
+    }
+}
+
+/// Exit codes of every command, in the order `exit-codes` lists them
+/// "*" applies to all commands; the root check applies to every command that changes state.
+pub const EXIT_CODES: &[(&str, &[Exit])] = &[
+    ("*", &[Exit::Success, Exit::Error, Exit::NotRoot]),
+    ("* --timeout", &[Exit::TimedOut]),
+    ("check", &[Exit::Success, Exit::IssuesFound]),
+    ("list --changed-since", &[Exit::Success, Exit::InventoryChanged]),
+    ("diff", &[Exit::Success, Exit::OutOfSync]),
+    ("drift", &[Exit::Success, Exit::Drifted]),
+    ("reset", &[Exit::Success, Exit::ResetIncomplete]),
+    ("check-container", &[Exit::Success, Exit::ContainerUnusable]),
+    ("verify", &[Exit::Success, Exit::VerifyFailed]),
+    ("show --check", &[Exit::Success, Exit::NotEnoughReady]),
+    ("check-interfaces", &[Exit::Success, Exit::InterfaceNotFound, Exit::WrongMode, Exit::UsageError, Exit::OtherError]),
+    ("ensure-vfio", &[Exit::Success, Exit::InterfaceNotFound, Exit::BindFailed, Exit::UsageError, Exit::OtherError]),
+    ("validate", &[Exit::Success, Exit::ValidationFailed]),
+    ("config validate-file", &[Exit::Success, Exit::ValidationFailed]),
+    ("install --yes", &[Exit::Success, Exit::ConfigMissing, Exit::TestFailed]),
+];
+
+/// Print the exit code table
+pub fn show_exit_codes() {
+    println!("{}", "Exit codes:".bright_cyan());
+    for (command, reasons) in EXIT_CODES {
+        println!();
+        println!("  {}", command.bright_white());
+        for reason in *reasons {
+            println!("    {}  {}", reason.code().to_string().bright_cyan(), reason.meaning());
+        }
+    }
+}
+
+/// Print the exit code table as JSON
+pub fn show_exit_codes_json() -> Result<()> {
+    let output: Vec<serde_json::Value> = EXIT_CODES
+        .iter()
+        .map(|(command, reasons)| json!({
+            "command": command,
+            "codes": reasons.iter().map(|r| json!({
+                "code": r.code(),
+                "meaning": r.meaning(),
+            })).collect::<Vec<_>>(),
+        }))
+        .collect();
+
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
@@ -2,9 +2,13 @@ use anyhow::Result;
 use std::fs;
 use std::path::Path;
 
+use crate::config::{FrameworkDefinition, ReferenceType};
 use crate::device::{NetworkDevice, DeviceStatus};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Userspace-IO drivers DPDK can use instead of vfio-pci (e.g. in VMs without an IOMMU)
+pub const UIO_DRIVERS: &[&str] = &["uio_pci_generic", "igb_uio"];
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum Framework {
     Dpdk,
     Rdma,
@@ -14,10 +18,29 @@ pub enum Framework {
     Spdk,
     Vpp,
     Xdp,
+    /// User-defined framework from a `[frameworks.<name>]` config section
+    Custom { name: String, definition: FrameworkDefinition },
+}
+
+impl std::str::FromStr for Framework {
+    type Err = anyhow::Error;
+
+    /// Parse a framework name, falling back to user-defined frameworks in the config
+    /// An unknown name is an error listing the valid ones
+    fn from_str(s: &str) -> Result<Self> {
+        Self::builtin(s).or_else(|| Self::custom(s)).ok_or_else(|| {
+            let mut supported = "dpdk, rdma, tcpdirect, openonload, efvi, spdk, vpp, xdp".to_string();
+            for name in Self::custom_names() {
+                supported.push_str(", ");
+                supported.push_str(&name);
+            }
+            anyhow::anyhow!("Unknown framework: {}\nSupported: {}\nCustom frameworks can be defined in a [frameworks.<name>] config section", s, supported)
+        })
+    }
 }
 
 impl Framework {
-    pub fn from_str(s: &str) -> Option<Self> {
+    fn builtin(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
             "dpdk" => Some(Framework::Dpdk),
             "rdma" => Some(Framework::Rdma),
@@ -31,6 +54,24 @@ impl Framework {
         }
     }
 
+    fn custom(s: &str) -> Option<Self> {
+        let cfg = crate::config::load_config().ok()?;
+        let (name, definition) = cfg.frameworks
+            .into_iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(s))?;
+
+        Some(Framework::Custom { name, definition })
+    }
+
+    /// Names of user-defined frameworks in the config (sorted)
+    pub fn custom_names() -> Vec<String> {
+        let mut names: Vec<String> = crate::config::load_config()
+            .map(|cfg| cfg.frameworks.into_keys().collect())
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+
     pub fn name(&self) -> &str {
         match self {
             Framework::Dpdk => "DPDK",
@@ -41,15 +82,27 @@ impl Framework {
             Framework::Spdk => "SPDK",
             Framework::Vpp => "VPP",
             Framework::Xdp => "XDP",
+            Framework::Custom { name, .. } => name,
         }
     }
 
     pub fn requires_vfio(&self) -> bool {
-        matches!(self, Framework::Dpdk | Framework::TcpDirect | Framework::Spdk | Framework::Vpp)
+        match self {
+            Framework::Custom { definition, .. } => definition.requires_vfio,
+            _ => matches!(self, Framework::Dpdk | Framework::TcpDirect | Framework::Spdk | Framework::Vpp),
+        }
+    }
+
+    /// Whether devices bound to a UIO driver also count as ready
+    pub fn accepts_uio(&self) -> bool {
+        matches!(self, Framework::Dpdk)
     }
 
     pub fn requires_kernel(&self) -> bool {
-        matches!(self, Framework::Rdma | Framework::OpenOnload | Framework::EfVi | Framework::Xdp)
+        match self {
+            Framework::Custom { definition, .. } => !definition.requires_vfio,
+            _ => matches!(self, Framework::Rdma | Framework::OpenOnload | Framework::EfVi | Framework::Xdp),
+        }
     }
 }
 
@@ -58,40 +111,114 @@ pub struct FrameworkDevice {
     pub device: NetworkDevice,
     pub is_ready: bool,
     pub reference_string: String, // What the app would use (PCI address, RDMA name, interface name)
+    pub reason: Option<String>,   // Why the device is not capable/ready; for ready XDP devices, the XDP mode
+}
+
+/// How XDP programs attach to a device
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum XdpMode {
+    Native,   // In the driver, before any skb is allocated
+    Generic,  // SKB mode: works on any netdev, but far slower
+}
+
+impl XdpMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            XdpMode::Native => "XDP native",
+            XdpMode::Generic => "XDP generic (slow)",
+        }
+    }
 }
 
 /// Check if device is capable of supporting the framework
-pub fn is_device_capable(device: &NetworkDevice, framework: Framework) -> bool {
+pub fn is_device_capable(device: &NetworkDevice, framework: &Framework) -> bool {
+    incapable_reason(device, framework).is_none()
+}
+
+/// Explain why a device is not capable of supporting the framework (None if capable)
+pub fn incapable_reason(device: &NetworkDevice, framework: &Framework) -> Option<String> {
     match framework {
         // DPDK: All NICs are capable
-        Framework::Dpdk => true,
+        Framework::Dpdk => None,
 
         // RDMA: Only Mellanox and Broadcom with RoCE
-        Framework::Rdma => is_rdma_capable(device),
+        Framework::Rdma => {
+            if is_rdma_capable(device) {
+                None
+            } else if device.vendor_id == "0x14e4" {
+                Some(format!("Broadcom device {} not known to support RoCE", device.device_id))
+            } else {
+                Some(format!("vendor {} does not support RDMA (Mellanox or Broadcom RoCE only)", device.vendor_id))
+            }
+        }
 
         // Solarflare frameworks: Only Solarflare NICs
-        Framework::TcpDirect | Framework::OpenOnload | Framework::EfVi => is_solarflare(device),
+        Framework::TcpDirect | Framework::OpenOnload | Framework::EfVi => {
+            if is_solarflare(device) {
+                None
+            } else {
+                Some("not a Solarflare NIC".to_string())
+            }
+        }
 
         // SPDK: All NICs are capable
-        Framework::Spdk => true,
+        Framework::Spdk => None,
 
         // VPP: All NICs are capable (uses DPDK underneath)
-        Framework::Vpp => true,
+        Framework::Vpp => None,
+
+        // XDP: Any kernel network driver (generic XDP is the fallback)
+        Framework::Xdp => {
+            if xdp_mode(device).is_some() {
+                None
+            } else if let Some(ref driver) = device.driver {
+                Some(format!("driver {} has no netdev for XDP (needs a kernel network driver)", driver))
+            } else {
+                Some("no kernel driver bound".to_string())
+            }
+        }
+
+        // Custom: vendor / vendor:device filter from the config
+        Framework::Custom { name, definition } => {
+            if matches_definition(device, definition) {
+                None
+            } else {
+                Some(format!("{} not in the {} vendor/device list", device.vendor_device(), name))
+            }
+        }
+    }
+}
 
-        // XDP: Check if driver supports XDP
-        Framework::Xdp => is_xdp_capable(device),
+/// Explain why a capable device is not ready to use with the framework (None if ready)
+fn not_ready_reason(device: &NetworkDevice, framework: &Framework) -> Option<String> {
+    if is_device_ready(device, framework) {
+        return None;
+    }
+
+    if framework.requires_vfio() {
+        if device.is_vfio_missing_node() {
+            Some("bound to vfio-pci but no device node (check IOMMU/driver)".to_string())
+        } else if framework.accepts_uio() {
+            Some(format!("driver {} - needs binding to vfio-pci (or {})",
+                device.driver.as_deref().unwrap_or("(none)"), UIO_DRIVERS.join("/")))
+        } else {
+            Some(format!("driver {} - needs binding to vfio-pci", device.driver.as_deref().unwrap_or("(none)")))
+        }
+    } else {
+        Some(format!("driver {} - needs a kernel driver", device.driver.as_deref().unwrap_or("(none)")))
     }
 }
 
 /// Check if device is ready to use with the framework RIGHT NOW
-pub fn is_device_ready(device: &NetworkDevice, framework: Framework) -> bool {
+pub fn is_device_ready(device: &NetworkDevice, framework: &Framework) -> bool {
     if !is_device_capable(device, framework) {
         return false;
     }
 
     if framework.requires_vfio() {
-        // Must be in VFIO mode
-        device.status == DeviceStatus::Vfio
+        // Must be in VFIO mode with a usable group device node, or on a UIO driver if accepted
+        (device.status == DeviceStatus::Vfio && device.has_vfio_node())
+            || (framework.accepts_uio() && is_uio_bound(device))
     } else if framework.requires_kernel() {
         // Must be in kernel mode
         device.status == DeviceStatus::Kernel
@@ -101,7 +228,7 @@ pub fn is_device_ready(device: &NetworkDevice, framework: Framework) -> bool {
 }
 
 /// Get the reference string that applications would use
-pub fn get_reference_string(device: &NetworkDevice, framework: Framework) -> Result<String> {
+pub fn get_reference_string(device: &NetworkDevice, framework: &Framework) -> Result<String> {
     match framework {
         // DPDK, SPDK, VPP, TCPDirect: Use PCI addresses
         Framework::Dpdk | Framework::Spdk | Framework::Vpp | Framework::TcpDirect => {
@@ -117,9 +244,44 @@ pub fn get_reference_string(device: &NetworkDevice, framework: Framework) -> Res
         Framework::OpenOnload | Framework::EfVi | Framework::Xdp => {
             Ok(device.interface.clone())
         }
+
+        Framework::Custom { definition, .. } => match definition.reference_type {
+            ReferenceType::Pci => Ok(device.pci_address.clone()),
+            ReferenceType::Interface => Ok(device.interface.clone()),
+            ReferenceType::Rdma => get_rdma_device_name(&device.pci_address),
+        },
     }
 }
 
+/// Check if a device is bound to one of the UIO drivers
+pub fn is_uio_bound(device: &NetworkDevice) -> bool {
+    device.driver
+        .as_deref()
+        .is_some_and(|d| UIO_DRIVERS.contains(&d))
+}
+
+/// Check a device against a custom framework's vendor and vendor:device filters
+/// Empty filters match every device; IDs are compared case-insensitively with or without "0x"
+fn matches_definition(device: &NetworkDevice, definition: &FrameworkDefinition) -> bool {
+    fn normalize(id: &str) -> String {
+        id.trim().to_lowercase().trim_start_matches("0x").to_string()
+    }
+
+    let vendor = normalize(&device.vendor_id);
+    let dev_id = normalize(&device.device_id);
+
+    let vendor_ok = definition.vendors.is_empty()
+        || definition.vendors.iter().any(|v| normalize(v) == vendor);
+
+    let device_ok = definition.devices.is_empty()
+        || definition.devices.iter().any(|pair| {
+            pair.split_once(':')
+                .is_some_and(|(v, d)| normalize(v) == vendor && normalize(d) == dev_id)
+        });
+
+    vendor_ok && device_ok
+}
+
 /// Check if device is RDMA-capable (Mellanox or Broadcom with RoCE)
 fn is_rdma_capable(device: &NetworkDevice) -> bool {
     match device.vendor_id.as_str() {
@@ -143,22 +305,24 @@ fn is_solarflare(device: &NetworkDevice) -> bool {
     device.vendor_id == "0x1924"
 }
 
-/// Check if device supports XDP
-fn is_xdp_capable(device: &NetworkDevice) -> bool {
-    // XDP support depends on the driver
-    // Most modern drivers support XDP, but not all
-    if let Some(ref driver) = device.driver {
-        matches!(driver.as_str(),
-            "i40e" | "ice" | "ixgbe" | "ixgbevf" |
-            "mlx5_core" | "mlx4_core" |
-            "virtio_net" | "veth" |
-            "tun" | "tap" |
-            "nfp" | "qede" | "bnxt_en" |
-            "thunderx" | "ena"
-        )
-    } else {
-        false
+/// XDP mode a device supports (None without a kernel driver that exposes a netdev)
+/// Native XDP depends on the driver; generic XDP works on any netdev.
+pub fn xdp_mode(device: &NetworkDevice) -> Option<XdpMode> {
+    let driver = device.driver.as_deref()?;
+    let has_netdev = !device.interface.starts_with('(');
+    if device.status != DeviceStatus::Kernel || !has_netdev || UIO_DRIVERS.contains(&driver) {
+        return None;
     }
+
+    let native = matches!(driver,
+        "i40e" | "ice" | "ixgbe" | "ixgbevf" |
+        "mlx5_core" | "mlx4_core" |
+        "virtio_net" | "veth" |
+        "tun" | "tap" |
+        "nfp" | "qede" | "bnxt_en" |
+        "thunderx" | "ena"
+    );
+    Some(if native { XdpMode::Native } else { XdpMode::Generic })
 }
 
 /// Get RDMA device name from PCI address
@@ -177,12 +341,11 @@ fn get_rdma_device_name(pci_address: &str) -> Result<String> {
 
         // Get the device symlink to find PCI address
         let device_path = entry.path().join("device");
-        if let Ok(target) = fs::read_link(&device_path) {
-            if let Some(dev_name) = target.file_name() {
-                if dev_name.to_string_lossy() == pci_address {
-                    return Ok(rdma_name);
-                }
-            }
+        if let Ok(target) = fs::read_link(&device_path)
+            && let Some(dev_name) = target.file_name()
+            && dev_name.to_string_lossy() == pci_address
+        {
+            return Ok(rdma_name);
         }
     }
 
@@ -190,7 +353,7 @@ fn get_rdma_device_name(pci_address: &str) -> Result<String> {
 }
 
 /// Get all capable devices for a framework
-pub fn get_capable_devices(framework: Framework) -> Result<Vec<FrameworkDevice>> {
+pub fn get_capable_devices(framework: &Framework) -> Result<Vec<FrameworkDevice>> {
     let all_devices = crate::device::list_network_devices()?;
     let mut result = Vec::new();
 
@@ -204,10 +367,16 @@ pub fn get_capable_devices(framework: Framework) -> Result<Vec<FrameworkDevice>>
                 device.pci_address.clone()
             };
 
+            let reason = not_ready_reason(&device, framework).or_else(|| match framework {
+                Framework::Xdp => xdp_mode(&device).map(|m| m.label().to_string()),
+                _ => None,
+            });
+
             result.push(FrameworkDevice {
                 device,
                 is_ready,
                 reference_string,
+                reason,
             });
         }
     }
@@ -215,8 +384,189 @@ pub fn get_capable_devices(framework: Framework) -> Result<Vec<FrameworkDevice>>
     Ok(result)
 }
 
+/// Get devices that cannot support a framework, with the reason why
+pub fn get_incapable_devices(framework: &Framework) -> Result<Vec<FrameworkDevice>> {
+    let all_devices = crate::device::list_network_devices()?;
+
+    Ok(all_devices
+        .into_iter()
+        .filter_map(|device| {
+            incapable_reason(&device, framework).map(|reason| FrameworkDevice {
+                reference_string: device.pci_address.clone(),
+                device,
+                is_ready: false,
+                reason: Some(reason),
+            })
+        })
+        .collect())
+}
+
 /// Get only ready (available) devices for a framework
-pub fn get_available_devices(framework: Framework) -> Result<Vec<FrameworkDevice>> {
+pub fn get_available_devices(framework: &Framework) -> Result<Vec<FrameworkDevice>> {
     let capable = get_capable_devices(framework)?;
     Ok(capable.into_iter().filter(|d| d.is_ready).collect())
 }
+
+/// Ways to detect a userspace dependency; any one match counts as present
+#[derive(Debug, Clone, Copy)]
+enum Probe {
+    Command(&'static str),
+    Path(&'static str),
+    PkgConfig(&'static str),
+}
+
+/// A userspace component a framework needs, and where it was found (None = missing)
+#[derive(Debug, Clone)]
+pub struct Dependency {
+    pub name: &'static str,
+    pub found: Option<String>,
+    pub expected: String, // What was looked for, for the report
+}
+
+impl Framework {
+    /// Userspace pieces the framework needs besides the device binding
+    fn dependency_probes(&self) -> Vec<(&'static str, &'static [Probe])> {
+        match self {
+            Framework::Dpdk => vec![
+                ("DPDK testpmd", &[Probe::Command("dpdk-testpmd")]),
+                ("DPDK libraries", &[Probe::PkgConfig("libdpdk")]),
+            ],
+            Framework::Rdma => vec![
+                ("rdma-core tools", &[Probe::Command("ibv_devinfo")]),
+                ("RDMA devices", &[Probe::Path("/sys/class/infiniband")]),
+            ],
+            Framework::TcpDirect => vec![
+                ("TCPDirect", &[Probe::Command("zf_stackdump"), Probe::Path("/usr/include/zf/zf.h")]),
+            ],
+            Framework::OpenOnload => vec![
+                ("Onload launcher", &[Probe::Command("onload")]),
+                ("Onload kernel module", &[Probe::Path("/sys/module/onload")]),
+            ],
+            Framework::EfVi => vec![
+                ("ef_vi headers", &[Probe::Path("/usr/include/etherfabric/ef_vi.h")]),
+            ],
+            Framework::Spdk => vec![
+                ("SPDK setup script", &[
+                    Probe::Command("spdk-setup"),
+                    Probe::Path("/usr/local/share/spdk/scripts/setup.sh"),
+                    Probe::Path("/usr/share/spdk/scripts/setup.sh"),
+                ]),
+                ("SPDK libraries", &[Probe::PkgConfig("spdk_nvme")]),
+            ],
+            Framework::Vpp => vec![
+                ("VPP", &[Probe::Command("vpp")]),
+                ("vppctl", &[Probe::Command("vppctl")]),
+            ],
+            Framework::Xdp => vec![
+                ("XDP loader", &[Probe::Command("xdp-loader"), Probe::Command("bpftool")]),
+            ],
+            Framework::Custom { .. } => Vec::new(),
+        }
+    }
+}
+
+/// Find an executable in $PATH (plus the sbin directories, which sudo may drop)
+fn find_command(name: &str) -> Option<String> {
+    let path = std::env::var("PATH").unwrap_or_default();
+    path.split(':')
+        .chain(["/usr/local/sbin", "/usr/sbin", "/sbin"])
+        .filter(|dir| !dir.is_empty())
+        .map(|dir| Path::new(dir).join(name))
+        .find(|candidate| candidate.is_file())
+        .map(|candidate| candidate.display().to_string())
+}
+
+fn run_probe(probe: Probe) -> Option<String> {
+    match probe {
+        Probe::Command(name) => find_command(name),
+        Probe::Path(path) => Path::new(path).exists().then(|| path.to_string()),
+        Probe::PkgConfig(package) => std::process::Command::new("pkg-config")
+            .args(["--exists", package])
+            .status()
+            .ok()
+            .filter(|status| status.success())
+            .map(|_| format!("pkg-config {}", package)),
+    }
+}
+
+fn describe_probe(probe: Probe) -> String {
+    match probe {
+        Probe::Command(name) => name.to_string(),
+        Probe::Path(path) => path.to_string(),
+        Probe::PkgConfig(package) => format!("pkg-config {}", package),
+    }
+}
+
+/// Check for the framework's userspace dependencies (best effort)
+pub fn check_dependencies(framework: &Framework) -> Vec<Dependency> {
+    framework
+        .dependency_probes()
+        .into_iter()
+        .map(|(name, probes)| Dependency {
+            name,
+            found: probes.iter().find_map(|p| run_probe(*p)),
+            expected: probes.iter().map(|p| describe_probe(*p)).collect::<Vec<_>>().join(" or "),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::SriovRole;
+
+    fn nic(vendor: &str, device: &str) -> NetworkDevice {
+        NetworkDevice {
+            interface: "eth0".to_string(),
+            pci_address: "0000:01:00.0".to_string(),
+            driver: None,
+            iommu_group: None,
+            vendor_id: vendor.to_string(),
+            device_id: device.to_string(),
+            subsystem_vendor_id: None,
+            subsystem_device_id: None,
+            speed: None,
+            max_speed: None,
+            status: DeviceStatus::Unbound,
+            sriov_role: SriovRole::None,
+            override_pending: None,
+        }
+    }
+
+    fn definition(vendors: &[&str], devices: &[&str]) -> FrameworkDefinition {
+        FrameworkDefinition {
+            requires_vfio: true,
+            reference_type: ReferenceType::Pci,
+            vendors: vendors.iter().map(|v| v.to_string()).collect(),
+            devices: devices.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn empty_filters_match_any_device() {
+        assert!(matches_definition(&nic("0x8086", "0x1572"), &definition(&[], &[])));
+    }
+
+    #[test]
+    fn vendor_filter_ignores_case_and_prefix() {
+        let def = definition(&["8086", "0x15B3"], &[]);
+        assert!(matches_definition(&nic("0x8086", "0x1572"), &def));
+        assert!(matches_definition(&nic("0x15b3", "0x1017"), &def));
+        assert!(!matches_definition(&nic("0x14e4", "0x16d7"), &def));
+    }
+
+    #[test]
+    fn device_filter_needs_vendor_and_device() {
+        let def = definition(&[], &["0x8086:0x1572", "15b3:1017", "bogus"]);
+        assert!(matches_definition(&nic("0x8086", "0x1572"), &def));
+        assert!(matches_definition(&nic("0x15b3", "0x1017"), &def));
+        assert!(!matches_definition(&nic("0x8086", "0x1017"), &def));
+    }
+
+    #[test]
+    fn both_filters_must_match() {
+        let def = definition(&["0x8086"], &["0x15b3:0x1017"]);
+        assert!(!matches_definition(&nic("0x8086", "0x1572"), &def));
+        assert!(!matches_definition(&nic("0x15b3", "0x1017"), &def));
+    }
+}
@@ -27,15 +27,165 @@ pub fn detect_cpu_vendor() -> CpuVendor {
     CpuVendor::Unknown
 }
 
-/// Check if IOMMU is enabled in current kernel parameters
+/// Check if IOMMU is fully configured (enabled and in passthrough mode)
 pub fn is_iommu_enabled() -> Result<bool> {
-    let cmdline = fs::read_to_string("/proc/cmdline")
-        .context("Failed to read /proc/cmdline")?;
+    Ok(is_iommu_on()? && is_passthrough_enabled()?)
+}
+
+/// Check if IOMMU is turned on in current kernel parameters
+pub fn is_iommu_on() -> Result<bool> {
+    let cmdline = read_cmdline()?;
+
+    Ok(cmdline.contains("intel_iommu=on") || cmdline.contains("amd_iommu=on"))
+}
+
+/// Check if IOMMU passthrough mode (iommu=pt) is set in current kernel parameters
+/// Passthrough is a performance recommendation, not a hard requirement for VFIO
+pub fn is_passthrough_enabled() -> Result<bool> {
+    let cmdline = read_cmdline()?;
+
+    Ok(cmdline.contains("iommu=pt"))
+}
+
+/// A passthrough-related kernel parameter beyond the basic `on`/`pt` pair
+#[derive(Debug, Clone)]
+pub struct KernelFlag {
+    pub param: String,
+    pub meaning: &'static str,
+}
+
+/// Additional parameters relevant to real passthrough setups
+/// Entries ending in '=' match any value.
+const PASSTHROUGH_FLAGS: &[(&str, &str)] = &[
+    ("intel_iommu=igfx_off", "IOMMU disabled for the integrated GPU"),
+    ("intel_iommu=sm_on", "scalable mode enabled"),
+    ("intel_iommu=strict", "strict IOTLB invalidation"),
+    ("amd_iommu=force_isolation", "every device isolated in its own domain"),
+    ("amd_iommu=fullflush", "IOTLB flushed on every unmap"),
+    ("iommu.strict=", "IOTLB invalidation policy"),
+    ("iommu.passthrough=", "default domain type"),
+    ("pcie_acs_override=", "ACS override (splits IOMMU groups without real isolation)"),
+    ("video=efifb:off", "host EFI framebuffer disabled"),
+    ("initcall_blacklist=sysfb_init", "host sysfb framebuffer disabled"),
+    ("vfio-pci.ids=", "devices claimed by vfio-pci at boot"),
+    ("vfio_iommu_type1.allow_unsafe_interrupts=", "interrupt remapping requirement relaxed"),
+];
+
+/// Split a kernel command line into parameters, one per comma-separated IOMMU option
+/// (`intel_iommu=on,igfx_off` becomes `intel_iommu=on` and `intel_iommu=igfx_off`)
+fn split_cmdline_params(cmdline: &str) -> Vec<String> {
+    cmdline
+        .split_whitespace()
+        .flat_map(|p| match p.split_once('=') {
+            Some((key @ ("intel_iommu" | "amd_iommu"), values)) => {
+                values.split(',').map(|v| format!("{}={}", key, v)).collect()
+            }
+            _ => vec![p.to_string()],
+        })
+        .collect()
+}
+
+/// Parameters set in the GRUB_CMDLINE_LINUX* variables of a /etc/default/grub file
+/// (comments and other variables don't count)
+fn grub_cmdline_params(grub_content: &str) -> Vec<String> {
+    grub_content
+        .lines()
+        .filter(|l| l.trim_start().starts_with("GRUB_CMDLINE_LINUX"))
+        .filter_map(|l| l.split_once('=').map(|(_, v)| v.trim().trim_matches('"')))
+        .flat_map(split_cmdline_params)
+        .collect()
+}
+
+/// Find passthrough-related flags in a kernel command line
+/// Comma-separated IOMMU options (`intel_iommu=on,igfx_off`) are matched individually.
+fn parse_passthrough_flags(cmdline: &str) -> Vec<KernelFlag> {
+    split_cmdline_params(cmdline)
+        .iter()
+        .filter_map(|param| {
+            PASSTHROUGH_FLAGS
+                .iter()
+                .find(|(flag, _)| if flag.ends_with('=') { param.starts_with(flag) } else { param == flag })
+                .map(|(_, meaning)| KernelFlag { param: param.clone(), meaning })
+        })
+        .collect()
+}
+
+/// Passthrough-related flags set on the running kernel
+pub fn active_passthrough_flags() -> Vec<KernelFlag> {
+    parse_passthrough_flags(&read_cmdline().unwrap_or_default())
+}
+
+/// `vendor:device` pairs a module claims via `<module>.ids=` on a kernel command line
+/// Both the dashed and underscored spellings of the module name are accepted.
+fn parse_cmdline_ids(cmdline: &str, module: &str) -> Vec<(String, String)> {
+    let prefixes = [format!("{}.ids=", module), format!("{}.ids=", module.replace('-', "_"))];
+    let mut ids = Vec::new();
+
+    for param in cmdline.split_whitespace() {
+        let Some(list) = prefixes.iter().find_map(|p| param.strip_prefix(p.as_str())) else { continue };
+        for id in list.split(',') {
+            let mut fields = id.split(':');
+            if let (Some(vendor), Some(dev_id)) = (fields.next(), fields.next()) {
+                let pair = (vendor.to_lowercase(), dev_id.to_lowercase());
+                if !ids.contains(&pair) {
+                    ids.push(pair);
+                }
+            }
+        }
+    }
+
+    ids
+}
+
+/// IDs the running kernel's command line hands to `module` (e.g. "vfio-pci", "pci-stub")
+pub fn cmdline_ids(module: &str) -> Vec<(String, String)> {
+    parse_cmdline_ids(&read_cmdline().unwrap_or_default(), module)
+}
 
-    let has_iommu = cmdline.contains("intel_iommu=on") || cmdline.contains("amd_iommu=on");
-    let has_passthrough = cmdline.contains("iommu=pt");
+fn read_cmdline() -> Result<String> {
+    fs::read_to_string("/proc/cmdline")
+        .context("Failed to read /proc/cmdline")
+}
+
+/// Generated GRUB configs (Debian/Ubuntu, then Fedora/RHEL layout)
+const GRUB_CFG_PATHS: &[&str] = &["/boot/grub/grub.cfg", "/boot/grub2/grub.cfg"];
 
-    Ok(has_iommu && has_passthrough)
+fn is_iommu_param(param: &str) -> bool {
+    ["intel_iommu=", "amd_iommu=", "iommu=", "iommu."].iter().any(|p| param.starts_with(p))
+        || get_gpu_params().iter().any(|p| p == param)
+}
+
+/// IOMMU parameters configured for the next boot but not active in the running kernel
+/// Parameters come from /etc/default/grub; when a generated grub.cfg is readable, only
+/// parameters that made it into grub.cfg count (otherwise update-grub is what's pending).
+pub fn pending_iommu_params() -> Vec<String> {
+    let Ok(grub_content) = fs::read_to_string(GRUB_DEFAULT) else {
+        return Vec::new();
+    };
+    let active = read_cmdline().unwrap_or_default();
+    let active: Vec<&str> = active.split_whitespace().collect();
+    let generated = GRUB_CFG_PATHS.iter().find_map(|p| fs::read_to_string(p).ok());
+
+    let mut pending: Vec<String> = grub_content
+        .lines()
+        .filter(|l| l.trim_start().starts_with("GRUB_CMDLINE_LINUX"))
+        .filter_map(|l| l.split_once('=').map(|(_, v)| v.trim().trim_matches('"')))
+        .flat_map(|v| v.split_whitespace())
+        .filter(|p| is_iommu_param(p) && !active.contains(p))
+        .filter(|p| {
+            generated.as_ref().is_none_or(|cfg| {
+                cfg.lines()
+                    .filter(|l| l.trim_start().starts_with("linux"))
+                    .any(|l| l.split_whitespace().any(|w| w == *p))
+            })
+        })
+        .map(String::from)
+        .collect();
+
+    // Both GRUB_CMDLINE_LINUX and _DEFAULT may carry a parameter; keep the first of each
+    let mut seen = std::collections::HashSet::new();
+    pending.retain(|p| seen.insert(p.clone()));
+    pending
 }
 
 /// Get required IOMMU parameters for current CPU
@@ -51,15 +201,37 @@ pub fn get_required_iommu_params() -> Result<Vec<String>> {
     }
 }
 
+/// Extra parameters for GPU passthrough: keep the host's integrated GPU and
+/// framebuffer off the devices being passed through
+pub fn get_gpu_params() -> Vec<String> {
+    let mut params = Vec::new();
+    if detect_cpu_vendor() == CpuVendor::Intel {
+        params.push("intel_iommu=igfx_off".to_string());
+    }
+    params.push("video=efifb:off".to_string());
+    params
+}
+
 /// Setup IOMMU in GRUB configuration
-pub fn setup_iommu(skip_confirm: bool) -> Result<()> {
+/// With `gpu`, also offer the GPU passthrough parameters from `get_gpu_params`
+pub fn setup_iommu(skip_confirm: bool, gpu: bool) -> Result<()> {
+    let active_flags: Vec<String> = active_passthrough_flags().into_iter().map(|f| f.param).collect();
+    let gpu_params: Vec<String> = if gpu {
+        get_gpu_params().into_iter().filter(|p| !active_flags.contains(p)).collect()
+    } else {
+        Vec::new()
+    };
+
     // Check if already enabled
     if is_iommu_enabled()? {
-        println!("{}", "✓ IOMMU is already enabled".bright_green());
-        return Ok(());
+        if gpu_params.is_empty() {
+            println!("{}", "✓ IOMMU is already enabled".bright_green());
+            return Ok(());
+        }
+        println!("{}", "✓ IOMMU is enabled, GPU passthrough parameters are not".bright_yellow());
+    } else {
+        println!("{}", "IOMMU is not enabled in kernel parameters".bright_yellow());
     }
-
-    println!("{}", "IOMMU is not enabled in kernel parameters".bright_yellow());
     println!();
 
     // Detect CPU
@@ -72,16 +244,30 @@ pub fn setup_iommu(skip_confirm: bool) -> Result<()> {
 
     println!("Detected CPU: {}", vendor_str.bright_cyan());
 
-    let params = get_required_iommu_params()?;
+    let mut params = get_required_iommu_params()?;
     println!("Required parameters: {}", params.join(" ").bright_cyan());
+
+    if !gpu_params.is_empty() {
+        println!("GPU passthrough parameters: {}", gpu_params.join(" ").bright_cyan());
+        let add_gpu = skip_confirm || Confirm::new()
+            .with_prompt("Add the GPU passthrough parameters too?")
+            .default(true)
+            .interact()?;
+        if add_gpu {
+            params.extend(gpu_params);
+        }
+    }
     println!();
 
     // Read current GRUB config
     let grub_content = fs::read_to_string(GRUB_DEFAULT)
         .context("Failed to read /etc/default/grub. Are you running as root?")?;
 
-    // Check if already has the parameters
-    if params.iter().all(|p| grub_content.contains(p)) {
+    // Only add what is missing; the required pair may already be there
+    let present = grub_cmdline_params(&grub_content);
+    params.retain(|p| !present.contains(p));
+
+    if params.is_empty() {
         println!("{}", "✓ GRUB already has IOMMU parameters".bright_green());
         println!("{}", "  You may need to reboot for changes to take effect.".bright_yellow());
         return Ok(());
@@ -147,34 +333,40 @@ pub fn setup_iommu(skip_confirm: bool) -> Result<()> {
 }
 
 /// Add IOMMU parameters to GRUB config
+/// Parameters already set in a GRUB_CMDLINE_LINUX* variable (comma form included) are skipped
 fn add_iommu_params(grub_content: &str, params: &[String]) -> Result<String> {
     let mut lines: Vec<String> = grub_content.lines().map(String::from).collect();
-    let params_str = params.join(" ");
+    let present = grub_cmdline_params(grub_content);
+    let params_str = params
+        .iter()
+        .filter(|p| !present.contains(p))
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(" ");
 
     for line in &mut lines {
         // Find GRUB_CMDLINE_LINUX_DEFAULT line
         if line.trim_start().starts_with("GRUB_CMDLINE_LINUX_DEFAULT") {
             // Extract the quoted value
-            if let Some(start_quote) = line.find('"') {
-                if let Some(end_quote) = line.rfind('"') {
-                    if start_quote < end_quote {
-                        let current_params = &line[start_quote + 1..end_quote];
-
-                        // Add new parameters
-                        let new_params = if current_params.is_empty() {
-                            params_str.clone()
-                        } else {
-                            format!("{} {}", current_params, params_str)
-                        };
-
-                        *line = format!(
-                            "GRUB_CMDLINE_LINUX_DEFAULT=\"{}\"",
-                            new_params
-                        );
-
-                        break;
-                    }
-                }
+            if let Some(start_quote) = line.find('"')
+                && let Some(end_quote) = line.rfind('"')
+                && start_quote < end_quote
+            {
+                let current_params = &line[start_quote + 1..end_quote];
+
+                // Add new parameters
+                let new_params = if current_params.is_empty() {
+                    params_str.clone()
+                } else {
+                    format!("{} {}", current_params, params_str)
+                };
+
+                *line = format!(
+                    "GRUB_CMDLINE_LINUX_DEFAULT=\"{}\"",
+                    new_params
+                );
+
+                break;
             }
         }
     }
@@ -205,3 +397,41 @@ pub fn check_grub_config() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cmdline_ids_accept_both_module_spellings() {
+        let cmdline = "ro pci-stub.ids=8086:10FB,8086:1521 vfio_pci.ids=8086:10fb quiet";
+        assert_eq!(parse_cmdline_ids(cmdline, "pci-stub"), vec![
+            ("8086".to_string(), "10fb".to_string()),
+            ("8086".to_string(), "1521".to_string()),
+        ]);
+        assert_eq!(parse_cmdline_ids(cmdline, "vfio-pci"), vec![("8086".to_string(), "10fb".to_string())]);
+        assert!(parse_cmdline_ids("ro quiet", "vfio-pci").is_empty());
+    }
+
+    #[test]
+    fn add_iommu_params_skips_options_already_set_in_comma_form() {
+        let grub = "\
+# GRUB_CMDLINE_LINUX_DEFAULT=\"iommu=pt\"
+GRUB_DEFAULT=0
+GRUB_CMDLINE_LINUX_DEFAULT=\"quiet intel_iommu=on,igfx_off\"
+GRUB_CMDLINE_LINUX=\"\"
+";
+        let params = ["intel_iommu=on", "iommu=pt", "intel_iommu=igfx_off"].map(String::from);
+        let updated = add_iommu_params(grub, &params).unwrap();
+
+        assert!(updated.contains("GRUB_CMDLINE_LINUX_DEFAULT=\"quiet intel_iommu=on,igfx_off iommu=pt\"\n"), "{}", updated);
+        assert!(updated.starts_with("# GRUB_CMDLINE_LINUX_DEFAULT=\"iommu=pt\"\n"));
+    }
+
+    #[test]
+    fn passthrough_flags_split_combined_options() {
+        let flags = parse_passthrough_flags("ro intel_iommu=on,igfx_off iommu=pt pcie_acs_override=downstream,multifunction quiet");
+        let params: Vec<&str> = flags.iter().map(|f| f.param.as_str()).collect();
+        assert_eq!(params, ["intel_iommu=igfx_off", "pcie_acs_override=downstream,multifunction"]);
+    }
+}
@@ -3,57 +3,270 @@ use std::path::Path;
 use anyhow::Result;
 use colored::Colorize;
 
-use crate::grub;
+use crate::{device, grub};
 
 #[derive(Debug, Clone)]
 pub struct SystemStatus {
-    pub iommu_enabled: bool,
+    pub iommu_on: bool,
+    pub passthrough: bool,
     pub vfio_module_loaded: bool,
+    pub modules: Vec<ModuleState>,
     pub iommu_groups_count: usize,
     pub vfio_devices_count: usize,
     pub cpu_vendor: grub::CpuVendor,
+    /// IOMMU parameters set up in GRUB that need a reboot to take effect
+    pub pending_params: Vec<String>,
+    /// Active passthrough-related parameters beyond `on`/`pt` (igfx_off, ACS override, ...)
+    pub passthrough_flags: Vec<grub::KernelFlag>,
+    pub iommu_hardware: IommuHardware,
+    pub kernel: KernelInfo,
+}
+
+impl SystemStatus {
+    /// Every required piece of the VFIO stack is in place
+    pub fn is_ready(&self) -> bool {
+        self.iommu_on && vfio_stack_loaded(&self.modules) && self.iommu_groups_count > 0
+    }
+}
+
+/// Running kernel release and its VFIO-relevant build options
+#[derive(Debug, Clone)]
+pub struct KernelInfo {
+    pub release: String,
+    /// Where the build config was read from; None if no config was found
+    pub config_source: Option<String>,
+    /// (option, value): "y"/"m"/..., or None if not set in the config
+    pub options: Vec<(&'static str, Option<String>)>,
+}
+
+impl KernelInfo {
+    /// Value of a build option; None if unset or the config is unavailable
+    pub fn option(&self, name: &str) -> Option<&str> {
+        self.options.iter()
+            .find(|(n, _)| *n == name)
+            .and_then(|(_, v)| v.as_deref())
+    }
+
+    /// The config is known and the option is explicitly not built
+    pub fn lacks(&self, name: &str) -> bool {
+        self.config_source.is_some() && self.option(name).is_none()
+    }
+}
+
+/// Kernel build options that VFIO depends on or that change its defaults
+pub const KERNEL_CONFIG_OPTIONS: &[&str] = &[
+    "CONFIG_VFIO",
+    "CONFIG_VFIO_PCI",
+    "CONFIG_VFIO_IOMMU_TYPE1",
+    "CONFIG_IOMMUFD",
+    "CONFIG_IOMMU_DEFAULT_PASSTHROUGH",
+];
+
+/// Whether the platform firmware describes an IOMMU at all
+#[derive(Debug, Clone, PartialEq)]
+pub enum IommuHardware {
+    /// ACPI table describing the IOMMU (DMAR, IVRS or IORT)
+    Present(&'static str),
+    /// ACPI tables are readable and none describes an IOMMU
+    Absent,
+    /// No ACPI tables to go by (e.g. device-tree platforms)
+    Unknown,
+}
+
+/// ACPI tables that describe IOMMU hardware: Intel VT-d, AMD-Vi, Arm SMMU
+const IOMMU_ACPI_TABLES: &[(&str, &str)] = &[
+    ("DMAR", "DMAR (Intel VT-d)"),
+    ("IVRS", "IVRS (AMD-Vi)"),
+    ("IORT", "IORT (Arm SMMU)"),
+];
+
+/// Detect IOMMU hardware from the ACPI tables the firmware exposes
+/// The table is only published when the IOMMU exists and is enabled in firmware,
+/// so `Absent` means either no IOMMU or one switched off in BIOS/UEFI; kernel parameters
+/// alone can't turn it on.
+pub fn detect_iommu_hardware() -> IommuHardware {
+    let tables = Path::new("/sys/firmware/acpi/tables");
+    if !tables.is_dir() {
+        return IommuHardware::Unknown;
+    }
+
+    IOMMU_ACPI_TABLES
+        .iter()
+        .find(|(name, _)| tables.join(name).exists())
+        .map(|(_, label)| IommuHardware::Present(label))
+        .unwrap_or(IommuHardware::Absent)
+}
+
+/// Every item `check` verifies, in report order
+pub const SYSTEM_CHECKS: &[&str] = &[
+    "kernel_config",
+    "iommu_hardware",
+    "iommu_enabled",
+    "vfio_core",
+    "iommu_backend",
+    "vfio_pci",
+    "iommu_groups",
+    "driver_override",
+    "pci_stub_ids",
+];
+
+/// Load state of one kernel module in the VFIO stack
+#[derive(Debug, Clone)]
+pub struct ModuleState {
+    pub name: &'static str,
+    pub loaded: bool,   // Loaded or built into the kernel
+    pub role: &'static str,
 }
 
 #[derive(Debug, Clone)]
 pub enum SystemIssue {
+    KernelLacksVfio,
+    NoIommuHardware,
     IommuNotEnabled,
+    RebootPending,
+    VfioCoreNotLoaded,
+    IommuBackendNotLoaded,
     VfioModuleNotLoaded,
     NoIommuGroups,
+    DriverOverridePending,
+    StubIdConflict,
+}
+
+/// How serious an issue is and whether it can be resolved now
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IssueSeverity {
+    Blocker,             // Prevents VFIO, needs manual configuration
+    BlockerNeedsReboot,  // Prevents VFIO, needs BIOS/UEFI change and reboot
+    AutoFixable,         // Can be resolved with --fix
+}
+
+impl IssueSeverity {
+    pub fn label(&self) -> &str {
+        match self {
+            IssueSeverity::Blocker => "BLOCKER",
+            IssueSeverity::BlockerNeedsReboot => "BLOCKER - NEEDS REBOOT",
+            IssueSeverity::AutoFixable => "AUTO-FIXABLE",
+        }
+    }
 }
 
 impl SystemIssue {
+    pub fn severity(&self) -> IssueSeverity {
+        match self {
+            SystemIssue::KernelLacksVfio => IssueSeverity::Blocker,
+            SystemIssue::NoIommuHardware => IssueSeverity::BlockerNeedsReboot,
+            SystemIssue::IommuNotEnabled => IssueSeverity::Blocker,
+            SystemIssue::RebootPending => IssueSeverity::BlockerNeedsReboot,
+            SystemIssue::VfioCoreNotLoaded
+            | SystemIssue::IommuBackendNotLoaded
+            | SystemIssue::VfioModuleNotLoaded => IssueSeverity::AutoFixable,
+            SystemIssue::NoIommuGroups => IssueSeverity::BlockerNeedsReboot,
+            SystemIssue::DriverOverridePending => IssueSeverity::AutoFixable,
+            SystemIssue::StubIdConflict => IssueSeverity::Blocker,
+        }
+    }
+
+    pub fn is_auto_fixable(&self) -> bool {
+        self.severity() == IssueSeverity::AutoFixable
+    }
+
+    /// Fixing this takes a reboot (kernel parameters, BIOS/UEFI or a different kernel)
+    pub fn needs_reboot(&self) -> bool {
+        !self.is_auto_fixable()
+    }
+
+    /// Name of the `check` item this issue fails
+    pub fn check_name(&self) -> &'static str {
+        match self {
+            SystemIssue::KernelLacksVfio => "kernel_config",
+            SystemIssue::NoIommuHardware => "iommu_hardware",
+            SystemIssue::IommuNotEnabled | SystemIssue::RebootPending => "iommu_enabled",
+            SystemIssue::VfioCoreNotLoaded => "vfio_core",
+            SystemIssue::IommuBackendNotLoaded => "iommu_backend",
+            SystemIssue::VfioModuleNotLoaded => "vfio_pci",
+            SystemIssue::NoIommuGroups => "iommu_groups",
+            SystemIssue::DriverOverridePending => "driver_override",
+            SystemIssue::StubIdConflict => "pci_stub_ids",
+        }
+    }
+
     pub fn description(&self) -> &str {
         match self {
+            SystemIssue::KernelLacksVfio => "Running kernel was built without CONFIG_VFIO/CONFIG_VFIO_PCI",
+            SystemIssue::NoIommuHardware => "No IOMMU reported by firmware (no DMAR/IVRS ACPI table)",
             SystemIssue::IommuNotEnabled => "IOMMU is not enabled in kernel parameters",
-            SystemIssue::VfioModuleNotLoaded => "VFIO kernel module is not loaded",
+            SystemIssue::RebootPending => "IOMMU is configured in GRUB but not active yet (reboot pending)",
+            SystemIssue::VfioCoreNotLoaded => "VFIO core module (vfio) is not loaded",
+            SystemIssue::IommuBackendNotLoaded => "No VFIO IOMMU backend loaded (vfio_iommu_type1 or iommufd)",
+            SystemIssue::VfioModuleNotLoaded => "VFIO PCI driver module (vfio_pci) is not loaded",
             SystemIssue::NoIommuGroups => "No IOMMU groups found",
+            SystemIssue::DriverOverridePending => "Devices are stuck unbound: driver_override names a driver that isn't loaded",
+            SystemIssue::StubIdConflict => "pci-stub.ids= on the kernel command line claims devices meant for vfio-pci",
         }
     }
 
     pub fn fix_command(&self) -> &str {
         match self {
+            SystemIssue::KernelLacksVfio => "Boot a kernel built with CONFIG_VFIO=m and CONFIG_VFIO_PCI=m (or =y)",
+            SystemIssue::NoIommuHardware => "Enable VT-d (Intel) or AMD-Vi (AMD) in BIOS/UEFI if the platform has one, then reboot",
             SystemIssue::IommuNotEnabled => "vfio-tool setup-grub",
+            SystemIssue::RebootPending => "sudo reboot",
+            SystemIssue::VfioCoreNotLoaded => "sudo modprobe vfio",
+            SystemIssue::IommuBackendNotLoaded => "sudo modprobe vfio_iommu_type1",
             SystemIssue::VfioModuleNotLoaded => "sudo modprobe vfio-pci",
             SystemIssue::NoIommuGroups => "Enable IOMMU in BIOS/UEFI (VT-d for Intel, AMD-Vi for AMD)",
+            SystemIssue::DriverOverridePending => "sudo modprobe vfio-pci (or the driver named in driver_override)",
+            SystemIssue::StubIdConflict => "Remove the IDs from pci-stub.ids= in GRUB_CMDLINE_LINUX, run update-grub and reboot",
         }
     }
 
     pub fn fix(&self) -> Result<()> {
         match self {
+            SystemIssue::KernelLacksVfio => {
+                println!("{}", "Cannot automatically fix: kernel built without VFIO".bright_red());
+                println!("Install or build a kernel with CONFIG_VFIO and CONFIG_VFIO_PCI enabled.");
+                anyhow::bail!("Manual intervention required");
+            }
+            SystemIssue::NoIommuHardware => {
+                println!("{}", "Cannot automatically fix: no IOMMU reported by firmware".bright_red());
+                println!("The firmware publishes no DMAR (Intel) or IVRS (AMD) table. Enable VT-d/AMD-Vi");
+                println!("in BIOS/UEFI if the platform has one, then reboot; without an IOMMU, VFIO");
+                println!("device assignment is not possible.");
+                anyhow::bail!("Manual intervention required");
+            }
             SystemIssue::IommuNotEnabled => {
                 println!("{}", "Fixing: IOMMU not enabled".bright_yellow());
                 println!("This requires GRUB configuration and reboot.");
                 println!("Run: sudo vfio-tool setup-grub");
                 anyhow::bail!("Manual intervention required");
             }
-            SystemIssue::VfioModuleNotLoaded => {
-                println!("{}", "Loading VFIO module...".bright_cyan());
-                std::process::Command::new("modprobe")
-                    .arg("vfio-pci")
-                    .status()?;
-                println!("{}", "✓ VFIO module loaded".bright_green());
+            SystemIssue::RebootPending => {
+                println!("{}", "Cannot automatically fix: reboot pending".bright_red());
+                println!("GRUB already has the IOMMU parameters; reboot to activate them.");
+                anyhow::bail!("Manual intervention required");
+            }
+            SystemIssue::VfioCoreNotLoaded => load_module("vfio"),
+            SystemIssue::IommuBackendNotLoaded => load_module("vfio_iommu_type1"),
+            SystemIssue::VfioModuleNotLoaded => load_module("vfio-pci"),
+            SystemIssue::DriverOverridePending => {
+                // Loading the driver binds every device whose override names it
+                let mut drivers: Vec<String> = crate::device::pending_overrides()
+                    .into_iter()
+                    .map(|(_, driver)| driver)
+                    .collect();
+                drivers.sort();
+                drivers.dedup();
+                for driver in drivers {
+                    load_module(&driver)?;
+                }
                 Ok(())
             }
+            SystemIssue::StubIdConflict => {
+                println!("{}", "Cannot automatically fix: pci-stub.ids= conflict".bright_red());
+                println!("Edit GRUB_CMDLINE_LINUX in /etc/default/grub so each ID is only in vfio-pci.ids=,");
+                println!("then regenerate the GRUB config and reboot.");
+                anyhow::bail!("Manual intervention required");
+            }
             SystemIssue::NoIommuGroups => {
                 println!("{}", "Cannot automatically fix: No IOMMU groups".bright_red());
                 println!("You must:");
@@ -67,20 +280,148 @@ impl SystemIssue {
     }
 }
 
+/// Load a module with modprobe as part of `check --fix`
+fn load_module(name: &str) -> Result<()> {
+    println!("{}", format!("Loading {} module...", name).bright_cyan());
+
+    let status = std::process::Command::new("modprobe")
+        .arg(name)
+        .status()?;
+    if !status.success() {
+        anyhow::bail!("modprobe {} failed", name);
+    }
+
+    println!("{}", format!("✓ {} module loaded", name).bright_green());
+    Ok(())
+}
+
+/// A device within an IOMMU group
+#[derive(Debug, Clone)]
+pub struct GroupDevice {
+    pub pci_address: String,
+    pub class: String,
+    pub vendor_device: String,
+    pub driver: Option<String>,
+    pub is_endpoint: bool,
+    pub numa_node: Option<u32>,
+}
+
+/// An IOMMU group and its member devices
+#[derive(Debug, Clone)]
+pub struct IommuGroup {
+    pub id: u32,
+    pub devices: Vec<GroupDevice>,
+}
+
+impl IommuGroup {
+    /// Safe to pass through on its own: exactly one endpoint device
+    pub fn is_isolated(&self) -> bool {
+        self.devices.iter().filter(|d| d.is_endpoint).count() == 1
+    }
+
+    /// Distinct NUMA nodes of the members, sorted (empty on single-node systems)
+    pub fn numa_nodes(&self) -> Vec<u32> {
+        let mut nodes: Vec<u32> = self.devices.iter().filter_map(|d| d.numa_node).collect();
+        nodes.sort();
+        nodes.dedup();
+        nodes
+    }
+
+    /// Members sit on more than one NUMA node, so no single node is local to the whole group
+    pub fn spans_numa_nodes(&self) -> bool {
+        self.numa_nodes().len() > 1
+    }
+
+    /// VFIO can open the group: every member is on a vfio driver or has none
+    pub fn is_viable(&self) -> bool {
+        self.devices.iter().all(|d| driver_allows_vfio(d.driver.as_deref()))
+    }
+}
+
+/// Whether a group member with this driver lets VFIO use the group
+/// No driver or a vfio driver (vfio-pci, vendor variants like mlx5_vfio_pci) is fine; the
+/// kernel also tolerates pci-stub and pcieport, which never do DMA on behalf of the device
+fn driver_allows_vfio(driver: Option<&str>) -> bool {
+    match driver {
+        None => true,
+        Some(d) => d.contains("vfio") || matches!(d, "pci-stub" | "pcieport"),
+    }
+}
+
+/// Check VFIO viability of a group from its members' current drivers
+pub fn group_viable(group_id: u32) -> Result<bool> {
+    let members = device::get_iommu_group_devices(group_id)?;
+
+    Ok(members
+        .iter()
+        .all(|pci| driver_allows_vfio(device::get_driver(pci).as_deref())))
+}
+
+/// List all IOMMU groups with their member devices, sorted by group ID
+pub fn list_iommu_groups() -> Result<Vec<IommuGroup>> {
+    let groups_dir = Path::new("/sys/kernel/iommu_groups");
+    let mut groups = Vec::new();
+
+    if !groups_dir.exists() {
+        return Ok(groups);
+    }
+
+    for entry in fs::read_dir(groups_dir)? {
+        let entry = entry?;
+        let Ok(id) = entry.file_name().to_string_lossy().parse::<u32>() else {
+            continue;
+        };
+
+        let mut members = device::get_iommu_group_devices(id)?;
+        members.sort();
+
+        let devices = members
+            .into_iter()
+            .map(|pci_address| GroupDevice {
+                class: device::get_pci_class(&pci_address).unwrap_or_else(|| "?".to_string()),
+                vendor_device: device::get_vendor_device_id(&pci_address)
+                    .map(|(v, d)| format!("{}:{}", v, d))
+                    .unwrap_or_else(|_| "?".to_string()),
+                driver: device::get_driver(&pci_address),
+                is_endpoint: device::is_endpoint(&pci_address),
+                numa_node: device::get_numa_node(&pci_address),
+                pci_address,
+            })
+            .collect();
+
+        groups.push(IommuGroup { id, devices });
+    }
+
+    groups.sort_by_key(|g| g.id);
+    Ok(groups)
+}
+
 /// Get overall system status
 pub fn get_system_status() -> Result<SystemStatus> {
-    let iommu_enabled = grub::is_iommu_enabled()?;
-    let vfio_module_loaded = is_vfio_module_loaded();
+    let iommu_on = grub::is_iommu_on()?;
+    let passthrough = grub::is_passthrough_enabled()?;
+    let modules = get_vfio_modules();
+    let vfio_module_loaded = is_module_present("vfio_pci");
     let iommu_groups_count = count_iommu_groups();
     let vfio_devices_count = count_vfio_devices();
     let cpu_vendor = grub::detect_cpu_vendor();
+    let pending_params = grub::pending_iommu_params();
+    let passthrough_flags = grub::active_passthrough_flags();
+    let iommu_hardware = detect_iommu_hardware();
+    let kernel = get_kernel_info();
 
     Ok(SystemStatus {
-        iommu_enabled,
+        iommu_on,
+        passthrough,
         vfio_module_loaded,
+        modules,
         iommu_groups_count,
         vfio_devices_count,
         cpu_vendor,
+        pending_params,
+        passthrough_flags,
+        iommu_hardware,
+        kernel,
     })
 }
 
@@ -88,13 +429,36 @@ pub fn get_system_status() -> Result<SystemStatus> {
 pub fn check_system() -> Result<Vec<SystemIssue>> {
     let mut issues = Vec::new();
 
-    // Check IOMMU
-    if !grub::is_iommu_enabled()? {
-        issues.push(SystemIssue::IommuNotEnabled);
+    // A kernel without VFIO can't be fixed by loading modules
+    let kernel = get_kernel_info();
+    if kernel.lacks("CONFIG_VFIO") || kernel.lacks("CONFIG_VFIO_PCI") {
+        issues.push(SystemIssue::KernelLacksVfio);
+    }
+
+    // No ACPI table: no IOMMU, or one disabled in BIOS/UEFI (the kernel checks below still apply)
+    if detect_iommu_hardware() == IommuHardware::Absent {
+        issues.push(SystemIssue::NoIommuHardware);
     }
 
-    // Check VFIO module
-    if !is_vfio_module_loaded() {
+    // Check IOMMU (passthrough mode is only a performance recommendation)
+    // After setup-grub, the parameters are in place and only a reboot is missing
+    if !grub::is_iommu_on()? {
+        let pending = grub::pending_iommu_params();
+        if pending.iter().any(|p| p.ends_with("_iommu=on")) {
+            issues.push(SystemIssue::RebootPending);
+        } else {
+            issues.push(SystemIssue::IommuNotEnabled);
+        }
+    }
+
+    // Check each piece of the VFIO module stack
+    if !is_module_present("vfio") {
+        issues.push(SystemIssue::VfioCoreNotLoaded);
+    }
+    if !is_module_present("vfio_iommu_type1") && !is_module_present("iommufd") {
+        issues.push(SystemIssue::IommuBackendNotLoaded);
+    }
+    if !is_module_present("vfio_pci") {
         issues.push(SystemIssue::VfioModuleNotLoaded);
     }
 
@@ -103,15 +467,99 @@ pub fn check_system() -> Result<Vec<SystemIssue>> {
         issues.push(SystemIssue::NoIommuGroups);
     }
 
+    // A device whose override driver isn't registered won't bind to anything
+    if !crate::device::pending_overrides().is_empty() {
+        issues.push(SystemIssue::DriverOverridePending);
+    }
+
+    // Whether pci-stub or vfio-pci gets a device listed for both depends on load order
+    let cfg = crate::config::load_config().ok();
+    if !crate::config::pci_stub_conflicts(cfg.as_ref()).is_empty() {
+        issues.push(SystemIssue::StubIdConflict);
+    }
+
     Ok(issues)
 }
 
-/// Check if VFIO module is loaded
-fn is_vfio_module_loaded() -> bool {
-    if let Ok(modules) = fs::read_to_string("/proc/modules") {
-        return modules.lines().any(|line| line.starts_with("vfio_pci"));
+/// Read the running kernel's release and VFIO-relevant build options
+/// The build config comes from /boot/config-<release> or, failing that, /proc/config.gz
+pub fn get_kernel_info() -> KernelInfo {
+    let release = nix::sys::utsname::uname()
+        .map(|u| u.release().to_string_lossy().to_string())
+        .unwrap_or_else(|_| "unknown".to_string());
+
+    let boot_config = format!("/boot/config-{}", release);
+    let config = fs::read_to_string(&boot_config)
+        .ok()
+        .map(|c| (boot_config, c))
+        .or_else(|| read_proc_config().map(|c| ("/proc/config.gz".to_string(), c)));
+
+    let (config_source, options) = match config {
+        Some((source, content)) => (Some(source), parse_kernel_config(&content, KERNEL_CONFIG_OPTIONS)),
+        None => (None, KERNEL_CONFIG_OPTIONS.iter().map(|n| (*n, None)).collect()),
+    };
+
+    KernelInfo { release, config_source, options }
+}
+
+/// Decompress /proc/config.gz (CONFIG_IKCONFIG_PROC) with gzip
+fn read_proc_config() -> Option<String> {
+    if !Path::new("/proc/config.gz").exists() {
+        return None;
     }
-    false
+
+    let output = std::process::Command::new("gzip")
+        .args(["-dc", "/proc/config.gz"])
+        .output()
+        .ok()?;
+
+    output.status.success()
+        .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Look up options in kernel config text
+/// "CONFIG_X=y" gives Some("y"); "# CONFIG_X is not set" or no line gives None
+fn parse_kernel_config(content: &str, names: &[&'static str]) -> Vec<(&'static str, Option<String>)> {
+    names
+        .iter()
+        .map(|name| {
+            let value = content
+                .lines()
+                .filter_map(|line| line.split_once('='))
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.trim().trim_matches('"').to_string());
+            (*name, value)
+        })
+        .collect()
+}
+
+/// Check if a module is loaded or built in (both appear under /sys/module)
+/// Built-in modules without parameters have no /sys/module entry, so vfio_pci
+/// also counts as present when its driver is registered
+fn is_module_present(name: &str) -> bool {
+    Path::new("/sys/module").join(name).exists()
+        || (name == "vfio_pci" && crate::vfio::is_vfio_pci_registered())
+}
+
+/// Load state of each module in the VFIO stack
+pub fn get_vfio_modules() -> Vec<ModuleState> {
+    [
+        ("vfio", "VFIO core"),
+        ("vfio_iommu_type1", "IOMMU backend (type1)"),
+        ("iommufd", "IOMMU backend (iommufd, alternative to type1)"),
+        ("vfio_pci", "PCI device driver"),
+        ("vfio_virqfd", "interrupt eventfds (merged into vfio on kernel 6.2+)"),
+    ]
+    .into_iter()
+    .map(|(name, role)| ModuleState { name, loaded: is_module_present(name), role })
+    .collect()
+}
+
+/// Required modules are loaded: vfio, vfio_pci and one IOMMU backend
+/// (vfio_virqfd is not required: newer kernels build it into vfio)
+pub fn vfio_stack_loaded(modules: &[ModuleState]) -> bool {
+    let loaded = |name: &str| modules.iter().any(|m| m.name == name && m.loaded);
+    loaded("vfio") && loaded("vfio_pci") && (loaded("vfio_iommu_type1") || loaded("iommufd"))
 }
 
 /// Count IOMMU groups
@@ -150,8 +598,179 @@ fn count_vfio_devices() -> usize {
         .unwrap_or(0)
 }
 
+const VFIO_CONTAINER: &str = "/dev/vfio/vfio";
+
+/// VFIO API version expected by the kernel interface (linux/vfio.h)
+pub const VFIO_API_VERSION: i32 = 0;
+
+/// VFIO_TYPE1_IOMMU extension ID
+const VFIO_TYPE1_IOMMU: i32 = 1;
+
+// VFIO ioctls: _IO(VFIO_TYPE, VFIO_BASE + n) with VFIO_TYPE = ';' and VFIO_BASE = 100
+nix::ioctl_none!(vfio_get_api_version, b';', 100);
+nix::ioctl_write_int_bad!(vfio_check_extension, nix::request_code_none!(b';', 101));
+
+nix::ioctl_readwrite_bad!(vfio_group_get_status, nix::request_code_none!(b';', 103), VfioGroupStatus);
+
+/// struct vfio_group_status (linux/vfio.h)
+#[repr(C)]
+pub struct VfioGroupStatus {
+    argsz: u32,
+    flags: u32,
+}
+
+const VFIO_GROUP_FLAGS_VIABLE: u32 = 1 << 0;
+const VFIO_GROUP_FLAGS_CONTAINER_SET: u32 = 1 << 1;
+
+/// Result of probing a VFIO group node
+#[derive(Debug, Clone)]
+pub struct GroupProbe {
+    pub viable: bool,
+    pub container_set: bool,
+}
+
+/// Open /dev/vfio/<group> and query VFIO_GROUP_GET_STATUS
+/// The container must be openable as well, so it is opened first (as userspace drivers do)
+pub fn probe_group(group_id: u32) -> Result<GroupProbe> {
+    use std::os::fd::AsRawFd;
+
+    probe_container()?;
+
+    let group_path = format!("/dev/vfio/{}", group_id);
+    let file = fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(&group_path)
+        .map_err(|e| match e.raw_os_error() {
+            Some(16) => anyhow::anyhow!(
+                "{} is busy: already opened by another process (e.g. a running DPDK application)", group_path),
+            _ => match e.kind() {
+                std::io::ErrorKind::NotFound => anyhow::anyhow!(
+                    "{} does not exist. Is the device bound to vfio-pci?", group_path),
+                std::io::ErrorKind::PermissionDenied => anyhow::anyhow!(
+                    "Permission denied opening {}. Run as root or set permissions.", group_path),
+                _ => anyhow::anyhow!("Failed to open {}: {}", group_path, e),
+            },
+        })?;
+
+    let mut status = VfioGroupStatus {
+        argsz: std::mem::size_of::<VfioGroupStatus>() as u32,
+        flags: 0,
+    };
+
+    // SAFETY: fd is valid for the lifetime of `file`; status is a properly sized vfio_group_status
+    unsafe { vfio_group_get_status(file.as_raw_fd(), &mut status) }
+        .map_err(|e| anyhow::anyhow!("VFIO_GROUP_GET_STATUS ioctl failed: {}", e))?;
+
+    Ok(GroupProbe {
+        viable: status.flags & VFIO_GROUP_FLAGS_VIABLE != 0,
+        container_set: status.flags & VFIO_GROUP_FLAGS_CONTAINER_SET != 0,
+    })
+}
+
+/// Result of probing the VFIO container node
+#[derive(Debug, Clone)]
+pub struct ContainerProbe {
+    pub api_version: i32,
+    pub type1_supported: bool,
+}
+
+/// Open /dev/vfio/vfio and query the API version, as VFIO userspace (e.g. DPDK) does on startup
+pub fn probe_container() -> Result<ContainerProbe> {
+    use std::os::fd::AsRawFd;
+
+    let file = fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(VFIO_CONTAINER)
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => anyhow::anyhow!(
+                "{} does not exist. Is the vfio module loaded?", VFIO_CONTAINER),
+            std::io::ErrorKind::PermissionDenied => anyhow::anyhow!(
+                "Permission denied opening {}. Run as root or set permissions (options.set_permissions).", VFIO_CONTAINER),
+            _ => anyhow::anyhow!("Failed to open {}: {}", VFIO_CONTAINER, e),
+        })?;
+
+    let fd = file.as_raw_fd();
+
+    // SAFETY: fd is a valid open file descriptor for the lifetime of `file`
+    let api_version = unsafe { vfio_get_api_version(fd) }
+        .map_err(|e| anyhow::anyhow!("VFIO_GET_API_VERSION ioctl failed: {}", e))?;
+
+    // SAFETY: as above; VFIO_CHECK_EXTENSION takes the extension ID by value
+    let type1_supported = unsafe { vfio_check_extension(fd, VFIO_TYPE1_IOMMU) }
+        .map(|r| r > 0)
+        .unwrap_or(false);
+
+    Ok(ContainerProbe {
+        api_version,
+        type1_supported,
+    })
+}
+
 /// Check if /dev/vfio/vfio exists
 #[allow(dead_code)]
 pub fn is_vfio_available() -> bool {
     Path::new("/dev/vfio/vfio").exists()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group_with_drivers(drivers: &[Option<&str>]) -> IommuGroup {
+        IommuGroup {
+            id: 7,
+            devices: drivers
+                .iter()
+                .enumerate()
+                .map(|(i, driver)| GroupDevice {
+                    pci_address: format!("0000:03:00.{}", i),
+                    class: "0x020000".to_string(),
+                    vendor_device: "0x8086:0x1521".to_string(),
+                    driver: driver.map(str::to_string),
+                    is_endpoint: true,
+                    numa_node: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn group_viability_with_mixed_drivers() {
+        assert!(group_with_drivers(&[Some("vfio-pci"), Some("vfio-pci")]).is_viable());
+        assert!(group_with_drivers(&[Some("vfio-pci"), None]).is_viable());
+        assert!(group_with_drivers(&[Some("mlx5_vfio_pci"), Some("pcieport")]).is_viable());
+        assert!(!group_with_drivers(&[Some("vfio-pci"), Some("igb")]).is_viable());
+        assert!(!group_with_drivers(&[None, Some("ixgbe")]).is_viable());
+    }
+
+    #[test]
+    fn group_numa_span() {
+        let mut group = group_with_drivers(&[Some("vfio-pci"), Some("vfio-pci"), None]);
+        assert!(!group.spans_numa_nodes());
+
+        group.devices[0].numa_node = Some(0);
+        group.devices[1].numa_node = Some(0);
+        assert_eq!(group.numa_nodes(), vec![0]);
+        assert!(!group.spans_numa_nodes());
+
+        group.devices[2].numa_node = Some(1);
+        assert_eq!(group.numa_nodes(), vec![0, 1]);
+        assert!(group.spans_numa_nodes());
+    }
+
+    #[test]
+    fn kernel_config_parsing() {
+        let config = "CONFIG_VFIO=m\n\
+            CONFIG_VFIO_PCI=y\n\
+            # CONFIG_IOMMU_DEFAULT_PASSTHROUGH is not set\n\
+            CONFIG_VFIO_PCI_CORE=m\n";
+
+        let options = parse_kernel_config(config, KERNEL_CONFIG_OPTIONS);
+        assert_eq!(options[0], ("CONFIG_VFIO", Some("m".to_string())));
+        assert_eq!(options[1], ("CONFIG_VFIO_PCI", Some("y".to_string())));
+        assert_eq!(options[2], ("CONFIG_VFIO_IOMMU_TYPE1", None));
+        assert_eq!(options[4], ("CONFIG_IOMMU_DEFAULT_PASSTHROUGH", None));
+    }
+}
@@ -5,9 +5,18 @@ pub mod cli;
 pub mod config;
 pub mod device;
 pub mod grub;
+pub mod lock;
+pub mod prompt;
 pub mod iommu;
 pub mod systemd;
 pub mod vfio;
 pub mod display;
 pub mod error;
 pub mod frameworks;
+pub mod snapshot;
+pub mod watch;
+pub mod diff;
+pub mod recipe;
+pub mod quiet;
+pub mod exit;
+pub mod drift;
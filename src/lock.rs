@@ -0,0 +1,44 @@
+use std::fs::{File, OpenOptions, TryLockError};
+use anyhow::{Result, Context};
+use colored::Colorize;
+
+const LOCK_FILE: &str = "/run/vfio-tool.lock";
+
+/// Exclusive lock held for the duration of a mutating operation
+/// The lock is released when this value is dropped (or the process exits)
+pub struct OperationLock {
+    _file: File,
+}
+
+/// Acquire the exclusive operation lock
+/// If `wait` is false, fails immediately when another invocation holds the lock
+pub fn acquire(wait: bool) -> Result<OperationLock> {
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(LOCK_FILE)
+        .context(format!("Failed to open lock file {}", LOCK_FILE))?;
+
+    match file.try_lock() {
+        Ok(()) => {}
+        Err(TryLockError::WouldBlock) => {
+            if !wait {
+                anyhow::bail!(
+                    "Another vfio-tool operation is in progress (lock held on {}).\n\
+                    Wait for it to finish or re-run with --wait.",
+                    LOCK_FILE
+                );
+            }
+
+            println!("{}", "Waiting for another vfio-tool operation to finish...".bright_yellow());
+            file.lock()
+                .context(format!("Failed to acquire lock on {}", LOCK_FILE))?;
+        }
+        Err(TryLockError::Error(e)) => {
+            return Err(e).context(format!("Failed to acquire lock on {}", LOCK_FILE));
+        }
+    }
+
+    Ok(OperationLock { _file: file })
+}
@@ -2,12 +2,21 @@ mod cli;
 mod config;
 mod device;
 mod grub;
+mod lock;
+mod prompt;
 mod iommu;
 mod systemd;
 mod vfio;
 mod display;
 mod error;
 mod frameworks;
+mod snapshot;
+mod watch;
+mod diff;
+mod recipe;
+mod quiet;
+mod exit;
+mod drift;
 
 use clap::Parser;
 use anyhow::Result;
@@ -0,0 +1,40 @@
+use std::io;
+use std::os::fd::AsFd;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use anyhow::Result;
+use nix::sys::termios::{self, SetArg};
+
+/// Run an interactive prompt, cancelling it if no answer arrives within `timeout`
+/// On timeout the terminal mode and cursor are restored and an error is returned.
+/// The prompt thread stays blocked on stdin until the process exits.
+pub fn with_timeout<T, F>(timeout: Option<Duration>, prompt: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> dialoguer::Result<T> + Send + 'static,
+{
+    let Some(timeout) = timeout else {
+        return Ok(prompt()?);
+    };
+
+    // dialoguer switches the terminal to raw mode while waiting for a key
+    let saved = termios::tcgetattr(io::stdin().as_fd()).ok();
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = tx.send(prompt());
+    });
+
+    match rx.recv_timeout(timeout) {
+        Ok(result) => Ok(result?),
+        Err(_) => {
+            if let Some(saved) = saved {
+                let _ = termios::tcsetattr(io::stdin().as_fd(), SetArg::TCSANOW, &saved);
+            }
+            // Show the cursor again (dialoguer hides it during selection)
+            eprintln!("\x1b[?25h");
+            anyhow::bail!("No input within {}s - cancelled", timeout.as_secs())
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Set in the re-executed child so it runs the command normally
+const CHILD_ENV: &str = "VFIO_TOOL_QUIET_CHILD";
+
+/// Whether this process is the child of a `--quiet-success` run
+pub fn is_child() -> bool {
+    std::env::var_os(CHILD_ENV).is_some()
+}
+
+/// Warning lines survive a successful quiet run
+fn is_warning(line: &str) -> bool {
+    line.contains('⚠') || line.contains("Warning")
+}
+
+/// Run this invocation again with stdout captured, then exit with its status
+/// On success only warning lines are printed (to stderr); on failure the full output is.
+/// Re-executing instead of capturing in-process also covers commands that call
+/// `std::process::exit` directly. Errors and the child's stderr pass through unchanged.
+/// With `notify`, this process (systemd's main PID) sends the readiness notification
+/// once the child succeeds; the child itself is not allowed to.
+pub fn run_captured(notify: bool) -> ! {
+    let output = std::env::current_exe().and_then(|exe| {
+        let mut child = Command::new(exe);
+        if notify {
+            child.env_remove("NOTIFY_SOCKET");
+        }
+        child
+            .args(std::env::args_os().skip(1))
+            .env(CHILD_ENV, "1")
+            .stdin(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .output()
+    });
+
+    let output = match output {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Error: failed to run vfio-tool: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut stderr = std::io::stderr();
+
+    if output.status.success() {
+        for line in stdout.lines().filter(|l| is_warning(l)) {
+            let _ = writeln!(stderr, "{}", line);
+        }
+        if notify && let Err(e) = crate::systemd::notify_ready() {
+            let _ = writeln!(stderr, "Error: {:#}", e);
+            std::process::exit(1);
+        }
+    } else {
+        let _ = stderr.write_all(stdout.as_bytes());
+    }
+
+    // Killed by a signal: no exit code, report failure
+    std::process::exit(output.status.code().unwrap_or(1));
+}
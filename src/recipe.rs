@@ -0,0 +1,217 @@
+use std::fs;
+use anyhow::{Result, Context};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::device::{self, DeviceStatus, NetworkDevice};
+use crate::vfio;
+
+/// Mode a recipe puts a device in
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecipeMode {
+    Vfio,
+    Kernel,
+}
+
+/// A device in a recipe, identified by hardware rather than interface name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecipeDevice {
+    pub vendor_id: String,
+    pub device_id: String,
+    /// PCI address on the machine the recipe was exported from
+    pub slot: String,
+    /// Position among devices with the same vendor:device, ordered by PCI address
+    pub index: usize,
+    pub mode: RecipeMode,
+}
+
+/// A portable binding setup for identical machines
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Recipe {
+    #[serde(default)]
+    pub description: String,
+
+    #[serde(default = "default_true")]
+    pub set_permissions: bool,
+
+    #[serde(default, rename = "device")]
+    pub devices: Vec<RecipeDevice>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Devices with the given vendor:device, in PCI address order
+fn same_model<'a>(devices: &'a [NetworkDevice], vendor_id: &str, device_id: &str) -> Vec<&'a NetworkDevice> {
+    let mut matches: Vec<&NetworkDevice> = devices
+        .iter()
+        .filter(|d| d.vendor_id == vendor_id && d.device_id == device_id)
+        .collect();
+    matches.sort_by(|a, b| a.pci_address.cmp(&b.pci_address));
+    matches
+}
+
+/// Build a recipe from the config's device lists
+pub fn from_config(config: &Config, description: &str) -> Result<Recipe> {
+    let devices = device::list_all_network_devices()?;
+
+    let configured = config.devices.vfio.iter().map(|i| (i, RecipeMode::Vfio))
+        .chain(config.devices.kernel.iter().map(|i| (i, RecipeMode::Kernel)));
+
+    let mut entries = Vec::new();
+    for (interface, mode) in configured {
+        let found = devices.iter().find(|d| {
+            d.interface == *interface
+                || config.devices.pci_mappings.get(interface) == Some(&d.pci_address)
+        });
+        let Some(dev) = found else {
+            println!("  {} Skipping {} - device not present", "⚠".bright_yellow(), interface);
+            continue;
+        };
+
+        let index = same_model(&devices, &dev.vendor_id, &dev.device_id)
+            .iter()
+            .position(|d| d.pci_address == dev.pci_address)
+            .unwrap_or_default();
+
+        entries.push(RecipeDevice {
+            vendor_id: dev.vendor_id.clone(),
+            device_id: dev.device_id.clone(),
+            slot: dev.pci_address.clone(),
+            index,
+            mode,
+        });
+    }
+
+    entries.sort_by(|a, b| a.slot.cmp(&b.slot));
+
+    Ok(Recipe {
+        description: description.to_string(),
+        set_permissions: config.options.set_permissions,
+        devices: entries,
+    })
+}
+
+/// Write a recipe as TOML
+pub fn save_recipe(path: &str, recipe: &Recipe) -> Result<()> {
+    let toml = toml::to_string_pretty(recipe)
+        .context("Failed to serialize recipe")?;
+
+    fs::write(path, toml)
+        .context(format!("Failed to write recipe file {}", path))?;
+
+    Ok(())
+}
+
+/// Read a recipe from a TOML file
+pub fn load_recipe(path: &str) -> Result<Recipe> {
+    let content = fs::read_to_string(path)
+        .context(format!("Failed to read recipe file {}", path))?;
+
+    toml::from_str(&content)
+        .context(format!("Failed to parse recipe file {}", path))
+}
+
+/// Find the local device for a recipe entry: same slot and model first, else the
+/// device at the same position among local devices of that model
+fn match_device<'a>(entry: &RecipeDevice, devices: &'a [NetworkDevice]) -> Option<&'a NetworkDevice> {
+    let candidates = same_model(devices, &entry.vendor_id, &entry.device_id);
+
+    candidates
+        .iter()
+        .find(|d| d.pci_address == entry.slot)
+        .or_else(|| candidates.get(entry.index))
+        .copied()
+}
+
+/// Match a recipe against local hardware and bind/unbind the matched devices
+/// Nothing is changed unless every recipe device has its own local match and, without
+/// `force`, none of the devices to bind fails the bind safety checks
+pub fn apply_recipe(recipe: &Recipe, dry_run: bool, force: bool) -> Result<()> {
+    if !recipe.description.is_empty() {
+        println!("{} {}", "Recipe:".bright_cyan(), recipe.description);
+    }
+    println!();
+
+    let devices = device::list_all_network_devices()?;
+
+    let mut to_vfio = Vec::new();
+    let mut to_kernel = Vec::new();
+    let mut unmatched = 0;
+    let mut matched: Vec<&str> = Vec::new();
+    let mut duplicates = 0;
+
+    for entry in &recipe.devices {
+        let model = format!("{}:{}", entry.vendor_id, entry.device_id);
+        let Some(dev) = match_device(entry, &devices) else {
+            println!("  {} {} #{} (slot {}) - {}", "✗".bright_red(), model, entry.index, entry.slot, "no matching device".bright_red());
+            unmatched += 1;
+            continue;
+        };
+
+        // A slot match for one entry can be another entry's index match
+        if matched.contains(&dev.pci_address.as_str()) {
+            println!("  {} {} #{} (slot {}) - {}", "✗".bright_red(), model, entry.index, entry.slot,
+                format!("resolves to {}, already matched by another entry", dev.pci_address).bright_red());
+            duplicates += 1;
+            continue;
+        }
+        matched.push(&dev.pci_address);
+
+        let (wanted, in_mode) = match entry.mode {
+            RecipeMode::Vfio => ("vfio", dev.status == DeviceStatus::Vfio),
+            RecipeMode::Kernel => ("kernel", dev.status == DeviceStatus::Kernel),
+        };
+        let mark = if in_mode { "✓".bright_green() } else { "~".bright_yellow() };
+        println!("  {} {} #{} → {} ({}) - {}", mark, model, entry.index, dev.pci_address, dev.interface, wanted);
+
+        if !in_mode {
+            match entry.mode {
+                RecipeMode::Vfio => to_vfio.push(dev.pci_address.clone()),
+                RecipeMode::Kernel => to_kernel.push(dev.pci_address.clone()),
+            }
+        }
+    }
+
+    println!();
+
+    if unmatched > 0 {
+        anyhow::bail!("{} recipe device(s) have no match on this machine; nothing was changed", unmatched);
+    }
+    if duplicates > 0 {
+        anyhow::bail!("{} recipe device(s) resolve to a device another entry already matched; nothing was changed", duplicates);
+    }
+
+    // A recipe from another host may match this host's uplink NIC or console GPU
+    if !force {
+        vfio::check_bind_safety(&to_vfio)?;
+    }
+
+    if to_vfio.is_empty() && to_kernel.is_empty() {
+        println!("{}", "✓ All recipe devices are already in their recipe mode".bright_green());
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("{}", "Dry run - no changes made".bright_yellow());
+        println!("  {} to bind, {} to return to kernel", to_vfio.len(), to_kernel.len());
+        return Ok(());
+    }
+
+    if !to_kernel.is_empty() {
+        let refs: Vec<&str> = to_kernel.iter().map(String::as_str).collect();
+        vfio::unbind_interfaces(&refs, true)?;
+    }
+    if !to_vfio.is_empty() {
+        let refs: Vec<&str> = to_vfio.iter().map(String::as_str).collect();
+        vfio::ensure_vfio(&refs, force)?;
+        if recipe.set_permissions {
+            vfio::set_vfio_permissions()?;
+        }
+    }
+
+    Ok(())
+}
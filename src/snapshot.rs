@@ -0,0 +1,150 @@
+use std::fs;
+use anyhow::{Result, Context};
+use serde::{Deserialize, Serialize};
+
+use crate::device::NetworkDevice;
+
+/// Fingerprint of a single network device
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceFingerprint {
+    pub pci_address: String,
+    pub vendor_id: String,
+    pub device_id: String,
+    #[serde(default)]
+    pub interface: String,
+}
+
+impl DeviceFingerprint {
+    pub fn vendor_device(&self) -> String {
+        format!("{}:{}", self.vendor_id, self.device_id)
+    }
+}
+
+/// Saved device inventory used for hardware-change detection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub devices: Vec<DeviceFingerprint>,
+}
+
+impl Snapshot {
+    /// Build a snapshot from the current device list
+    pub fn from_devices(devices: &[NetworkDevice]) -> Self {
+        let mut fingerprints: Vec<DeviceFingerprint> = devices
+            .iter()
+            .map(|d| DeviceFingerprint {
+                pci_address: d.pci_address.clone(),
+                vendor_id: d.vendor_id.clone(),
+                device_id: d.device_id.clone(),
+                interface: d.interface.clone(),
+            })
+            .collect();
+
+        fingerprints.sort_by(|a, b| a.pci_address.cmp(&b.pci_address));
+
+        Snapshot { devices: fingerprints }
+    }
+}
+
+/// A single difference between two snapshots
+#[derive(Debug, Clone)]
+pub enum InventoryChange {
+    /// Device present now but not in the snapshot
+    Added(DeviceFingerprint),
+    /// Device in the snapshot but no longer present
+    Removed(DeviceFingerprint),
+    /// Same PCI address, different vendor:device (card replaced)
+    Changed { old: DeviceFingerprint, new: DeviceFingerprint },
+}
+
+/// Write a snapshot to a file
+pub fn save_snapshot(path: &str, snapshot: &Snapshot) -> Result<()> {
+    let json = serde_json::to_string_pretty(snapshot)
+        .context("Failed to serialize snapshot")?;
+
+    fs::write(path, json + "\n")
+        .context(format!("Failed to write snapshot file {}", path))?;
+
+    Ok(())
+}
+
+/// Read a snapshot from a file
+pub fn load_snapshot(path: &str) -> Result<Snapshot> {
+    let content = fs::read_to_string(path)
+        .context(format!("Failed to read snapshot file {}", path))?;
+
+    let snapshot: Snapshot = serde_json::from_str(&content)
+        .context(format!("Failed to parse snapshot file {}", path))?;
+
+    Ok(snapshot)
+}
+
+/// Compare two snapshots, keyed by PCI address
+pub fn compare(old: &Snapshot, new: &Snapshot) -> Vec<InventoryChange> {
+    let mut changes = Vec::new();
+
+    for old_dev in &old.devices {
+        match new.devices.iter().find(|d| d.pci_address == old_dev.pci_address) {
+            Some(new_dev) => {
+                if new_dev.vendor_id != old_dev.vendor_id || new_dev.device_id != old_dev.device_id {
+                    changes.push(InventoryChange::Changed {
+                        old: old_dev.clone(),
+                        new: new_dev.clone(),
+                    });
+                }
+            }
+            None => changes.push(InventoryChange::Removed(old_dev.clone())),
+        }
+    }
+
+    for new_dev in &new.devices {
+        if !old.devices.iter().any(|d| d.pci_address == new_dev.pci_address) {
+            changes.push(InventoryChange::Added(new_dev.clone()));
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fingerprint(pci: &str, vendor: &str, device: &str) -> DeviceFingerprint {
+        DeviceFingerprint {
+            pci_address: pci.to_string(),
+            vendor_id: vendor.to_string(),
+            device_id: device.to_string(),
+            interface: String::new(),
+        }
+    }
+
+    #[test]
+    fn compare_reports_added_removed_and_replaced_cards() {
+        let old = Snapshot { devices: vec![
+            fingerprint("0000:01:00.0", "8086", "10fb"),
+            fingerprint("0000:02:00.0", "8086", "1521"),
+            fingerprint("0000:03:00.0", "15b3", "1017"),
+        ]};
+        let new = Snapshot { devices: vec![
+            fingerprint("0000:01:00.0", "8086", "10fb"),
+            fingerprint("0000:03:00.0", "15b3", "101b"),
+            fingerprint("0000:04:00.0", "14e4", "16d7"),
+        ]};
+
+        let changes = compare(&old, &new);
+        assert_eq!(changes.len(), 3);
+        assert!(matches!(&changes[0], InventoryChange::Removed(d) if d.pci_address == "0000:02:00.0"));
+        assert!(matches!(&changes[1], InventoryChange::Changed { old, new }
+            if old.device_id == "1017" && new.device_id == "101b"));
+        assert!(matches!(&changes[2], InventoryChange::Added(d) if d.pci_address == "0000:04:00.0"));
+    }
+
+    #[test]
+    fn compare_ignores_interface_renames() {
+        let old = Snapshot { devices: vec![fingerprint("0000:01:00.0", "8086", "10fb")] };
+        let mut new = old.clone();
+        new.devices[0].interface = "enp1s0f0".to_string();
+
+        assert!(compare(&old, &new).is_empty());
+    }
+}
@@ -1,13 +1,21 @@
 use std::fs;
+use std::io;
+use std::path::Path;
 use std::process::Command;
+use std::thread;
+use std::time::Duration;
 use anyhow::{Result, Context};
 use colored::Colorize;
 use dialoguer::Confirm;
+use nix::errno::Errno;
 
 use crate::config::Config;
 
 const SERVICE_FILE: &str = "/etc/systemd/system/vfio-tool.service";
-const SERVICE_BINARY: &str = "/usr/local/bin/vfio-tool";
+pub const SERVICE_BINARY: &str = "/usr/local/bin/vfio-tool";
+
+/// Attempts made to install the binary when the failure looks transient
+const INSTALL_ATTEMPTS: u32 = 3;
 
 /// Detect existing VFIO-related systemd services
 fn detect_vfio_services() -> Result<Vec<String>> {
@@ -69,11 +77,93 @@ fn cleanup_service(service_name: &str) -> Result<()> {
     Ok(())
 }
 
-/// Install systemd service
-pub fn install_service() -> Result<()> {
+/// Explain an I/O failure while installing into `dir`
+fn describe_install_error(e: &io::Error, dir: &Path) -> String {
+    match e.raw_os_error().map(Errno::from_raw) {
+        Some(Errno::EROFS) => format!("{} is on a read-only filesystem (use --install-path to install elsewhere)", dir.display()),
+        Some(Errno::ENOSPC) | Some(Errno::EDQUOT) => format!("No space left on the filesystem holding {}", dir.display()),
+        Some(Errno::EACCES) | Some(Errno::EPERM) => format!("Permission denied writing to {} (directory may be immutable)", dir.display()),
+        Some(Errno::ENOENT) => format!("Directory {} does not exist", dir.display()),
+        _ => format!("Failed to write to {}: {}", dir.display(), e),
+    }
+}
+
+/// Errors worth retrying (binary briefly busy, interrupted syscall)
+fn is_transient(e: &io::Error) -> bool {
+    matches!(
+        e.raw_os_error().map(Errno::from_raw),
+        Some(Errno::ETXTBSY) | Some(Errno::EBUSY) | Some(Errno::EAGAIN) | Some(Errno::EINTR)
+    )
+}
+
+/// Check that the binary's target directory exists and is writable
+fn check_install_dir(target: &Path) -> Result<()> {
+    let dir = target.parent().unwrap_or(Path::new("/"));
+    let probe = dir.join(format!(".vfio-tool.probe.{}", std::process::id()));
+
+    fs::File::create(&probe)
+        .map_err(|e| anyhow::anyhow!(describe_install_error(&e, dir)))?;
+    let _ = fs::remove_file(&probe);
+
+    Ok(())
+}
+
+/// Copy `source` to `target` atomically: write a temp file in the same directory,
+/// make it executable, then rename it over the target
+fn copy_binary_atomic(source: &Path, target: &Path) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = target.parent().unwrap_or(Path::new("/"));
+    let tmp = dir.join(format!(".vfio-tool.tmp.{}", std::process::id()));
+
+    let result = (|| {
+        fs::copy(source, &tmp)?;
+        // 0o755 = rwxr-xr-x
+        fs::set_permissions(&tmp, fs::Permissions::from_mode(0o755))?;
+        fs::File::open(&tmp)?.sync_all()?;
+        fs::rename(&tmp, target)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+
+    result
+}
+
+/// Install the running binary to `target`, retrying transient failures
+fn install_binary(target: &Path) -> Result<()> {
+    let current_exe = std::env::current_exe()
+        .context("Failed to get current executable path")?;
+    let dir = target.parent().unwrap_or(Path::new("/"));
+
+    let mut attempt = 1;
+    loop {
+        match copy_binary_atomic(&current_exe, target) {
+            Ok(()) => return Ok(()),
+            Err(e) if is_transient(&e) && attempt < INSTALL_ATTEMPTS => {
+                println!("  {} Install attempt {} failed ({}), retrying...", "⚠".bright_yellow(), attempt, e);
+                thread::sleep(Duration::from_millis(500));
+                attempt += 1;
+            }
+            Err(e) => {
+                anyhow::bail!("Failed to install binary to {}: {}", target.display(), describe_install_error(&e, dir));
+            }
+        }
+    }
+}
+
+/// Install systemd service, with the binary at `binary_path`
+pub fn install_service(binary_path: &str) -> Result<()> {
     println!("{}", "Installing VFIO systemd service...".bright_cyan());
     println!();
 
+    // Fail early (before any testing/prompts) if the binary can't be installed
+    let binary = Path::new(binary_path);
+    if !binary.exists() {
+        check_install_dir(binary)?;
+    }
+
     // Step 1: Validate configuration exists
     println!("{}", "Step 1: Validating configuration...".bright_cyan());
     let cfg = match crate::config::load_config() {
@@ -119,6 +209,8 @@ pub fn install_service() -> Result<()> {
     println!("  Options:");
     println!("    - Set permissions: {}", cfg.options.set_permissions);
     println!("    - Auto-load module: {}", cfg.options.auto_load_module);
+    println!("    - Enforce kernel list: {}", cfg.options.enforce_kernel_list);
+    println!("    - Allow absent: {}", cfg.options.allow_absent);
     println!();
 
     // Step 2: Offer to test configuration first
@@ -138,7 +230,7 @@ pub fn install_service() -> Result<()> {
         println!("{}", "Applying configuration for testing...".bright_cyan());
 
         use crate::vfio;
-        match vfio::apply_config(&cfg) {
+        match vfio::apply_config(&cfg, false) {
             Ok(()) => {
                 println!();
                 println!("{}", "✓ Configuration applied successfully!".bright_green().bold());
@@ -236,29 +328,81 @@ pub fn install_service() -> Result<()> {
         println!();
     }
 
-    // Check if binary is installed
-    if !std::path::Path::new(SERVICE_BINARY).exists() {
-        println!("{}", "Installing vfio-tool binary...".bright_cyan());
+    write_service(binary_path)?;
+    print_service_installed();
 
-        // Get current executable path
-        let current_exe = std::env::current_exe()
-            .context("Failed to get current executable path")?;
+    Ok(())
+}
+
+/// Outcome of a non-interactive install, for scripts to branch on
+#[derive(Debug, Clone, PartialEq)]
+pub enum InstallResult {
+    /// Service file written and enabled
+    Installed,
+    /// Service already installed with the same unit file
+    AlreadyInstalled,
+    /// No valid configuration to make persistent
+    ConfigMissing,
+    /// Applying the configuration failed (error message)
+    TestFailed(String),
+}
+
+/// Outcome of uninstalling the service
+#[derive(Debug, Clone, PartialEq)]
+pub enum UninstallResult {
+    Uninstalled,
+    NotInstalled,
+}
+
+/// Install the systemd service without prompts
+/// The configuration is applied first (when `test` is set) and the service is only
+/// installed if that succeeds. Conflicting VFIO services are reported but left alone.
+pub fn install(binary_path: &str, test: bool) -> Result<InstallResult> {
+    let cfg = match crate::config::load_config() {
+        Ok(cfg) => cfg,
+        Err(_) => return Ok(InstallResult::ConfigMissing),
+    };
+
+    let service_content = generate_service_file(binary_path);
+    let installed = fs::read_to_string(SERVICE_FILE).is_ok_and(|c| c == service_content);
+    if installed && Path::new(binary_path).exists() {
+        return Ok(InstallResult::AlreadyInstalled);
+    }
+
+    let binary = Path::new(binary_path);
+    if !binary.exists() {
+        check_install_dir(binary)?;
+    }
+
+    if test {
+        if let Err(e) = crate::vfio::apply_config(&cfg, false) {
+            return Ok(InstallResult::TestFailed(e.to_string()));
+        }
+        println!();
+    }
+
+    for service in detect_vfio_services()? {
+        println!("  {} Existing service {} may conflict with vfio-tool", "⚠".bright_yellow(), service.bright_yellow());
+    }
 
-        // Copy to /usr/local/bin
-        fs::copy(&current_exe, SERVICE_BINARY)
-            .context("Failed to copy binary. Try running with sudo.")?;
+    write_service(binary_path)?;
 
-        // Make executable (0o755 = rwxr-xr-x)
-        use std::os::unix::fs::PermissionsExt;
-        let permissions = fs::Permissions::from_mode(0o755);
-        fs::set_permissions(SERVICE_BINARY, permissions)
-            .context("Failed to set executable permissions on binary")?;
+    Ok(InstallResult::Installed)
+}
+
+/// Install the binary (if missing), write the unit file, reload systemd and enable the service
+fn write_service(binary_path: &str) -> Result<()> {
+    let binary = Path::new(binary_path);
 
-        println!("  ✓ Binary installed to {}", SERVICE_BINARY);
+    // Check if binary is installed
+    if !binary.exists() {
+        println!("{}", "Installing vfio-tool binary...".bright_cyan());
+        install_binary(binary)?;
+        println!("  ✓ Binary installed to {}", binary.display());
     }
 
     // Generate service file
-    let service_content = generate_service_file();
+    let service_content = generate_service_file(binary_path);
 
     // Write service file
     fs::write(SERVICE_FILE, service_content)
@@ -282,6 +426,11 @@ pub fn install_service() -> Result<()> {
 
     println!("  ✓ Service enabled (will run on boot)");
 
+    Ok(())
+}
+
+/// Print the post-install summary
+pub fn print_service_installed() {
     println!();
     println!("{}", "✓ VFIO systemd service installed!".bright_green());
     println!();
@@ -290,30 +439,33 @@ pub fn install_service() -> Result<()> {
     println!("  {} - Stop service", "sudo systemctl stop vfio-tool".bright_cyan());
     println!("  {} - Check status", "sudo systemctl status vfio-tool".bright_cyan());
     println!("  {} - View logs", "sudo journalctl -u vfio-tool".bright_cyan());
-
-    Ok(())
 }
 
 /// Uninstall systemd service
-pub fn uninstall_service() -> Result<()> {
-    println!("{}", "Uninstalling VFIO systemd service...".bright_cyan());
+pub fn uninstall_service() -> Result<UninstallResult> {
+    let installed = Path::new(SERVICE_FILE).exists();
+    if installed {
+        println!("{}", "Uninstalling VFIO systemd service...".bright_cyan());
+    }
 
-    // Stop service if running
-    let _ = Command::new("systemctl")
-        .args(["stop", "vfio-tool.service"])
-        .status();
+    // Stop and disable even without the unit file: a unit removed by hand can still be
+    // loaded in systemd or leave its enablement symlinks behind
+    for action in ["stop", "disable"] {
+        let _ = Command::new("systemctl")
+            .args([action, "vfio-tool.service"])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status();
+    }
 
-    // Disable service
-    let _ = Command::new("systemctl")
-        .args(["disable", "vfio-tool.service"])
-        .status();
+    if !installed {
+        return Ok(UninstallResult::NotInstalled);
+    }
 
     // Remove service file
-    if std::path::Path::new(SERVICE_FILE).exists() {
-        fs::remove_file(SERVICE_FILE)
-            .context("Failed to remove service file")?;
-        println!("  ✓ Service file removed");
-    }
+    fs::remove_file(SERVICE_FILE)
+        .context("Failed to remove service file")?;
+    println!("  ✓ Service file removed");
 
     // Reload systemd
     Command::new("systemctl")
@@ -326,11 +478,39 @@ pub fn uninstall_service() -> Result<()> {
     println!();
     println!("{}", "✓ VFIO systemd service uninstalled".bright_green());
 
+    Ok(UninstallResult::Uninstalled)
+}
+
+/// Tell systemd the service is ready (sd_notify "READY=1" on $NOTIFY_SOCKET)
+/// A no-op when not started by systemd as a Type=notify service.
+pub fn notify_ready() -> Result<()> {
+    use std::os::unix::net::{SocketAddr, UnixDatagram};
+
+    let Some(path) = std::env::var_os("NOTIFY_SOCKET") else {
+        return Ok(());
+    };
+    let path = path.to_string_lossy().to_string();
+
+    // "@name" is a socket in the abstract namespace
+    let addr = match path.strip_prefix('@') {
+        Some(name) => {
+            use std::os::linux::net::SocketAddrExt;
+            SocketAddr::from_abstract_name(name.as_bytes())
+        }
+        None => SocketAddr::from_pathname(&path),
+    }
+    .context(format!("Invalid NOTIFY_SOCKET {}", path))?;
+
+    let socket = UnixDatagram::unbound()
+        .context("Failed to create systemd notify socket")?;
+    socket.send_to_addr(b"READY=1\n", &addr)
+        .context(format!("Failed to notify systemd on {}", path))?;
+
     Ok(())
 }
 
 /// Generate systemd service file
-fn generate_service_file() -> String {
+fn generate_service_file(binary_path: &str) -> String {
     format!(
         r#"[Unit]
 Description=VFIO Device Binding for Kernel Bypass
@@ -338,8 +518,8 @@ Documentation=https://github.com/your-repo/vfio-tool
 After=network.target multi-user.target
 
 [Service]
-Type=oneshot
-ExecStart={} apply
+Type=notify
+ExecStart={} --quiet-success apply --systemd-notify
 RemainAfterExit=yes
 StandardOutput=journal
 StandardError=journal
@@ -349,13 +529,138 @@ Restart=no
 [Install]
 WantedBy=multi-user.target
 "#,
-        SERVICE_BINARY
+        binary_path
     )
 }
 
+/// Write a generated script to `path` ("-" or /dev/stdout print to stdout)
+/// Regular files are written atomically (temp file + rename) and made executable.
+/// Returns false if the script went to stdout.
+pub fn write_script(path: &str, script: &str) -> Result<bool> {
+    use std::io::Write;
+    use std::os::unix::fs::PermissionsExt;
+
+    if path == "-" || path == "/dev/stdout" {
+        io::stdout().write_all(script.as_bytes())?;
+        return Ok(false);
+    }
+
+    let target = Path::new(path);
+    let dir = match target.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let tmp = dir.join(format!(".vfio-tool-script.tmp.{}", std::process::id()));
+
+    let result = (|| {
+        fs::write(&tmp, script)?;
+        // 0o755 = rwxr-xr-x
+        fs::set_permissions(&tmp, fs::Permissions::from_mode(0o755))?;
+        fs::File::open(&tmp)?.sync_all()?;
+        fs::rename(&tmp, target)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result.context(format!("Failed to write script to {}", path))?;
+
+    Ok(true)
+}
+
+/// One operation of the generated binding script
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlanStep {
+    /// modprobe a module (failure tolerated)
+    LoadModule(&'static str),
+    /// Resolve the PCI address, unbind from the current driver, register the ID, bind to vfio-pci
+    BindInterface(String),
+    /// chmod 666 the VFIO container and group nodes
+    SetPermissions,
+}
+
+/// Operations the binding script performs for a config, in order
+pub fn binding_plan(config: &Config) -> Vec<PlanStep> {
+    let mut plan = Vec::new();
+
+    if config.options.auto_load_module {
+        plan.push(PlanStep::LoadModule("vfio-pci"));
+    }
+    for interface in &config.devices.vfio {
+        plan.push(PlanStep::BindInterface(interface.clone()));
+    }
+    if config.options.set_permissions {
+        plan.push(PlanStep::SetPermissions);
+    }
+
+    plan
+}
+
+/// The binding plan as JSON, with each interface's current PCI address, driver and ID
+/// filled in where known (null otherwise)
+pub fn generate_plan_json(config: &Config) -> serde_json::Value {
+    use serde_json::json;
+
+    let steps: Vec<serde_json::Value> = binding_plan(config)
+        .iter()
+        .map(|step| match step {
+            PlanStep::LoadModule(module) => json!({
+                "op": "load_module",
+                "module": module,
+                "optional": true,
+            }),
+            PlanStep::BindInterface(interface) => {
+                let netdev = format!("/sys/class/net/{}/device", interface);
+                let pci = fs::read_link(&netdev)
+                    .ok()
+                    .and_then(|t| t.file_name().map(|n| n.to_string_lossy().to_string()))
+                    .filter(|p| Path::new("/sys/bus/pci/devices").join(p).exists())
+                    .or_else(|| config.devices.pci_mappings.get(interface).cloned());
+                let driver = pci.as_deref().and_then(crate::device::get_driver);
+                let id = pci.as_deref()
+                    .and_then(|p| crate::device::get_vendor_device_id(p).ok())
+                    .map(|(v, d)| format!("{}:{}", v.trim_start_matches("0x"), d.trim_start_matches("0x")));
+
+                json!({
+                    "op": "bind_interface",
+                    "interface": interface,
+                    "steps": [
+                        { "op": "resolve_pci", "source": netdev, "pci": pci },
+                        { "op": "unbind", "driver": driver, "path": "/sys/bus/pci/devices/<pci>/driver/unbind", "value": "<pci>" },
+                        { "op": "register_id", "id": id, "path": "/sys/bus/pci/drivers/vfio-pci/new_id", "value": "<vendor> <device>" },
+                        { "op": "bind", "driver": "vfio-pci", "path": "/sys/bus/pci/drivers/vfio-pci/bind", "value": "<pci>" },
+                    ],
+                })
+            }
+            PlanStep::SetPermissions => json!({
+                "op": "set_permissions",
+                "paths": ["/dev/vfio/vfio", "/dev/vfio/<group>"],
+                "mode": "0666",
+            }),
+        })
+        .collect();
+
+    json!({ "steps": steps })
+}
+
+/// Interface -> PCI address as of now: live devices first, then the config's mappings
+/// Devices already on vfio-pci have no netdev, so the mappings cover those
+fn resolve_pci_addresses(config: &Config) -> Result<std::collections::HashMap<String, String>> {
+    let mut addresses = config.devices.pci_mappings.clone();
+    for dev in crate::device::list_network_devices()? {
+        if !dev.interface.starts_with('(') {
+            addresses.insert(dev.interface, dev.pci_address);
+        }
+    }
+    Ok(addresses)
+}
+
 /// Generate standalone bash script
-pub fn generate_bash_script(config: &Config) -> Result<String> {
+/// With `static_pci`, PCI addresses are resolved now and written into the script, so it
+/// still works when an interface has no netdev (e.g. already bound) when the script runs
+pub fn generate_bash_script(config: &Config, static_pci: bool) -> Result<String> {
     let mut script = String::new();
+    let addresses = if static_pci { Some(resolve_pci_addresses(config)?) } else { None };
 
     script.push_str("#!/bin/bash\n");
     script.push_str("#\n");
@@ -370,29 +675,74 @@ pub fn generate_bash_script(config: &Config) -> Result<String> {
     script.push_str("echo \"===== VFIO Device Binding =====\"\n");
     script.push_str("echo\n\n");
 
-    // Load VFIO module
-    if config.options.auto_load_module {
-        script.push_str("# Load VFIO module\n");
-        script.push_str("echo \"Loading VFIO module...\"\n");
-        script.push_str("modprobe -q vfio-pci || true\n");
-        script.push_str("echo \"✓ VFIO module loaded\"\n");
-        script.push_str("echo\n\n");
-    }
+    let plan = binding_plan(config);
+
+    for (i, step) in plan.iter().enumerate() {
+        match step {
+            PlanStep::LoadModule(module) => {
+                script.push_str("# Load VFIO module\n");
+                script.push_str("echo \"Loading VFIO module...\"\n");
+                script.push_str(&format!("modprobe -q {} || true\n", module));
+                script.push_str("echo \"✓ VFIO module loaded\"\n");
+                script.push_str("echo\n\n");
+            }
 
-    // Bind each interface
-    if !config.devices.vfio.is_empty() {
-        script.push_str("# Bind interfaces to VFIO\n");
+            PlanStep::BindInterface(interface) => {
+                if !matches!(plan[..i].last(), Some(PlanStep::BindInterface(_))) {
+                    script.push_str("# Bind interfaces to VFIO\n");
+                }
 
-        for interface in &config.devices.vfio {
-            script.push_str(&format!("echo \"Binding {}...\"\n", interface));
+                script.push_str(&format!("echo \"Binding {}...\"\n", interface));
+
+                if let Some(ref addresses) = addresses {
+                    let Some(pci) = addresses.get(interface) else {
+                        eprintln!("{} {} has no known PCI address; the script will skip it", "⚠".bright_yellow(), interface);
+                        script.push_str(&format!("# {} was not present when this script was generated\n", interface));
+                        script.push_str(&format!("echo \"  ✗ {} has no known PCI address\"\necho\n\n", interface));
+                        continue;
+                    };
+
+                    script.push_str(&format!(
+                        r#"# {} resolved at generation time
+PCI_ADDR={}
+if [ -e /sys/bus/pci/devices/$PCI_ADDR ]; then
+    if [ "$(basename "$(readlink /sys/bus/pci/devices/$PCI_ADDR/driver 2>/dev/null)")" = "vfio-pci" ]; then
+        echo "  ✓ {} ($PCI_ADDR) already bound to vfio-pci"
+    else
+        VENDOR=$(cat /sys/bus/pci/devices/$PCI_ADDR/vendor | sed 's/0x//')
+        DEVICE=$(cat /sys/bus/pci/devices/$PCI_ADDR/device | sed 's/0x//')
+
+        # Unbind from current driver
+        if [ -e /sys/bus/pci/devices/$PCI_ADDR/driver ]; then
+            echo "$PCI_ADDR" > /sys/bus/pci/devices/$PCI_ADDR/driver/unbind 2>/dev/null || true
+        fi
+
+        # Register with VFIO
+        echo "$VENDOR $DEVICE" > /sys/bus/pci/drivers/vfio-pci/new_id 2>/dev/null || true
+
+        # Bind to VFIO
+        echo "$PCI_ADDR" > /sys/bus/pci/drivers/vfio-pci/bind 2>/dev/null || true
+
+        echo "  ✓ {} ($PCI_ADDR) bound to vfio-pci"
+    fi
+else
+    echo "  ✗ {} ($PCI_ADDR) not found"
+fi
+echo
 
-            script.push_str(&format!(
-                "# Get PCI address and device IDs for {}\n",
-                interface
-            ));
+"#,
+                        interface, pci, interface, interface, interface
+                    ));
+                    continue;
+                }
 
-            script.push_str(&format!(
-                r#"if [ -e /sys/class/net/{}/device ]; then
+                script.push_str(&format!(
+                    "# Get PCI address and device IDs for {}\n",
+                    interface
+                ));
+
+                script.push_str(&format!(
+                    r#"if [ -e /sys/class/net/{}/device ]; then
     PCI_ADDR=$(basename $(readlink /sys/class/net/{}/device))
     VENDOR=$(cat /sys/bus/pci/devices/$PCI_ADDR/vendor | sed 's/0x//')
     DEVICE=$(cat /sys/bus/pci/devices/$PCI_ADDR/device | sed 's/0x//')
@@ -415,26 +765,26 @@ fi
 echo
 
 "#,
-                interface, interface, interface, interface
-            ));
-        }
-    }
+                    interface, interface, interface, interface
+                ));
+            }
 
-    // Set permissions
-    if config.options.set_permissions {
-        script.push_str("# Set VFIO device permissions\n");
-        script.push_str("echo \"Setting VFIO device permissions...\"\n");
-        script.push_str("chmod 666 /dev/vfio/vfio 2>/dev/null || true\n");
-        script.push_str(
-            r#"for dev in /dev/vfio/*; do
+            PlanStep::SetPermissions => {
+                script.push_str("# Set VFIO device permissions\n");
+                script.push_str("echo \"Setting VFIO device permissions...\"\n");
+                script.push_str("chmod 666 /dev/vfio/vfio 2>/dev/null || true\n");
+                script.push_str(
+                    r#"for dev in /dev/vfio/*; do
     if [ "$dev" != "/dev/vfio/vfio" ]; then
         chmod 666 "$dev" 2>/dev/null || true
     fi
 done
 "#,
-        );
-        script.push_str("echo \"✓ Permissions set\"\n");
-        script.push_str("echo\n\n");
+                );
+                script.push_str("echo \"✓ Permissions set\"\n");
+                script.push_str("echo\n\n");
+            }
+        }
     }
 
     script.push_str("echo \"✓ VFIO binding complete\"\n");
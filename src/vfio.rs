@@ -1,19 +1,28 @@
 use std::fs;
 use std::path::Path;
+use std::sync::OnceLock;
+use std::time::Duration;
 use anyhow::{Result, Context};
 use colored::Colorize;
 
 use crate::device::{self, NetworkDevice, DeviceStatus};
 use crate::config::Config;
+use crate::frameworks::{self, Framework};
 
 /// Bind interfaces to VFIO
-pub fn bind_interfaces(interfaces: &[&str]) -> Result<()> {
+/// Without `force`, nothing is bound if any of the devices fails `check_bind_safety`.
+pub fn bind_interfaces(interfaces: &[&str], force: bool) -> Result<()> {
+    bind_interface_batch(interfaces, force, false)?;
+    Ok(())
+}
+
+/// Bind interfaces to VFIO, returning the ones that failed
+/// With `keep_going`, each failure (including a failed safety check) is reported and the
+/// remaining interfaces are still bound; otherwise the first failure is returned as an error.
+fn bind_interface_batch(interfaces: &[&str], force: bool, keep_going: bool) -> Result<Vec<String>> {
     println!("{}", "Binding interfaces to VFIO...".bright_cyan());
     println!();
 
-    // Load VFIO module if not loaded
-    ensure_vfio_module_loaded()?;
-
     // Collect interface -> PCI mappings BEFORE binding
     let mut pci_mappings = std::collections::HashMap::new();
     for interface in interfaces {
@@ -21,36 +30,209 @@ pub fn bind_interfaces(interfaces: &[&str]) -> Result<()> {
             pci_mappings.insert(interface.to_string(), device.pci_address.clone());
         }
     }
+    let altnames = capture_altnames(&pci_mappings);
+    let batch: Vec<String> = interfaces.iter().filter_map(|i| target_pci_address(i)).collect();
+
+    if !force && !keep_going {
+        check_bind_safety(&batch)?;
+    }
+
+    // Load VFIO module if not loaded
+    ensure_vfio_module_loaded()?;
 
+    let mut failed = Vec::new();
     for interface in interfaces {
         println!("Processing: {}", interface.bright_yellow());
 
-        // Try to get device info by interface name
-        match device::get_device_info(interface) {
-            Ok(device) => {
-                bind_device(&device)?;
-            }
-            Err(_) => {
-                // Interface not found - check if we have PCI address in config
-                if let Some(pci_addr) = find_pci_address_in_vfio(interface) {
-                    println!("  {} Interface not visible, binding by PCI address {}", "ℹ".bright_blue(), pci_addr);
-                    bind_by_pci_address(&pci_addr)?;
-                } else {
-                    anyhow::bail!(
-                        "Interface {} not found and no PCI address mapping available. \
-                        Please ensure the device exists or use: sudo vfio-tool bind <pci-address>",
-                        interface
-                    );
-                }
+        let safe = match target_pci_address(interface) {
+            Some(pci) if keep_going && !force => check_bind_safety(&[pci]),
+            _ => Ok(()),
+        };
+        if let Err(e) = safe.and_then(|()| bind_named(interface, &batch)) {
+            if !keep_going {
+                return Err(e);
             }
+            println!("  {} {:#}", "✗".bright_red(), e);
+            failed.push(interface.to_string());
         }
         println!();
     }
 
     // Save PCI mappings to config for later unbinding
-    save_pci_mappings(&pci_mappings)?;
+    save_pci_mappings(&pci_mappings, &altnames)?;
+
+    if failed.is_empty() {
+        println!("{}", "✓ All interfaces bound to VFIO".bright_green());
+        println!();
+    }
+    println!("Device nodes created in /dev/vfio/:");
+    list_vfio_devices()?;
+
+    Ok(failed)
+}
+
+/// Bind one interface, by PCI address from the saved mappings if its netdev is gone
+fn bind_named(interface: &str, batch: &[String]) -> Result<()> {
+    // Try to get device info by interface name
+    match device::get_device_info(interface) {
+        Ok(device) => bind_device(&device, batch),
+        // The netdev is there but can't be bound (e.g. a USB NIC): say why
+        Err(e) if device::interface_exists(interface) => Err(e),
+        Err(_) => {
+            // Interface not found - check if we have PCI address in config
+            if let Some(pci_addr) = find_pci_address_in_vfio(interface) {
+                println!("  {} Interface not visible, binding by PCI address {}", "ℹ".bright_blue(), pci_addr);
+                bind_by_pci_address(interface, &pci_addr, batch)
+            } else {
+                anyhow::bail!(
+                    "Interface {} not found and no PCI address mapping available. \
+                    Please ensure the device exists or use: sudo vfio-tool bind <pci-address>",
+                    interface
+                );
+            }
+        }
+    }
+}
+
+/// A device resolved for a parallel bind
+struct BindTarget {
+    name: String,        // As given on the command line (interface or PCI address)
+    pci_address: String,
+}
+
+/// Outcome of binding one device in a parallel bind
+enum BindOutcome {
+    Bound,
+    AlreadyBound,
+    NotFound,
+    Failed(String),
+}
 
-    println!("{}", "✓ All interfaces bound to VFIO".bright_green());
+/// Bind one PCI device to vfio-pci without printing (safe to run from worker threads)
+fn bind_pci_quiet(pci_address: &str) -> Result<BindOutcome> {
+    if is_bound_to_vfio(pci_address) {
+        return Ok(BindOutcome::AlreadyBound);
+    }
+
+    let (vendor, dev_id) = device::get_vendor_device_id(pci_address)?;
+    check_vfio_driver_registered()?;
+
+    if device::get_driver(pci_address).is_some() {
+        unbind_pci_device(pci_address)?;
+    }
+    register_device_id(&vendor, &dev_id)?;
+    bind_and_record(pci_address)?;
+
+    Ok(BindOutcome::Bound)
+}
+
+/// Bind devices concurrently with at most `jobs` workers
+/// Work is split by IOMMU group: devices sharing a group are bound one after another by
+/// the same worker, so no two devices of one group are ever bound at the same time.
+pub fn bind_parallel(targets: &[&str], jobs: usize, force: bool) -> Result<()> {
+    use std::collections::BTreeMap;
+    use std::sync::Mutex;
+
+    println!("{}", "Binding devices to VFIO in parallel...".bright_cyan());
+    println!();
+
+    // Resolve names to PCI addresses up front (interface names vanish once bound)
+    let resolved: Vec<(&str, Option<String>)> = targets.iter().map(|name| (*name, target_pci_address(name))).collect();
+    if !force {
+        let found: Vec<String> = resolved.iter().filter_map(|(_, pci)| pci.clone()).collect();
+        check_bind_safety(&found)?;
+    }
+
+    ensure_vfio_module_loaded()?;
+
+    let batch: Vec<String> = resolved.iter().filter_map(|(_, pci)| pci.clone()).collect();
+    let mut outcomes: Vec<(String, String, BindOutcome)> = Vec::new();
+    let mut by_group: BTreeMap<String, Vec<BindTarget>> = BTreeMap::new();
+    let mut pci_mappings = std::collections::HashMap::new();
+
+    for (name, pci) in resolved {
+        let Some(pci) = pci else {
+            outcomes.push((name.to_string(), String::new(), BindOutcome::NotFound));
+            continue;
+        };
+
+        if !is_bound_to_vfio(&pci) && let Err(e) = check_group_complete(name, &pci, &batch) {
+            outcomes.push((name.to_string(), pci, BindOutcome::Failed(format!("{:#}", e))));
+            continue;
+        }
+
+        if !device::is_valid_pci_address(name) {
+            pci_mappings.insert(name.to_string(), pci.clone());
+        }
+
+        // Devices without a group cannot conflict with anything; give each its own bucket
+        let key = match device::get_iommu_group(&pci) {
+            Some(group) => format!("group-{}", group),
+            None => format!("pci-{}", pci),
+        };
+        by_group.entry(key).or_default().push(BindTarget { name: name.to_string(), pci_address: pci });
+    }
+
+    let altnames = capture_altnames(&pci_mappings);
+
+    let group_count = by_group.len();
+    let workers = jobs.max(1).min(group_count.max(1));
+    let buckets: Mutex<Vec<Vec<BindTarget>>> = Mutex::new(by_group.into_values().collect());
+    let results: Mutex<Vec<(String, String, BindOutcome)>> = Mutex::new(Vec::new());
+
+    println!("  {} group(s) across {} worker(s)", group_count, workers);
+    println!();
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            // The guard is moved into the closure, so the queue is unlocked while binding
+            scope.spawn(|| while let Some(bucket) = buckets.lock().ok().and_then(|mut b| b.pop()) {
+                for target in bucket {
+                    let outcome = bind_pci_quiet(&target.pci_address)
+                        .unwrap_or_else(|e| BindOutcome::Failed(format!("{:#}", e)));
+                    if let Ok(mut r) = results.lock() {
+                        r.push((target.name, target.pci_address, outcome));
+                    }
+                }
+            });
+        }
+    });
+
+    outcomes.extend(results.into_inner().unwrap_or_else(|e| e.into_inner()));
+
+    // Report in command-line order
+    outcomes.sort_by_key(|(name, _, _)| targets.iter().position(|t| t == name));
+
+    let mut not_found = false;
+    let mut failed = false;
+    for (name, pci, outcome) in &outcomes {
+        match outcome {
+            BindOutcome::Bound => println!("  {} {} ({}) bound to vfio-pci", "✓".bright_green(), name, pci),
+            BindOutcome::AlreadyBound => println!("  {} {} ({}) already bound to vfio-pci", "✓".bright_green(), name, pci),
+            BindOutcome::NotFound => {
+                println!("  {} {} - {}", "✗".bright_red(), name, "NOT FOUND".bright_red().bold());
+                not_found = true;
+            }
+            BindOutcome::Failed(e) => {
+                println!("  {} {} ({}) - {}", "✗".bright_red(), name, pci, e);
+                failed = true;
+            }
+        }
+    }
+    println!();
+
+    if !pci_mappings.is_empty() {
+        save_pci_mappings(&pci_mappings, &altnames)?;
+    }
+
+    if not_found {
+        anyhow::bail!("One or more interfaces not found");
+    }
+    if failed {
+        anyhow::bail!("Failed to bind one or more interfaces");
+    }
+
+    println!("{}", "✓ All devices bound to VFIO".bright_green());
     println!();
     println!("Device nodes created in /dev/vfio/:");
     list_vfio_devices()?;
@@ -58,8 +240,186 @@ pub fn bind_interfaces(interfaces: &[&str]) -> Result<()> {
     Ok(())
 }
 
+/// Refuse to bind devices whose loss would cut this machine off: a NIC carrying the default
+/// route (directly or via a bond/team master) or a GPU backing the console
+/// Every bind path runs this on the resolved PCI addresses unless --force is given.
+pub fn check_bind_safety(pci_addresses: &[String]) -> Result<()> {
+    for pci_address in pci_addresses {
+        if let Some(reason) = bind_hazard(pci_address) {
+            anyhow::bail!("{} {}.\nUse --force to bind anyway.", pci_address, reason);
+        }
+    }
+
+    Ok(())
+}
+
+/// Why binding a device would cut the machine off, if it would
+fn bind_hazard(pci_address: &str) -> Option<String> {
+    if let Some(reason) = uplink_usage(pci_address) {
+        return Some(format!("{}. Binding it to VFIO would drop network connectivity", reason));
+    }
+
+    let is_display = device::get_pci_class(pci_address)
+        .is_some_and(|c| c.starts_with("0x03"));
+    if is_display && let Some(reason) = console_usage(pci_address) {
+        return Some(format!("{}. Binding it to VFIO will turn the console dark", reason));
+    }
+
+    None
+}
+
+/// Describe how a device's network interface carries the default route, if one does
+fn uplink_usage(pci_address: &str) -> Option<String> {
+    let route_ifaces = default_route_interfaces();
+    if route_ifaces.is_empty() {
+        return None;
+    }
+
+    for interface in pci_netdevs(pci_address) {
+        // Walk up master links: slave -> bond/team -> bridge ...
+        let mut current = interface.clone();
+        for _ in 0..8 {
+            if route_ifaces.contains(&current) {
+                let via = if current == interface {
+                    String::new()
+                } else {
+                    format!(" (via its master {})", current)
+                };
+                return Some(format!("({}) carries the default route{}", interface, via));
+            }
+
+            match get_master_interface(&current) {
+                Some(master) => current = master,
+                None => break,
+            }
+        }
+    }
+
+    None
+}
+
+/// Network interfaces a PCI device currently has (none while on vfio-pci or driverless)
+/// virtio-net interfaces hang off the device's virtioN child rather than the device itself.
+fn pci_netdevs(pci_address: &str) -> Vec<String> {
+    let device_dir = Path::new("/sys/bus/pci/devices").join(pci_address);
+    let mut net_dirs = vec![device_dir.join("net")];
+    if let Ok(entries) = fs::read_dir(&device_dir) {
+        net_dirs.extend(entries
+            .flatten()
+            .filter(|e| e.file_name().to_string_lossy().starts_with("virtio"))
+            .map(|e| e.path().join("net")));
+    }
+
+    net_dirs
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten().map(|e| e.file_name().to_string_lossy().to_string()))
+        .collect()
+}
+
+/// PCI address of an interface name or PCI address, falling back to the saved mappings
+/// for interfaces that are no longer visible
+fn target_pci_address(target: &str) -> Option<String> {
+    if device::is_valid_pci_address(target) {
+        return Path::new(&format!("/sys/bus/pci/devices/{}", target)).exists().then(|| target.to_string());
+    }
+
+    device::get_device_info(target)
+        .map(|d| d.pci_address)
+        .ok()
+        .or_else(|| find_pci_address_in_vfio(target))
+}
+
+/// Describe how a display device is driving the console, if it is
+fn console_usage(pci_address: &str) -> Option<String> {
+    let device_dir = fs::canonicalize(format!("/sys/bus/pci/devices/{}", pci_address)).ok()?;
+    let backs = |link: &Path| fs::canonicalize(link).is_ok_and(|p| p == device_dir);
+
+    // fbdev framebuffers (fb0 from amdgpu/i915/nouveau fbdev emulation, etc.)
+    if let Ok(entries) = fs::read_dir("/sys/class/graphics") {
+        for entry in entries.filter_map(|e| e.ok()) {
+            if backs(&entry.path().join("device")) {
+                return Some(format!("backs framebuffer {}", entry.file_name().to_string_lossy()));
+            }
+        }
+    }
+
+    // DRM cards with a connected output (card0-HDMI-A-1/status = connected)
+    if let Ok(entries) = fs::read_dir("/sys/class/drm") {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Some((card, _connector)) = name.split_once('-') else {
+                continue;
+            };
+            let connected = fs::read_to_string(entry.path().join("status"))
+                .is_ok_and(|s| s.trim() == "connected");
+            if connected && backs(&Path::new("/sys/class/drm").join(card).join("device")) {
+                return Some(format!("drives connected display {}", name));
+            }
+        }
+    }
+
+    // Firmware framebuffers (efifb/vesafb/simplefb) live on the boot VGA device
+    let boot_vga = fs::read_to_string(device_dir.join("boot_vga"))
+        .is_ok_and(|v| v.trim() == "1");
+    let firmware_fb = fs::read_to_string("/proc/fb")
+        .ok()
+        .and_then(|fbs| {
+            fbs.lines()
+                .map(|l| l.split_once(' ').map(|(_, name)| name).unwrap_or(l).to_string())
+                .find(|name| ["EFI VGA", "VESA VGA", "simple"].iter().any(|fw| name.contains(fw)))
+        });
+    if boot_vga && let Some(name) = firmware_fb {
+        return Some(format!("is the boot VGA device backing the {} framebuffer", name));
+    }
+
+    None
+}
+
+/// Interfaces carrying an IPv4 or IPv6 default route
+fn default_route_interfaces() -> Vec<String> {
+    let mut ifaces = Vec::new();
+
+    // IPv4: Iface Destination Gateway Flags RefCnt Use Metric Mask ...
+    if let Ok(routes) = fs::read_to_string("/proc/net/route") {
+        for line in routes.lines().skip(1) {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() > 7 && fields[1] == "00000000" && fields[7] == "00000000" {
+                ifaces.push(fields[0].to_string());
+            }
+        }
+    }
+
+    // IPv6: dest dest_prefix src src_prefix next_hop metric refcnt use flags iface
+    if let Ok(routes) = fs::read_to_string("/proc/net/ipv6_route") {
+        for line in routes.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() >= 10
+                && fields[0].chars().all(|c| c == '0')
+                && fields[1] == "00"
+                && fields[9] != "lo"
+            {
+                ifaces.push(fields[9].to_string());
+            }
+        }
+    }
+
+    ifaces.sort();
+    ifaces.dedup();
+    ifaces
+}
+
+/// Get the bond/team/bridge master of an interface, if enslaved
+fn get_master_interface(interface: &str) -> Option<String> {
+    fs::read_link(format!("/sys/class/net/{}/master", interface))
+        .ok()
+        .and_then(|target| target.file_name().map(|n| n.to_string_lossy().to_string()))
+}
+
 /// Unbind interfaces from VFIO
-pub fn unbind_interfaces(interfaces: &[&str]) -> Result<()> {
+/// Without `reprobe`, the devices are left driverless, but nothing pins them there: their IDs
+/// stay in vfio-pci's dynamic ID table, so the next drivers_probe or rescan rebinds them to vfio-pci
+pub fn unbind_interfaces(interfaces: &[&str], reprobe: bool) -> Result<()> {
     println!("{}", "Unbinding interfaces from VFIO...".bright_cyan());
     println!();
 
@@ -72,7 +432,7 @@ pub fn unbind_interfaces(interfaces: &[&str]) -> Result<()> {
         println!("Processing: {}", interface.bright_yellow());
 
         // Check if this looks like a PCI address (format: 0000:XX:XX.X)
-        let pci_addr = if is_pci_address(interface) {
+        let pci_addr = if device::is_valid_pci_address(interface) {
             // Unbind by PCI address directly (if it exists and is bound)
             let device_path = format!("/sys/bus/pci/devices/{}", interface);
             if Path::new(&device_path).exists() {
@@ -132,46 +492,276 @@ pub fn unbind_interfaces(interfaces: &[&str]) -> Result<()> {
 
     println!("{}", "✓ All interfaces unbound from VFIO".bright_green());
 
-    // Trigger driver reprobe to let kernel drivers take over
-    if !pci_addresses.is_empty() {
+    if !reprobe {
+        // Binding went through new_id, not driver_override, so the IDs are still in vfio-pci's
+        // dynamic ID table: the next drivers_probe or rescan gives the devices back to vfio-pci
         println!();
-        println!("{}", "Reprobing kernel drivers...".bright_cyan());
-
+        println!("{}", "Not reprobing - devices left without a driver:".bright_yellow());
         for pci_addr in &pci_addresses {
-            // Clear driver_override to allow kernel to choose driver
-            let override_path = format!("/sys/bus/pci/devices/{}/driver_override", pci_addr);
-            let _ = fs::write(&override_path, "\n");
-
-            // Trigger reprobe
-            let probe_path = "/sys/bus/pci/drivers_probe";
-            if let Err(e) = fs::write(probe_path, pci_addr) {
-                println!("  {} Warning: Could not reprobe {} - {}", "⚠".bright_yellow(), pci_addr, e);
-            } else {
-                println!("  {} Reprobed {}", "✓".bright_green(), pci_addr);
+            println!("  {}", pci_addr);
+        }
+        println!("  {} Their IDs are still registered with vfio-pci, so a reprobe or PCI rescan rebinds them", "ℹ".bright_blue());
+        return Ok(());
+    }
+
+    // Trigger driver reprobe to let kernel drivers take over
+    reprobe_kernel_drivers(&pci_addresses)?;
+
+    Ok(())
+}
+
+/// Clear driver_override and reprobe so kernel drivers can take over the devices
+fn reprobe_kernel_drivers(pci_addresses: &[String]) -> Result<()> {
+    if pci_addresses.is_empty() {
+        return Ok(());
+    }
+
+    println!();
+    println!("{}", "Reprobing kernel drivers...".bright_cyan());
+
+    for pci_addr in pci_addresses {
+        // Clear driver_override to allow kernel to choose driver
+        let override_path = format!("/sys/bus/pci/devices/{}/driver_override", pci_addr);
+        write_sysfs_lenient(&override_path, "\n", &format!("clear driver_override of {}", pci_addr))?;
+
+        // Trigger reprobe
+        let probe_path = "/sys/bus/pci/drivers_probe";
+        if let Err(e) = fs::write(probe_path, pci_addr) {
+            if e.kind() == std::io::ErrorKind::PermissionDenied {
+                return Err(permission_denied(probe_path, &format!("reprobe {}", pci_addr)));
             }
+            println!("  {} Warning: Could not reprobe {} - {}", "⚠".bright_yellow(), pci_addr, e);
+        } else {
+            println!("  {} Reprobed {}", "✓".bright_green(), pci_addr);
         }
+    }
+
+    // Wait for drivers to settle
+    println!("  Waiting for drivers to load...");
+    std::thread::sleep(std::time::Duration::from_secs(2));
+
+    println!();
+    println!("{}", "✓ Kernel drivers loaded".bright_green());
+
+    Ok(())
+}
+
+/// Get the endpoint devices of an IOMMU group (PCI bridges are skipped)
+fn get_group_endpoints(group_id: u32) -> Result<Vec<String>> {
+    let members = device::get_iommu_group_devices(group_id)?;
+
+    let mut endpoints: Vec<String> = members
+        .into_iter()
+        .filter(|pci_addr| device::is_endpoint(pci_addr))
+        .collect();
+
+    endpoints.sort();
+
+    if endpoints.is_empty() {
+        anyhow::bail!("IOMMU group {} contains no bindable endpoint devices", group_id);
+    }
 
-        // Wait for drivers to settle
-        println!("  Waiting for drivers to load...");
-        std::thread::sleep(std::time::Duration::from_secs(2));
+    Ok(endpoints)
+}
+
+/// Bind every endpoint device in an IOMMU group to VFIO
+pub fn bind_group(group_id: u32, force: bool) -> Result<()> {
+    let endpoints = get_group_endpoints(group_id)?;
 
+    println!("{}", format!("Binding IOMMU group {} to VFIO...", group_id).bright_cyan());
+    println!();
+
+    // Load VFIO module if not loaded
+    ensure_vfio_module_loaded()?;
+
+    bind_group_endpoints(&endpoints, force)?;
+
+    println!("{}", format!("✓ IOMMU group {} bound to VFIO", group_id).bright_green());
+    println!();
+    println!("Device nodes created in /dev/vfio/:");
+    list_vfio_devices()?;
+
+    Ok(())
+}
+
+/// Bind a group's endpoint devices, recording the interface mappings they had first
+/// Without `force`, nothing is bound if any endpoint fails `check_bind_safety`.
+fn bind_group_endpoints(endpoints: &[String], force: bool) -> Result<()> {
+    if !force {
+        check_bind_safety(endpoints)?;
+    }
+
+    // Collect interface -> PCI mappings BEFORE binding (interfaces disappear afterwards)
+    let mut pci_mappings = std::collections::HashMap::new();
+    for pci_addr in endpoints {
+        for interface in pci_netdevs(pci_addr) {
+            pci_mappings.insert(interface, pci_addr.clone());
+        }
+    }
+    let altnames = capture_altnames(&pci_mappings);
+
+    for pci_addr in endpoints {
+        println!("Processing: {}", pci_addr.bright_yellow());
+        bind_by_pci_address(pci_addr, pci_addr, endpoints)?;
         println!();
-        println!("{}", "✓ Kernel drivers loaded".bright_green());
+    }
+
+    if !pci_mappings.is_empty() {
+        save_pci_mappings(&pci_mappings, &altnames)?;
     }
 
     Ok(())
 }
 
-/// Unbind all VFIO devices and refresh config mappings
-pub fn unbind_all() -> Result<()> {
-    println!("{}", "Resetting all VFIO devices...".bright_cyan());
+/// Unbind every endpoint device in an IOMMU group from VFIO
+pub fn unbind_group(group_id: u32) -> Result<()> {
+    let endpoints = get_group_endpoints(group_id)?;
+
+    println!("{}", format!("Unbinding IOMMU group {} from VFIO...", group_id).bright_cyan());
     println!();
 
+    let mut unbound = Vec::new();
+    for pci_addr in &endpoints {
+        println!("Processing: {}", pci_addr.bright_yellow());
+        if is_bound_to_vfio(pci_addr) {
+            unbound.push(pci_addr.clone());
+        }
+        unbind_by_pci_address(pci_addr)?;
+        println!();
+    }
+
+    println!("{}", format!("✓ IOMMU group {} unbound from VFIO", group_id).bright_green());
+
+    reprobe_kernel_drivers(&unbound)?;
+
+    Ok(())
+}
+
+/// Bind the whole IOMMU group of each named interface (or PCI address)
+/// Without `force`, every endpoint of every group is checked before anything is bound.
+pub fn bind_interface_groups(interfaces: &[&str], force: bool) -> Result<()> {
+    let groups = interface_groups(interfaces)?;
+
+    if !force {
+        let mut endpoints = Vec::new();
+        for &group in &groups {
+            endpoints.extend(get_group_endpoints(group)?);
+        }
+        check_bind_safety(&endpoints)?;
+    }
+
+    for group in groups {
+        bind_group(group, force)?;
+        println!();
+    }
+
+    Ok(())
+}
+
+/// Unbind the whole IOMMU group of each named interface (or PCI address)
+/// Leaving part of a group on vfio-pci keeps /dev/vfio/<group> around but unusable
+pub fn unbind_interface_groups(interfaces: &[&str]) -> Result<()> {
+    for group in interface_groups(interfaces)? {
+        unbind_group(group)?;
+        println!();
+    }
+
+    Ok(())
+}
+
+/// IOMMU groups of the named interfaces (or PCI addresses), without duplicates
+fn interface_groups(interfaces: &[&str]) -> Result<Vec<u32>> {
+    let config = crate::config::load_config().ok();
+    let mut groups = Vec::new();
+
+    for interface in interfaces {
+        let pci_addr = if device::is_valid_pci_address(interface) {
+            Some(interface.to_string())
+        } else {
+            device::get_device_info(interface)
+                .map(|d| d.pci_address)
+                .ok()
+                .or_else(|| find_pci_address_in_vfio(interface))
+                .or_else(|| config.as_ref().and_then(|c| c.devices.pci_mappings.get(*interface).cloned()))
+        };
+
+        let pci_addr = pci_addr
+            .ok_or_else(|| anyhow::anyhow!("Interface {} not found and no PCI address mapping available", interface))?;
+        let group = device::get_iommu_group(&pci_addr)
+            .ok_or_else(|| anyhow::anyhow!("{} ({}) has no IOMMU group. Is IOMMU enabled?", interface, pci_addr))?;
+
+        if !groups.contains(&group) {
+            groups.push(group);
+        }
+    }
+
+    Ok(groups)
+}
+
+/// What `reset` left a device bound to
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResetOutcome {
+    Unbound,                // Off vfio-pci, but no kernel driver claimed it
+    Reprobed,               // A kernel driver claimed it; no network interface
+    Reappeared(String),     // A kernel driver claimed it and the interface is back
+    ReprobeFailed(String),  // The drivers_probe write failed
+}
+
+/// Result of resetting one device
+#[derive(Debug, Clone)]
+pub struct ResetResult {
+    pub pci_address: String,
+    pub driver: Option<String>,
+    pub outcome: ResetOutcome,
+}
+
+impl ResetResult {
+    /// Whether the device is back on a kernel driver
+    pub fn succeeded(&self) -> bool {
+        matches!(self.outcome, ResetOutcome::Reprobed | ResetOutcome::Reappeared(_))
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let (outcome, interface, error) = match &self.outcome {
+            ResetOutcome::Unbound => ("unbound", None, None),
+            ResetOutcome::Reprobed => ("reprobed", None, None),
+            ResetOutcome::Reappeared(iface) => ("reappeared", Some(iface), None),
+            ResetOutcome::ReprobeFailed(e) => ("reprobe-failed", None, Some(e)),
+        };
+        serde_json::json!({
+            "pci": self.pci_address,
+            "outcome": outcome,
+            "driver": self.driver,
+            "interface": interface,
+            "error": error,
+        })
+    }
+}
+
+/// Progress line for `reset`: stdout normally, stderr when stdout carries JSON
+fn progress(json: bool, line: impl std::fmt::Display) {
+    if json {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+}
+
+/// Unbind all VFIO devices of a PCI class and refresh config mappings
+/// `class` is a class filter prefix (e.g. "02" for network); None unbinds every class.
+/// With `managed_only`, devices bound by other means (kernel cmdline, other tools) are left alone
+/// With `json`, progress goes to stderr so the caller can print the results on stdout
+pub fn unbind_all(managed_only: bool, class: Option<&str>, json: bool) -> Result<Vec<ResetResult>> {
+    let filter = class.map(device::parse_class_filter).transpose()?;
+
+    progress(json, "Resetting all VFIO devices...".bright_cyan());
+    progress(json, "");
+
     let vfio_driver_path = Path::new("/sys/bus/pci/drivers/vfio-pci");
 
     if !vfio_driver_path.exists() {
-        println!("{}", "No VFIO devices bound.".bright_green());
-        return Ok(());
+        progress(json, "No VFIO devices bound.".bright_green());
+        return Ok(Vec::new());
     }
 
     // Collect PCI addresses before unbinding
@@ -183,82 +773,138 @@ pub fn unbind_all() -> Result<()> {
 
         // Only check PCI devices (format: 0000:XX:XX.X)
         if name_str.contains(':') && name_str.contains('.') {
-            // Check if it's a network device
-            let class_path = format!("/sys/bus/pci/devices/{}/class", name_str);
-            if let Ok(class_str) = fs::read_to_string(&class_path) {
-                let class_code = class_str.trim();
-                if class_code.starts_with("0x02") {
-                    pci_addresses.push(name_str.to_string());
-                }
+            let in_scope = match filter {
+                Some(ref filter) => device::get_pci_class(&name_str)
+                    .is_some_and(|c| device::class_matches(&c, filter)),
+                None => true,
+            };
+            if in_scope {
+                pci_addresses.push(name_str.to_string());
             }
         }
     }
 
+    if managed_only {
+        let managed = load_managed_devices();
+        pci_addresses.retain(|pci_addr| {
+            let is_managed = managed.contains(pci_addr);
+            if !is_managed {
+                progress(json, format!("Skipping: {} {}", pci_addr.bright_blue(), "(not bound by vfio-tool)".bright_black()));
+            }
+            is_managed
+        });
+    }
+
     if pci_addresses.is_empty() {
-        println!("{}", "No VFIO network devices found.".bright_green());
-        return Ok(());
+        let scope = match filter {
+            Some(ref f) if f.as_str() == "02" => "network ".to_string(),
+            Some(ref f) => format!("class 0x{} ", f),
+            None => String::new(),
+        };
+        progress(json, format!("No VFIO {}devices found.", scope).bright_green());
+        return Ok(Vec::new());
     }
 
     // Unbind all devices
     let mut count = 0;
     for pci_addr in &pci_addresses {
-        println!("Unbinding: {}", pci_addr.bright_yellow());
+        progress(json, format!("Unbinding: {}", pci_addr.bright_yellow()));
         unbind_pci_device(pci_addr)?;
         count += 1;
     }
 
-    println!("\n{} {} unbound from vfio-pci", "✓".bright_green(),
-        if count == 1 { "device" } else { "devices" });
+    progress(json, format!("\n{} {} unbound from vfio-pci", "✓".bright_green(),
+        if count == 1 { "device" } else { "devices" }));
 
     // Trigger driver reprobe to let kernel drivers take over
-    println!();
-    println!("{}", "Reprobing kernel drivers...".bright_cyan());
+    progress(json, "");
+    progress(json, "Reprobing kernel drivers...".bright_cyan());
+    let mut probe_errors = std::collections::HashMap::new();
     for pci_addr in &pci_addresses {
         // Clear driver_override to allow kernel to choose driver
         let override_path = format!("/sys/bus/pci/devices/{}/driver_override", pci_addr);
-        let _ = fs::write(&override_path, "\n");
+        write_sysfs_lenient(&override_path, "\n", &format!("clear driver_override of {}", pci_addr))?;
 
-        // Trigger reprobe
+        // Trigger reprobe; unlike the override, a failure here leaves the device driverless
         let probe_path = "/sys/bus/pci/drivers_probe";
-        let _ = fs::write(probe_path, pci_addr);
+        match fs::write(probe_path, pci_addr) {
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                return Err(permission_denied(probe_path, &format!("reprobe {}", pci_addr)));
+            }
+            Err(e) => {
+                probe_errors.insert(pci_addr.clone(), e.to_string());
+            }
+            Ok(()) => {}
+        }
     }
 
     // Wait for interfaces to settle
-    println!("  Waiting for interfaces to appear...");
+    progress(json, "  Waiting for interfaces to appear...");
     std::thread::sleep(std::time::Duration::from_secs(2));
 
-    // Scan for interface names and update config mappings
-    println!();
-    println!("{}", "Updating interface mappings...".bright_cyan());
+    // Record where each device ended up and update config mappings
+    progress(json, "");
+    progress(json, "Updating interface mappings...".bright_cyan());
 
+    let mut results = Vec::new();
     let mut new_mappings = std::collections::HashMap::new();
     for pci_addr in &pci_addresses {
+        let driver = device::get_driver(pci_addr);
+
         // Check if interface reappeared
         let net_dir = format!("/sys/bus/pci/devices/{}/net", pci_addr);
-        if let Ok(entries) = fs::read_dir(&net_dir) {
-            for entry in entries.flatten() {
-                let iface_name = entry.file_name().to_string_lossy().to_string();
-                new_mappings.insert(iface_name.clone(), pci_addr.clone());
-                println!("  {} → {}", pci_addr.bright_blue(), iface_name.bright_green());
+        let interface = fs::read_dir(&net_dir)
+            .ok()
+            .and_then(|entries| entries.flatten().next())
+            .map(|entry| entry.file_name().to_string_lossy().to_string());
+
+        let outcome = match (probe_errors.remove(pci_addr), &driver, interface) {
+            (Some(e), _, _) => ResetOutcome::ReprobeFailed(e),
+            (None, None, _) => ResetOutcome::Unbound,
+            (None, Some(_), Some(iface)) => ResetOutcome::Reappeared(iface),
+            (None, Some(_), None) => ResetOutcome::Reprobed,
+        };
+
+        match outcome {
+            ResetOutcome::Reappeared(ref iface) => {
+                new_mappings.insert(iface.clone(), pci_addr.clone());
+                progress(json, format!("  {} → {}", pci_addr.bright_blue(), iface.bright_green()));
+            }
+            ResetOutcome::Reprobed => {
+                progress(json, format!("  {} → {} (no interface)", pci_addr.bright_blue(), driver.as_deref().unwrap_or_default()));
+            }
+            ResetOutcome::Unbound => {
+                progress(json, format!("  {} {} {}", "⚠".bright_yellow(), pci_addr.bright_blue(), "no kernel driver claimed it".bright_yellow()));
+            }
+            ResetOutcome::ReprobeFailed(ref e) => {
+                progress(json, format!("  {} {} reprobe failed: {}", "⚠".bright_yellow(), pci_addr.bright_blue(), e));
             }
         }
+
+        results.push(ResetResult { pci_address: pci_addr.clone(), driver, outcome });
     }
 
     if !new_mappings.is_empty() {
-        save_pci_mappings(&new_mappings)?;
-        println!();
-        println!("{}", "✓ Interface mappings updated in config".bright_green());
+        save_pci_mappings(&new_mappings, &capture_altnames(&new_mappings))?;
+        progress(json, "");
+        progress(json, "✓ Interface mappings updated in config".bright_green());
     }
 
-    Ok(())
+    Ok(results)
 }
 
 /// Apply saved configuration
-pub fn apply_config(config: &Config) -> Result<()> {
+/// Without `force`, devices that fail `check_bind_safety` are not bound.
+pub fn apply_config(config: &Config, force: bool) -> Result<()> {
     println!("{}", "Applying VFIO configuration...".bright_cyan());
     println!();
 
-    if config.devices.vfio.is_empty() {
+    // Return kernel-list devices to their kernel drivers first (opt-in)
+    if config.options.enforce_kernel_list {
+        restore_kernel_devices(config)?;
+    }
+
+    if config.devices.vfio.is_empty() && config.devices.groups.is_empty() {
         println!("{}", "No devices configured for VFIO.".bright_yellow());
         return Ok(());
     }
@@ -268,38 +914,125 @@ pub fn apply_config(config: &Config) -> Result<()> {
         ensure_vfio_module_loaded()?;
     }
 
+    // Bind every endpoint of the configured IOMMU groups (first, so listed devices in
+    // those groups don't look like partial group binds)
+    // Failures are collected so one bad group or device doesn't leave the rest unbound
+    let mut failed = Vec::new();
+    for &group_id in &config.devices.groups {
+        let endpoints = match get_group_endpoints(group_id) {
+            Ok(endpoints) => endpoints,
+            Err(e) => {
+                println!("{} IOMMU group {}: {:#}", "✗".bright_red(), group_id, e);
+                failed.push(format!("IOMMU group {}", group_id));
+                continue;
+            }
+        };
+        if endpoints.iter().all(|pci| is_bound_to_vfio(pci)) {
+            println!("{} IOMMU group {} already bound to VFIO", "✓".bright_green(), group_id);
+            continue;
+        }
+
+        println!("{}", format!("Binding IOMMU group {} to VFIO...", group_id).bright_cyan());
+        match bind_group_endpoints(&endpoints, force) {
+            Ok(()) => println!("{}", format!("✓ IOMMU group {} bound to VFIO", group_id).bright_green()),
+            Err(e) => {
+                println!("{} IOMMU group {}: {:#}", "✗".bright_red(), group_id, e);
+                failed.push(format!("IOMMU group {}", group_id));
+            }
+        }
+        println!();
+    }
+
     // Bind VFIO devices
-    let vfio_refs: Vec<&str> = config.devices.vfio.iter().map(String::as_str).collect();
-    bind_interfaces(&vfio_refs)?;
+    if !config.devices.vfio.is_empty() {
+        let vfio_refs: Vec<&str> = config.devices.vfio.iter().map(String::as_str).collect();
+        failed.extend(bind_interface_batch(&vfio_refs, force, true)?);
+    }
 
     // Set permissions
     if config.options.set_permissions {
         set_vfio_permissions()?;
     }
 
+    if !failed.is_empty() {
+        anyhow::bail!("Failed to bind {} of the configured entries: {}", failed.len(), failed.join(", "));
+    }
+
+    Ok(())
+}
+
+/// Return every configured device to its configured mode (recovery after `reset`)
+/// Equivalent to `apply` with kernel-list enforcement, plus reprobing kernel-list
+/// devices that were left without any driver
+pub fn restore_config(config: &Config) -> Result<()> {
+    println!("{}", "Restoring devices to their configured modes...".bright_cyan());
+    println!();
+
+    let driverless: Vec<String> = config.devices.kernel
+        .iter()
+        .filter_map(|iface| config.devices.pci_mappings.get(iface))
+        .filter(|pci| Path::new(&format!("/sys/bus/pci/devices/{}", pci)).exists())
+        .filter(|pci| device::get_driver(pci).is_none())
+        .cloned()
+        .collect();
+    reprobe_kernel_drivers(&driverless)?;
+
+    let mut enforced = config.clone();
+    enforced.options.enforce_kernel_list = true;
+    apply_config(&enforced, false)
+}
+
+/// Unbind any device in the config's kernel list that is currently bound to vfio-pci
+fn restore_kernel_devices(config: &Config) -> Result<()> {
+    let to_restore: Vec<&str> = config.devices.kernel
+        .iter()
+        .filter(|iface| {
+            config.devices.pci_mappings
+                .get(iface.as_str())
+                .cloned()
+                .or_else(|| find_pci_address_in_vfio(iface))
+                .map(|pci| is_bound_to_vfio(&pci))
+                .unwrap_or(false)
+        })
+        .map(String::as_str)
+        .collect();
+
+    if to_restore.is_empty() {
+        return Ok(());
+    }
+
+    println!("{}", "Returning kernel-list devices to kernel drivers...".bright_cyan());
+    println!();
+
+    unbind_interfaces(&to_restore, true)?;
+    println!();
+
     Ok(())
 }
 
 /// Bind a single device to VFIO
-fn bind_device(device: &NetworkDevice) -> Result<()> {
+/// `batch` holds the PCI addresses of every device being bound in the same operation
+fn bind_device(device: &NetworkDevice, batch: &[String]) -> Result<()> {
     // Check current status
     if device.is_vfio_bound() {
         println!("  {} Already bound to vfio-pci", "✓".bright_green());
         return Ok(());
     }
 
+    check_vfio_driver_registered()?;
+    check_group_complete(&device.interface, &device.pci_address, batch)?;
+
     // Step 1: Unbind from current driver (if any)
-    if device.driver.is_some() {
+    if let Some(ref driver) = device.driver {
         unbind_pci_device(&device.pci_address)?;
-        println!("  {} Unbound from {}", "✓".bright_green(),
-            device.driver.as_ref().unwrap());
+        println!("  {} Unbound from {}", "✓".bright_green(), driver);
     }
 
     // Step 2: Register device ID with VFIO
     register_device_id(&device.vendor_id, &device.device_id)?;
 
     // Step 3: Bind to vfio-pci
-    bind_pci_device(&device.pci_address)?;
+    bind_and_record(&device.pci_address)?;
     println!("  {} Bound to vfio-pci", "✓".bright_green());
 
     // Step 4: Verify
@@ -313,6 +1046,40 @@ fn bind_device(device: &NetworkDevice) -> Result<()> {
     Ok(())
 }
 
+/// Fail before binding a device whose IOMMU group would end up split between vfio-pci
+/// and kernel drivers. VFIO only opens a group once every endpoint in it is on vfio-pci
+/// (or driverless), so a partial bind would otherwise fail later, when /dev/vfio/<group>
+/// is opened. Endpoints in `batch` are being bound too and don't count.
+fn check_group_complete(name: &str, pci_address: &str, batch: &[String]) -> Result<()> {
+    let Some(group) = device::get_iommu_group(pci_address) else {
+        return Ok(());
+    };
+    let Ok(endpoints) = get_group_endpoints(group) else {
+        return Ok(());
+    };
+
+    let blocking: Vec<String> = endpoints
+        .into_iter()
+        .filter(|pci| pci != pci_address && !batch.contains(pci))
+        .filter_map(|pci| match device::get_driver(&pci) {
+            Some(driver) if driver != "vfio-pci" => Some(format!("{} ({})", pci, driver)),
+            _ => None,
+        })
+        .collect();
+
+    if blocking.is_empty() {
+        return Ok(());
+    }
+
+    anyhow::bail!(
+        "{} shares IOMMU group {} with devices that would stay on kernel drivers: {}\n\
+        VFIO can only use a group once all of its devices are on vfio-pci, so the functions of \
+        one multi-function card can't be split between VFIO and the kernel.\n\
+        Bind the whole group instead: sudo vfio-tool bind {} --group",
+        name, group, blocking.join(", "), name
+    )
+}
+
 /// Unbind a single device from VFIO
 fn unbind_device(device: &NetworkDevice) -> Result<()> {
     if !device.is_vfio_bound() {
@@ -326,13 +1093,69 @@ fn unbind_device(device: &NetworkDevice) -> Result<()> {
     Ok(())
 }
 
-/// Ensure VFIO module is loaded
-fn ensure_vfio_module_loaded() -> Result<()> {
-    // Check if already loaded
+/// VFIO-related kernel modules that can be loaded with `load-module`
+pub const KNOWN_MODULES: &[&str] = &["vfio", "vfio-pci", "vfio_iommu_type1", "iommufd"];
+
+/// Whether the vfio-pci driver is registered with the PCI bus
+/// True whether it was loaded as a module or built into the kernel; built-in drivers
+/// never appear in /proc/modules.
+pub fn is_vfio_pci_registered() -> bool {
+    Path::new("/sys/bus/pci/drivers/vfio-pci").exists()
+}
+
+/// Check if a kernel module is loaded (names compared with '-' normalized to '_')
+/// vfio-pci also counts as loaded when it is built into the kernel
+fn is_module_loaded(name: &str) -> Result<bool> {
+    if name.replace('-', "_") == "vfio_pci" && is_vfio_pci_registered() {
+        return Ok(true);
+    }
+
     let modules = fs::read_to_string("/proc/modules")
         .context("Failed to read /proc/modules")?;
 
-    if modules.lines().any(|line| line.starts_with("vfio_pci")) {
+    let module_name = name.replace('-', "_");
+
+    Ok(modules
+        .lines()
+        .any(|line| line.split_whitespace().next() == Some(module_name.as_str())))
+}
+
+/// Load a known VFIO-related kernel module, reporting if it was already loaded
+pub fn load_module(name: &str) -> Result<()> {
+    let normalized = name.replace('-', "_");
+    if !KNOWN_MODULES.iter().any(|m| m.replace('-', "_") == normalized) {
+        anyhow::bail!(
+            "Unknown module: {}\nSupported: {}",
+            name,
+            KNOWN_MODULES.join(", ")
+        );
+    }
+
+    if is_module_loaded(name)? {
+        println!("{} Module {} is already loaded", "✓".bright_green(), name.bright_white());
+        return Ok(());
+    }
+
+    println!("{}", format!("Loading {} module...", name).bright_cyan());
+
+    let output = std::process::Command::new("modprobe")
+        .arg(name)
+        .output()
+        .context("Failed to run modprobe")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Failed to load {} module: {}", name, stderr.trim());
+    }
+
+    println!("{} Module {} loaded", "✓".bright_green(), name.bright_white());
+    Ok(())
+}
+
+/// Ensure VFIO module is loaded
+fn ensure_vfio_module_loaded() -> Result<()> {
+    // Check if already loaded
+    if is_module_loaded("vfio_pci")? {
         return Ok(());
     }
 
@@ -353,9 +1176,26 @@ fn ensure_vfio_module_loaded() -> Result<()> {
     Ok(())
 }
 
+/// Fail clearly if the vfio-pci driver isn't registered (its new_id node is missing)
+/// Called before a device is detached from its kernel driver, so a failure leaves it untouched
+fn check_vfio_driver_registered() -> Result<()> {
+    let new_id_path = "/sys/bus/pci/drivers/vfio-pci/new_id";
+
+    if !Path::new(new_id_path).exists() {
+        anyhow::bail!(
+            "vfio-pci driver not registered ({} missing) - is the vfio-pci module loaded?\n\
+            Try: sudo modprobe vfio-pci",
+            new_id_path
+        );
+    }
+
+    Ok(())
+}
+
 /// Register device ID with VFIO driver
 fn register_device_id(vendor: &str, device: &str) -> Result<()> {
     let new_id_path = "/sys/bus/pci/drivers/vfio-pci/new_id";
+    check_vfio_driver_registered()?;
 
     // Extract hex values (remove 0x prefix if present)
     let vendor_hex = vendor.trim_start_matches("0x");
@@ -363,9 +1203,38 @@ fn register_device_id(vendor: &str, device: &str) -> Result<()> {
 
     let id_string = format!("{} {}", vendor_hex, device_hex);
 
-    // This might fail if already registered, which is fine
-    let _ = fs::write(new_id_path, &id_string);
+    // This fails with EEXIST if the ID is already registered, which is fine
+    write_sysfs_lenient(new_id_path, &id_string, &format!("register {}:{} with vfio-pci", vendor_hex, device_hex))?;
+
+    Ok(())
+}
+
+/// Retry policy for a bind that fails with EBUSY while the device is not on vfio-pci
+#[derive(Debug, Clone, Copy)]
+pub struct EbusyRetry {
+    pub attempts: u32,
+    pub delay: Duration,
+}
+
+static EBUSY_RETRY: OnceLock<EbusyRetry> = OnceLock::new();
+
+/// Retry binds that hit EBUSY for the rest of this invocation
+/// Covers the window where udev or the kernel driver briefly reclaims a device after unbind
+pub fn set_ebusy_retry(retry: EbusyRetry) {
+    let _ = EBUSY_RETRY.set(retry);
+}
+
+/// Bind a device to vfio-pci and record it as bound by this tool
+fn bind_and_record(pci_address: &str) -> Result<()> {
+    bind_recorded(pci_address, MANAGED_STATE_FILE, bind_pci_device)
+}
 
+/// Run a bind and record the device in `state_file` if it succeeded
+/// Recording can't live in the bind itself: `register_device_id` usually has vfio-pci
+/// auto-bind the device, so the bind finds it already there and returns early.
+fn bind_recorded(pci_address: &str, state_file: &str, bind: impl FnOnce(&str) -> Result<()>) -> Result<()> {
+    bind(pci_address)?;
+    record_managed_device(state_file, pci_address);
     Ok(())
 }
 
@@ -377,9 +1246,35 @@ fn bind_pci_device(pci_address: &str) -> Result<()> {
     }
 
     let bind_path = "/sys/bus/pci/drivers/vfio-pci/bind";
+    let retry = EBUSY_RETRY.get().copied().unwrap_or(EbusyRetry { attempts: 0, delay: Duration::ZERO });
+    let mut attempt = 0;
 
     // Try to bind
-    match fs::write(bind_path, pci_address) {
+    loop {
+        let result = fs::write(bind_path, pci_address);
+        let busy = matches!(&result, Err(e) if e.raw_os_error() == Some(16));
+
+        // EBUSY (error 16) while the device is already on vfio-pci is success:
+        // register_device_id() may have auto-bound it
+        if busy && !is_bound_to_vfio(pci_address) && attempt < retry.attempts {
+            attempt += 1;
+            println!("  {} {} busy, retrying bind ({}/{})...", "⟳".bright_yellow(), pci_address, attempt, retry.attempts);
+            std::thread::sleep(retry.delay);
+
+            // The device may have settled onto vfio-pci during the delay
+            if is_bound_to_vfio(pci_address) {
+                return Ok(());
+            }
+            continue;
+        }
+
+        return finish_bind(result, pci_address, attempt);
+    }
+}
+
+/// Interpret the final bind write, after any EBUSY retries
+fn finish_bind(result: std::io::Result<()>, pci_address: &str, retried: u32) -> Result<()> {
+    match result {
         Ok(_) => Ok(()),
         Err(e) if e.raw_os_error() == Some(16) => {
             // EBUSY (error 16) - check if device is already bound to vfio-pci
@@ -389,24 +1284,62 @@ fn bind_pci_device(pci_address: &str) -> Result<()> {
                 Ok(())
             } else {
                 // Device is busy with something else - real error
-                Err(e).context(format!(
-                    "Failed to bind {} to vfio-pci: device is busy with another driver",
-                    pci_address
+                let retried = if retried > 0 { format!(" (after {} retries)", retried) } else { String::new() };
+                Err(e).context(with_kernel_messages(
+                    format!("Failed to bind {} to vfio-pci: device is busy with another driver{}", pci_address, retried),
+                    pci_address,
                 ))
             }
         }
-        Err(e) => Err(e).context(format!("Failed to bind {} to vfio-pci", pci_address)),
+        Err(e) => Err(e).context(with_kernel_messages(
+            format!("Failed to bind {} to vfio-pci", pci_address),
+            pci_address,
+        )),
     }
 }
 
+/// Number of recent kernel log lines about a device shown with a bind failure
+const KERNEL_MESSAGE_LINES: usize = 10;
+
+/// Recent kernel log lines mentioning a PCI address (e.g. RMRR or reset failures)
+/// Empty if the log can't be read (dmesg missing, or restricted by kernel.dmesg_restrict)
+fn kernel_messages(pci_address: &str) -> Vec<String> {
+    let Ok(output) = std::process::Command::new("dmesg").output() else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+
+    let log = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = log
+        .lines()
+        .filter(|line| line.contains(pci_address))
+        .collect();
+
+    lines[lines.len().saturating_sub(KERNEL_MESSAGE_LINES)..]
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+}
+
+/// Append the device's recent kernel log lines to an error message
+/// The real cause of a failed bind is usually only in the kernel log
+fn with_kernel_messages(message: String, pci_address: &str) -> String {
+    let lines = kernel_messages(pci_address);
+    if lines.is_empty() {
+        return message;
+    }
+
+    format!("{}\n\nRecent kernel messages for {}:\n  {}", message, pci_address, lines.join("\n  "))
+}
+
 /// Check if a PCI device is currently bound to vfio-pci
 fn is_bound_to_vfio(pci_address: &str) -> bool {
     let driver_path = format!("/sys/bus/pci/devices/{}/driver", pci_address);
 
-    if let Ok(target) = fs::read_link(&driver_path) {
-        if let Some(driver_name) = target.file_name() {
-            return driver_name.to_string_lossy() == "vfio-pci";
-        }
+    if let Ok(target) = fs::read_link(&driver_path) && let Some(driver_name) = target.file_name() {
+        return driver_name.to_string_lossy() == "vfio-pci";
     }
 
     false
@@ -414,23 +1347,135 @@ fn is_bound_to_vfio(pci_address: &str) -> bool {
 
 /// Unbind PCI device from its current driver
 fn unbind_pci_device(pci_address: &str) -> Result<()> {
+    if is_bound_to_vfio(pci_address) {
+        forget_managed_device(pci_address);
+    }
+
     let device_path = format!("/sys/bus/pci/devices/{}/driver/unbind", pci_address);
 
     // This might fail if already unbound, which is fine
-    let _ = fs::write(&device_path, pci_address);
+    write_sysfs_lenient(&device_path, pci_address, &format!("unbind {}", pci_address))?;
 
     Ok(())
 }
 
-/// Check if a string looks like a PCI address (format: 0000:XX:XX.X)
-fn is_pci_address(s: &str) -> bool {
-    // PCI address format: 4 hex digits : 2 hex digits : 2 hex digits . 1 hex digit
-    // Example: 0000:01:00.0
-    s.contains(':') && s.contains('.')
+/// Error for a sysfs write refused for lack of privileges
+fn permission_denied(path: &str, operation: &str) -> anyhow::Error {
+    anyhow::anyhow!("Permission denied: cannot {} (writing {}) - rerun with sudo", operation, path)
+}
+
+/// Best-effort sysfs write: expected failures (already registered, already unbound) are
+/// ignored, but a permission error stops the operation instead of faking success
+fn write_sysfs_lenient(path: &str, value: &str, operation: &str) -> Result<()> {
+    match fs::write(path, value) {
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => Err(permission_denied(path, operation)),
+        _ => Ok(()),
+    }
+}
+
+/// State file listing PCI addresses bound to vfio-pci by this tool (cleared on reboot)
+/// One "<pci> <unix time of bind>" line per device; older files have no timestamp.
+/// Kept in /run rather than the config: bindings don't outlive a boot, so neither should this.
+const MANAGED_STATE_FILE: &str = "/run/vfio-tool.managed";
+
+/// Load (PCI address, bind time) entries for devices bound by this tool since boot
+fn load_managed_entries(state_file: &str) -> Vec<(String, Option<u64>)> {
+    fs::read_to_string(state_file)
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|l| {
+                    let mut fields = l.split_whitespace();
+                    let pci = fields.next()?.to_string();
+                    let bound_at = fields.next().and_then(|t| t.parse().ok());
+                    Some((pci, bound_at))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Load PCI addresses bound to vfio-pci by this tool since boot
+fn load_managed_devices() -> Vec<String> {
+    load_managed_entries(MANAGED_STATE_FILE).into_iter().map(|(pci, _)| pci).collect()
+}
+
+fn save_managed_entries(state_file: &str, entries: &[(String, Option<u64>)]) {
+    let content: String = entries
+        .iter()
+        .map(|(pci, bound_at)| match bound_at {
+            Some(t) => format!("{} {}\n", pci, t),
+            None => format!("{}\n", pci),
+        })
+        .collect();
+
+    // Tracking is best-effort; failure must not break binding
+    let _ = fs::write(state_file, content);
+}
+
+/// Serializes read-modify-write of the managed state file across parallel binds
+static MANAGED_STATE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Record that this tool bound a device to vfio-pci
+/// A device that is already recorded keeps its original bind time
+fn record_managed_device(state_file: &str, pci_address: &str) {
+    let _guard = MANAGED_STATE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut entries = load_managed_entries(state_file);
+    if !entries.iter().any(|(d, _)| d == pci_address) {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .ok();
+        entries.push((pci_address.to_string(), now));
+        save_managed_entries(state_file, &entries);
+    }
+}
+
+/// Forget a device once it is unbound from vfio-pci
+fn forget_managed_device(pci_address: &str) {
+    let _guard = MANAGED_STATE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut entries = load_managed_entries(MANAGED_STATE_FILE);
+    if entries.iter().any(|(d, _)| d == pci_address) {
+        entries.retain(|(d, _)| d != pci_address);
+        save_managed_entries(MANAGED_STATE_FILE, &entries);
+    }
+}
+
+/// Where a device's bound-since time came from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoundSinceSource {
+    /// Recorded by vfio-tool when it bound the device
+    Recorded,
+    /// mtime of the sysfs driver symlink (device bound by other means)
+    DriverLink,
+}
+
+/// Unix time a vfio-pci device was bound, if known
+/// Falls back to the driver symlink's mtime for devices vfio-tool didn't bind
+pub fn bound_since(pci_address: &str) -> Option<(u64, BoundSinceSource)> {
+    if !is_bound_to_vfio(pci_address) {
+        return None;
+    }
+
+    let recorded = load_managed_entries(MANAGED_STATE_FILE)
+        .into_iter()
+        .find(|(pci, _)| pci == pci_address)
+        .and_then(|(_, bound_at)| bound_at);
+    if let Some(t) = recorded {
+        return Some((t, BoundSinceSource::Recorded));
+    }
+
+    fs::symlink_metadata(format!("/sys/bus/pci/devices/{}/driver", pci_address))
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| (d.as_secs(), BoundSinceSource::DriverLink))
 }
 
 /// Bind device by PCI address directly (without interface name)
-fn bind_by_pci_address(pci_address: &str) -> Result<()> {
+/// `name` is what the user asked for (interface or PCI address), used in messages;
+/// endpoints in `batch` are being bound too and may complete the device's IOMMU group.
+fn bind_by_pci_address(name: &str, pci_address: &str, batch: &[String]) -> Result<()> {
     // Check if device exists
     let device_path_str = format!("/sys/bus/pci/devices/{}", pci_address);
     let device_path = Path::new(&device_path_str);
@@ -454,23 +1499,25 @@ fn bind_by_pci_address(pci_address: &str) -> Result<()> {
         return Ok(());
     }
 
+    check_vfio_driver_registered()?;
+    check_group_complete(name, pci_address, batch)?;
+
     // Unbind from current driver if any
     let driver_path = format!("/sys/bus/pci/devices/{}/driver", pci_address);
-    if Path::new(&driver_path).exists() {
-        if let Ok(target) = fs::read_link(&driver_path) {
-            if let Some(driver_name) = target.file_name() {
-                let driver = driver_name.to_string_lossy();
-                unbind_pci_device(pci_address)?;
-                println!("  {} Unbound from {}", "✓".bright_green(), driver);
-            }
-        }
+    if Path::new(&driver_path).exists()
+        && let Ok(target) = fs::read_link(&driver_path)
+        && let Some(driver_name) = target.file_name()
+    {
+        let driver = driver_name.to_string_lossy();
+        unbind_pci_device(pci_address)?;
+        println!("  {} Unbound from {}", "✓".bright_green(), driver);
     }
 
     // Register device ID with VFIO
     register_device_id(&vendor, &device)?;
 
     // Bind to vfio-pci
-    bind_pci_device(pci_address)?;
+    bind_and_record(pci_address)?;
     println!("  {} Bound to vfio-pci", "✓".bright_green());
 
     Ok(())
@@ -509,25 +1556,50 @@ fn unbind_by_pci_address(pci_address: &str) -> Result<()> {
 /// Try to find a PCI address for an interface name
 fn find_pci_address_in_vfio(interface: &str) -> Option<String> {
     // Strategy 1: Check the saved config for interface->PCI mappings
+    if let Ok(config) = crate::config::load_config()
+        && let Some(pci_addr) = config.devices.pci_mappings.get(interface)
+    {
+        return Some(pci_addr.clone());
+    }
+
+    // Strategy 1b: The name is a saved altname of a mapped interface
     if let Ok(config) = crate::config::load_config() {
-        if let Some(pci_addr) = config.devices.pci_mappings.get(interface) {
+        let primary = config.devices.altnames
+            .iter()
+            .find(|(_, names)| names.iter().any(|n| n == interface))
+            .and_then(|(iface, _)| config.devices.pci_mappings.get(iface));
+        if let Some(pci_addr) = primary {
             return Some(pci_addr.clone());
         }
     }
 
     // Strategy 2: Check if interface still exists in /sys/class/net
     let net_link = format!("/sys/class/net/{}/device", interface);
-    if let Ok(target) = fs::read_link(&net_link) {
-        if let Some(device_name) = target.file_name() {
-            return Some(device_name.to_string_lossy().to_string());
+    if let Ok(target) = fs::read_link(&net_link) && let Some(device_name) = target.file_name() {
+        let device_name = device_name.to_string_lossy().to_string();
+        // USB and virtual NICs hang off another bus and have no PCI address
+        if device::is_valid_pci_address(&device_name) {
+            return Some(device_name);
         }
     }
 
     None
 }
 
-/// Save PCI mappings to config file
-fn save_pci_mappings(mappings: &std::collections::HashMap<String, String>) -> Result<()> {
+/// Read the altnames of mapped interfaces while they still have a netdev
+fn capture_altnames(mappings: &std::collections::HashMap<String, String>) -> std::collections::HashMap<String, Vec<String>> {
+    mappings
+        .keys()
+        .map(|iface| (iface.clone(), device::get_interface_altnames(iface)))
+        .filter(|(_, names)| !names.is_empty())
+        .collect()
+}
+
+/// Save PCI mappings (and the interfaces' altnames) to config file
+fn save_pci_mappings(
+    mappings: &std::collections::HashMap<String, String>,
+    altnames: &std::collections::HashMap<String, Vec<String>>,
+) -> Result<()> {
     // Load existing config
     let mut config = crate::config::load_config().unwrap_or_default();
 
@@ -535,6 +1607,9 @@ fn save_pci_mappings(mappings: &std::collections::HashMap<String, String>) -> Re
     for (iface, pci) in mappings {
         config.devices.pci_mappings.insert(iface.clone(), pci.clone());
     }
+    for (iface, names) in altnames {
+        config.devices.altnames.insert(iface.clone(), names.clone());
+    }
 
     // Save back
     crate::config::save_config_raw(&config)?;
@@ -543,7 +1618,7 @@ fn save_pci_mappings(mappings: &std::collections::HashMap<String, String>) -> Re
 }
 
 /// Set permissions on VFIO device nodes
-fn set_vfio_permissions() -> Result<()> {
+pub fn set_vfio_permissions() -> Result<()> {
     println!("{}", "Setting VFIO device permissions...".bright_cyan());
 
     let vfio_dir = Path::new("/dev/vfio");
@@ -602,12 +1677,108 @@ fn list_vfio_devices() -> Result<()> {
     Ok(())
 }
 
+/// End-to-end check that a device is usable through VFIO right now
+/// Opens the container and group, checks group viability and that all group members are bound
+pub fn verify(target: &str) -> Result<bool> {
+    let config = crate::config::load_config().ok();
+
+    // Resolve interface name or PCI address
+    let dev = if device::is_valid_pci_address(target) {
+        device::get_device_info_by_pci(target, &config)?
+    } else {
+        match device::get_device_info(target) {
+            Ok(dev) => dev,
+            Err(_) => {
+                let pci_addr = find_pci_address_in_vfio(target)
+                    .ok_or_else(|| anyhow::anyhow!("Interface {} not found", target))?;
+                device::get_device_info_by_pci(&pci_addr, &config)?
+            }
+        }
+    };
+
+    println!("{} {} ({})", "Verifying".bright_cyan(), target.bright_white(), dev.pci_address);
+    println!();
+
+    let mut usable = true;
+
+    if dev.is_vfio_bound() {
+        println!("  {} Bound to vfio-pci", "✓".bright_green());
+    } else {
+        println!("  {} Not bound to vfio-pci (driver: {})", "✗".bright_red(),
+            dev.driver.as_deref().unwrap_or("none"));
+        usable = false;
+    }
+
+    let Some(group) = dev.iommu_group else {
+        println!("  {} No IOMMU group - is IOMMU enabled?", "✗".bright_red());
+        println!();
+        println!("{}", format!("✗ {} is NOT usable via VFIO", target).bright_red().bold());
+        return Ok(false);
+    };
+
+    // Group membership: every endpoint must be bound to vfio-pci (or have no driver)
+    let members = device::get_iommu_group_devices(group).unwrap_or_default();
+    let unbound_members: Vec<String> = members
+        .iter()
+        .filter(|m| device::is_endpoint(m))
+        .filter(|m| matches!(device::get_driver(m).as_deref(), Some(d) if d != "vfio-pci"))
+        .cloned()
+        .collect();
+
+    if unbound_members.is_empty() {
+        println!("  {} All {} member(s) of IOMMU group {} bound to vfio-pci", "✓".bright_green(), members.len(), group);
+    } else {
+        println!("  {} IOMMU group {} members on other drivers: {}", "✗".bright_red(), group, unbound_members.join(", "));
+        usable = false;
+    }
+
+    match crate::iommu::probe_group(group) {
+        Ok(probe) => {
+            println!("  {} Opened /dev/vfio/vfio and /dev/vfio/{}", "✓".bright_green(), group);
+            if probe.viable {
+                println!("  {} Group {} is viable", "✓".bright_green(), group);
+            } else {
+                println!("  {} Group {} is NOT viable", "✗".bright_red(), group);
+                usable = false;
+            }
+            if probe.container_set {
+                println!("  {} Group already attached to a container", "ℹ".bright_blue());
+            }
+        }
+        Err(e) => {
+            println!("  {} {}", "✗".bright_red(), e);
+            usable = false;
+        }
+    }
+
+    println!();
+    if usable {
+        println!("{}", format!("✓ {} is usable via VFIO", target).bright_green().bold());
+    } else {
+        println!("{}", format!("✗ {} is NOT usable via VFIO", target).bright_red().bold());
+    }
+
+    Ok(usable)
+}
+
+/// Report an interface that does not exist (a warning with `warn_missing`)
+fn print_not_found(interface: &str, warn_missing: bool) {
+    if warn_missing {
+        println!("{} {} - {}", "⚠".bright_yellow(), interface.bright_white(), "not found (allowed by --warn-missing)".bright_yellow());
+    } else {
+        println!("{} {} - {}", "✗".bright_red(), interface.bright_white(), "INTERFACE NOT FOUND".bright_red().bold());
+    }
+}
+
 /// Check interfaces with specific mode requirements
 /// Exit codes: 0 = all good, 1 = not found, 2 = wrong mode, 3 = other error
-pub fn check_interfaces_with_mode(vfio_ifaces: &[&str], kernel_ifaces: &[&str], existence_ifaces: &[&str]) -> Result<()> {
+/// With `warn_missing`, absent interfaces are reported but do not fail the check;
+/// interfaces that exist in the wrong mode still do
+pub fn check_interfaces_with_mode(vfio_ifaces: &[&str], kernel_ifaces: &[&str], existence_ifaces: &[&str], warn_missing: bool) -> Result<()> {
     println!("{}", "Checking interfaces...".bright_cyan());
     println!();
 
+    let mut missing = 0;
     let mut all_ok = true;
     let mut not_found = false;
     let mut wrong_mode = false;
@@ -640,10 +1811,21 @@ pub fn check_interfaces_with_mode(vfio_ifaces: &[&str], kernel_ifaces: &[&str],
                         wrong_mode = true;
                     }
                 }
-                Err(_) => {
-                    println!("{} {} - {}", "✗".bright_red(), interface.bright_white(), "INTERFACE NOT FOUND".bright_red().bold());
+                Err(e) if device::interface_exists(interface) => {
+                    // No PCI device behind it, so it can never be in VFIO mode
+                    println!("{} {} - {}", "✗".bright_red(), interface.bright_white(), "NOT in VFIO mode".bright_red());
+                    println!("  {:#}", e);
                     all_ok = false;
-                    not_found = true;
+                    wrong_mode = true;
+                }
+                Err(_) => {
+                    print_not_found(interface, warn_missing);
+                    if warn_missing {
+                        missing += 1;
+                    } else {
+                        all_ok = false;
+                        not_found = true;
+                    }
                 }
             }
         }
@@ -678,10 +1860,19 @@ pub fn check_interfaces_with_mode(vfio_ifaces: &[&str], kernel_ifaces: &[&str],
                         wrong_mode = true;
                     }
                 }
+                Err(e) if device::interface_exists(interface) => {
+                    // Not a PCI device: it stays on its kernel driver
+                    println!("{} {} - {}", "✓".bright_green(), interface.bright_white(), "kernel mode".bright_yellow());
+                    println!("  {:#}", e);
+                }
                 Err(_) => {
-                    println!("{} {} - {}", "✗".bright_red(), interface.bright_white(), "INTERFACE NOT FOUND".bright_red().bold());
-                    all_ok = false;
-                    not_found = true;
+                    print_not_found(interface, warn_missing);
+                    if warn_missing {
+                        missing += 1;
+                    } else {
+                        all_ok = false;
+                        not_found = true;
+                    }
                 }
             }
         }
@@ -705,17 +1896,28 @@ pub fn check_interfaces_with_mode(vfio_ifaces: &[&str], kernel_ifaces: &[&str],
                         dev.driver.as_deref().unwrap_or("none")
                     );
                 }
+                Err(e) if device::interface_exists(interface) => {
+                    println!("{} {} - exists in {} mode", "✓".bright_green(), interface.bright_white(), "kernel".bright_yellow());
+                    println!("  {:#}", e);
+                }
                 Err(_) => {
-                    println!("{} {} - {}", "✗".bright_red(), interface.bright_white(), "INTERFACE NOT FOUND".bright_red().bold());
-                    all_ok = false;
-                    not_found = true;
+                    print_not_found(interface, warn_missing);
+                    if warn_missing {
+                        missing += 1;
+                    } else {
+                        all_ok = false;
+                        not_found = true;
+                    }
                 }
             }
         }
         println!();
     }
 
-    if all_ok {
+    if all_ok && missing > 0 {
+        println!("{}", format!("✓ All present interfaces passed ({} missing, ignored)", missing).bright_green().bold());
+        Ok(())
+    } else if all_ok {
         println!("{}", "✓ All interface checks passed".bright_green().bold());
         Ok(())
     } else if not_found {
@@ -800,20 +2002,114 @@ pub fn check_interfaces(interfaces: &[&str]) -> Result<()> {
     }
 }
 
+/// Device info for an interface name or PCI address, including VFIO-bound devices
+pub fn resolve_device(target: &str, config: &Option<Config>) -> Result<NetworkDevice> {
+    if device::is_valid_pci_address(target) {
+        return device::get_device_info_by_pci(target, config);
+    }
+
+    // Keep the netdev lookup's error (e.g. not a PCI device) when there's no mapping either
+    device::get_device_info(target).or_else(|e| match find_pci_address_in_vfio(target) {
+        Some(pci) => device::get_device_info_by_pci(&pci, config),
+        None => Err(e),
+    })
+}
+
+/// Put a device in the state a framework needs (VFIO or its kernel driver) and print the
+/// reference the application should use
+/// Kernel frameworks may only recognise a device once its kernel driver is back (XDP needs
+/// the netdev), so for those capability is checked again after the driver returns.
+pub fn use_for_framework(target: &str, framework: &Framework, force: bool) -> Result<()> {
+    let config = crate::config::load_config().ok();
+    let dev = resolve_device(target, &config)?;
+    let label = if dev.pci_address == target { target.to_string() } else { format!("{} ({})", target, dev.pci_address) };
+
+    if let Some(reason) = frameworks::incapable_reason(&dev, framework)
+        && (framework.requires_vfio() || dev.status == DeviceStatus::Kernel)
+    {
+        anyhow::bail!("{} can't be used for {}: {}", label, framework.name(), reason);
+    }
+
+    if frameworks::is_device_ready(&dev, framework) {
+        println!("{}", format!("✓ {} is already ready for {}", target, framework.name()).bright_green());
+    } else if framework.requires_vfio() {
+        ensure_vfio(&[target], force)?;
+    } else {
+        unbind_interfaces(&[dev.pci_address.as_str()], true)?;
+    }
+
+    let dev = device::get_device_info_by_pci(&dev.pci_address, &config)?;
+    if let Some(reason) = frameworks::incapable_reason(&dev, framework) {
+        anyhow::bail!("{} can't be used for {}: {}", label, framework.name(), reason);
+    }
+    if !frameworks::is_device_ready(&dev, framework) {
+        anyhow::bail!("{} is still not ready for {} (driver {})",
+            label, framework.name(), dev.driver.as_deref().unwrap_or("(none)"));
+    }
+
+    let reference = frameworks::get_reference_string(&dev, framework)
+        .unwrap_or_else(|_| dev.pci_address.clone());
+    println!();
+    println!("{} {} ready for {}: {}", "✓".bright_green(), target, framework.name(), reference.bright_white());
+
+    Ok(())
+}
+
 /// Ensure interfaces are in VFIO mode, binding them if necessary
+/// Accepts interface names and PCI addresses (e.g. 0000:01:00.0)
+/// Without `force`, nothing is bound if any of the devices fails `check_bind_safety`.
 /// Exit codes: 0 = success, 1 = not found, 2 = failed to bind, 3 = other error
-pub fn ensure_vfio(interfaces: &[&str]) -> Result<()> {
+pub fn ensure_vfio(interfaces: &[&str], force: bool) -> Result<()> {
     println!("{}", "Ensuring interfaces are in VFIO mode...".bright_cyan());
     println!();
 
+    // Devices requested together may complete each other's IOMMU groups
+    let batch: Vec<String> = interfaces.iter().filter_map(|i| target_pci_address(i)).collect();
+    if !force {
+        check_bind_safety(&batch)?;
+    }
+
     // Load VFIO module if not loaded
     ensure_vfio_module_loaded()?;
 
+    let config = crate::config::load_config().ok();
+
     let mut all_ok = true;
     let mut not_found = false;
     let mut bind_failed = false;
 
     for interface in interfaces {
+        if device::is_valid_pci_address(interface) {
+            // PCI address - works even when no kernel interface exists (already VFIO-bound)
+            match device::get_device_info_by_pci(interface, &config) {
+                Ok(dev) => {
+                    if dev.status == DeviceStatus::Vfio {
+                        println!("{} {} - {}", "✓".bright_green(), interface.bright_white(), "already in VFIO mode".bright_green());
+                    } else {
+                        println!("{} {} - {}", "○".bright_yellow(), interface.bright_white(), "not in VFIO mode, binding...".bright_yellow());
+
+                        match bind_by_pci_address(interface, interface, &batch) {
+                            Ok(()) => {
+                                println!("  {} {} bound to vfio-pci", "✓".bright_green(), interface);
+                            }
+                            Err(e) => {
+                                println!("  {} Failed to bind {}: {}", "✗".bright_red(), interface, e);
+                                all_ok = false;
+                                bind_failed = true;
+                            }
+                        }
+                    }
+                }
+                Err(_) => {
+                    println!("{} {} - {}", "✗".bright_red(), interface.bright_white(), "PCI DEVICE NOT FOUND".bright_red().bold());
+                    all_ok = false;
+                    not_found = true;
+                }
+            }
+            println!();
+            continue;
+        }
+
         match device::get_device_info(interface) {
             Ok(dev) => {
                 if dev.status == DeviceStatus::Vfio {
@@ -821,7 +2117,7 @@ pub fn ensure_vfio(interfaces: &[&str]) -> Result<()> {
                 } else {
                     println!("{} {} - {}", "○".bright_yellow(), interface.bright_white(), "currently in kernel mode, binding...".bright_yellow());
 
-                    match bind_device(&dev) {
+                    match bind_device(&dev, &batch) {
                         Ok(()) => {
                             println!("  {} {} ({}) bound to vfio-pci", "✓".bright_green(), interface, dev.pci_address);
                         }
@@ -833,6 +2129,12 @@ pub fn ensure_vfio(interfaces: &[&str]) -> Result<()> {
                     }
                 }
             }
+            Err(e) if device::interface_exists(interface) => {
+                println!("{} {} - {}", "✗".bright_red(), interface.bright_white(), "CANNOT BE BOUND".bright_red().bold());
+                println!("  {:#}", e);
+                all_ok = false;
+                bind_failed = true;
+            }
             Err(_) => {
                 println!("{} {} - {}", "✗".bright_red(), interface.bright_white(), "INTERFACE NOT FOUND".bright_red().bold());
                 all_ok = false;
@@ -855,3 +2157,52 @@ pub fn ensure_vfio(interfaces: &[&str]) -> Result<()> {
         anyhow::bail!("Unknown error ensuring VFIO mode")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_bound_device_is_recorded() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_file = dir.path().join("managed");
+        let state_file = state_file.to_str().unwrap();
+
+        // register_device_id had vfio-pci claim the device, so the bind returns without writing
+        bind_recorded("0000:01:00.0", state_file, |_| Ok(())).unwrap();
+
+        let entries = load_managed_entries(state_file);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].0, "0000:01:00.0");
+        assert!(entries[0].1.is_some());
+    }
+
+    #[test]
+    fn failed_bind_is_not_recorded() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_file = dir.path().join("managed");
+        let state_file = state_file.to_str().unwrap();
+
+        assert!(bind_recorded("0000:01:00.0", state_file, |_| anyhow::bail!("busy")).is_err());
+        assert!(load_managed_entries(state_file).is_empty());
+    }
+
+    #[test]
+    fn binding_a_non_pci_netdev_says_why() {
+        // The loopback netdev always exists and has no device behind it
+        let err = bind_named("lo", &[]).unwrap_err().to_string();
+        assert!(err.contains("not a physical device"), "{}", err);
+        assert!(!err.contains("not found"), "{}", err);
+    }
+
+    #[test]
+    fn rebinding_keeps_the_first_bind_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_file = dir.path().join("managed");
+        let state_file = state_file.to_str().unwrap();
+        fs::write(state_file, "0000:01:00.0 1000\n").unwrap();
+
+        bind_recorded("0000:01:00.0", state_file, |_| Ok(())).unwrap();
+        assert_eq!(load_managed_entries(state_file), [("0000:01:00.0".to_string(), Some(1000))]);
+    }
+}
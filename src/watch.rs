@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use anyhow::Result;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::device::{self, DeviceStatus, NetworkDevice};
+
+/// Observed state of a device between polls
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceState {
+    pub interface: String,
+    pub status: String,
+    pub driver: Option<String>,
+}
+
+/// A detected state transition for one device
+#[derive(Debug, Clone)]
+pub struct Transition {
+    pub pci_address: String,
+    pub interface: String,
+    pub old_state: String,
+    pub new_state: String,
+}
+
+fn state_name(status: &DeviceStatus) -> &'static str {
+    match status {
+        DeviceStatus::Vfio => "vfio",
+        DeviceStatus::Kernel => "kernel",
+        DeviceStatus::Unbound => "unbound",
+    }
+}
+
+/// Snapshot current device states, keyed by PCI address
+pub fn poll_states() -> Result<HashMap<String, DeviceState>> {
+    let devices: Vec<NetworkDevice> = device::list_network_devices()?;
+
+    Ok(devices
+        .into_iter()
+        .map(|d| {
+            let state = DeviceState {
+                interface: d.interface,
+                status: state_name(&d.status).to_string(),
+                driver: d.driver,
+            };
+            (d.pci_address, state)
+        })
+        .collect())
+}
+
+/// Compare two polls and return the transitions (devices appearing/disappearing included)
+pub fn diff_states(old: &HashMap<String, DeviceState>, new: &HashMap<String, DeviceState>) -> Vec<Transition> {
+    let mut transitions = Vec::new();
+
+    for (pci, new_state) in new {
+        let old_status = old.get(pci).map(|s| s.status.as_str()).unwrap_or("absent");
+        let old_driver = old.get(pci).and_then(|s| s.driver.clone());
+
+        if old_status == new_state.status && old_driver == new_state.driver {
+            continue;
+        }
+
+        // Devices on vfio-pci lose their interface name, so prefer the last known real name
+        let interface = match old.get(pci) {
+            Some(old_state) if new_state.interface.starts_with('(') => old_state.interface.clone(),
+            _ => new_state.interface.clone(),
+        };
+
+        transitions.push(Transition {
+            pci_address: pci.clone(),
+            interface,
+            old_state: old_status.to_string(),
+            new_state: new_state.status.clone(),
+        });
+    }
+
+    for (pci, old_state) in old {
+        if !new.contains_key(pci) {
+            transitions.push(Transition {
+                pci_address: pci.clone(),
+                interface: old_state.interface.clone(),
+                old_state: old_state.status.clone(),
+                new_state: "absent".to_string(),
+            });
+        }
+    }
+
+    transitions.sort_by(|a, b| a.pci_address.cmp(&b.pci_address));
+    transitions
+}
+
+/// Run the on-change hook for a transition
+/// The command runs via `sh -c` with the interface and new state as $1 and $2,
+/// and VFIO_TOOL_{INTERFACE,PCI,OLD_STATE,NEW_STATE} in the environment.
+/// Failures are logged and never abort the watch loop.
+/// With `json`, the hook's stdout goes to stderr so the event stream stays clean.
+fn run_hook(command: &str, transition: &Transition, json: bool) {
+    let mut hook = Command::new("sh");
+    if json {
+        hook.stdout(std::io::stderr());
+    }
+
+    let result = hook
+        .arg("-c")
+        .arg(command)
+        .arg("vfio-tool-hook")
+        .arg(&transition.interface)
+        .arg(&transition.new_state)
+        .env("VFIO_TOOL_INTERFACE", &transition.interface)
+        .env("VFIO_TOOL_PCI", &transition.pci_address)
+        .env("VFIO_TOOL_OLD_STATE", &transition.old_state)
+        .env("VFIO_TOOL_NEW_STATE", &transition.new_state)
+        .status();
+
+    match result {
+        Ok(status) if status.success() => {}
+        Ok(status) => {
+            eprintln!("  {} on-change command failed for {} ({})", "⚠".bright_yellow(), transition.interface, status);
+        }
+        Err(e) => {
+            eprintln!("  {} Failed to run on-change command for {}: {}", "⚠".bright_yellow(), transition.interface, e);
+        }
+    }
+}
+
+/// Print a transition as a single-line JSON event
+fn print_json_event(transition: &Transition) {
+    let ts = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+
+    let event = json!({
+        "ts": ts,
+        "interface": transition.interface,
+        "pci": transition.pci_address,
+        "from": transition.old_state,
+        "to": transition.new_state,
+    });
+    println!("{}", event);
+}
+
+/// Poll device states and report (and optionally act on) every transition
+/// With `json`, stdout carries only newline-delimited event objects.
+pub fn watch(interval_secs: u64, on_change: Option<&str>, json: bool) -> Result<()> {
+    let interval = Duration::from_secs(interval_secs.max(1));
+    let mut previous = poll_states()?;
+
+    if !json {
+        println!("{}", format!("Watching {} device(s) every {}s (Ctrl-C to stop)...", previous.len(), interval.as_secs()).bright_cyan());
+        if let Some(cmd) = on_change {
+            println!("  On change: {}", cmd.bright_white());
+        }
+    }
+
+    loop {
+        thread::sleep(interval);
+
+        let current = match poll_states() {
+            Ok(states) => states,
+            Err(e) => {
+                eprintln!("  {} Failed to read device states: {}", "⚠".bright_yellow(), e);
+                continue;
+            }
+        };
+
+        for transition in diff_states(&previous, &current) {
+            if json {
+                print_json_event(&transition);
+            } else {
+                println!(
+                    "  {} {} ({}): {} → {}",
+                    "~".bright_yellow(),
+                    transition.interface.bright_white(),
+                    transition.pci_address,
+                    transition.old_state,
+                    transition.new_state.bright_green()
+                );
+            }
+
+            if let Some(cmd) = on_change {
+                run_hook(cmd, &transition, json);
+            }
+        }
+
+        previous = current;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(interface: &str, status: &str, driver: Option<&str>) -> DeviceState {
+        DeviceState {
+            interface: interface.to_string(),
+            status: status.to_string(),
+            driver: driver.map(String::from),
+        }
+    }
+
+    fn poll(entries: &[(&str, DeviceState)]) -> HashMap<String, DeviceState> {
+        entries.iter().map(|(pci, s)| (pci.to_string(), s.clone())).collect()
+    }
+
+    #[test]
+    fn unchanged_devices_produce_no_transitions() {
+        let states = poll(&[("0000:01:00.0", state("eth0", "kernel", Some("ixgbe")))]);
+        assert!(diff_states(&states, &states).is_empty());
+    }
+
+    #[test]
+    fn bind_keeps_the_last_known_interface_name() {
+        let old = poll(&[("0000:01:00.0", state("eth0", "kernel", Some("ixgbe")))]);
+        let new = poll(&[("0000:01:00.0", state("(0000:01:00.0)", "vfio", Some("vfio-pci")))]);
+
+        let transitions = diff_states(&old, &new);
+        assert_eq!(transitions.len(), 1);
+        assert_eq!(transitions[0].interface, "eth0");
+        assert_eq!(transitions[0].old_state, "kernel");
+        assert_eq!(transitions[0].new_state, "vfio");
+    }
+
+    #[test]
+    fn driver_change_within_a_status_is_a_transition() {
+        let old = poll(&[("0000:01:00.0", state("eth0", "kernel", Some("ixgbe")))]);
+        let new = poll(&[("0000:01:00.0", state("eth0", "kernel", Some("ixgbevf")))]);
+        assert_eq!(diff_states(&old, &new).len(), 1);
+    }
+
+    #[test]
+    fn appearing_and_disappearing_devices_sorted_by_address() {
+        let old = poll(&[("0000:02:00.0", state("eth1", "kernel", Some("igb")))]);
+        let new = poll(&[("0000:01:00.0", state("eth0", "unbound", None))]);
+
+        let transitions = diff_states(&old, &new);
+        let summary: Vec<(&str, &str, &str)> = transitions
+            .iter()
+            .map(|t| (t.pci_address.as_str(), t.old_state.as_str(), t.new_state.as_str()))
+            .collect();
+        assert_eq!(summary, [
+            ("0000:01:00.0", "absent", "unbound"),
+            ("0000:02:00.0", "kernel", "absent"),
+        ]);
+    }
+}